@@ -0,0 +1,80 @@
+//! Benchmarks of the read path, run with `cargo bench --features bench`.
+//!
+//! Constructing a `SkipEncodingBom` does not read anything: the BOM detection happens on the first read, so each
+//! benchmark measures the construction together with the reads, and inputs and outputs go through `black_box`.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use skip_bom::{detect, BomType, DetectOptions, SkipEncodingBom};
+use std::io::Read;
+
+/// Reader returning a single byte for each read.
+struct ByteByByteReader<'a>(&'a [u8]);
+
+impl Read for ByteByByteReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let count = buf.len().min(self.0.len()).min(1);
+        buf[..count].copy_from_slice(&self.0[..count]);
+        self.0 = &self.0[count..];
+        Ok(count)
+    }
+}
+
+fn stream(bom_type: Option<BomType>, content_len: usize) -> Vec<u8> {
+    let mut bytes = bom_type.map_or(&[][..], |bom_type| bom_type.bom_bytes()).to_vec();
+    bytes.extend((0..content_len).map(|i| b'a' + (i % 26) as u8));
+    bytes
+}
+
+/// Read the whole stream with buffers of `buffer_len` bytes.
+fn read_all<R: Read>(mut reader: R, buffer_len: usize) -> usize {
+    let mut buf = vec![0u8; buffer_len];
+    let mut total = 0;
+    loop {
+        match reader.read(&mut buf).unwrap() {
+            0 => break total,
+            bytes_read => total += black_box(&buf[..bytes_read]).len(),
+        }
+    }
+}
+
+fn small_buffer_reads(c: &mut Criterion) {
+    let bytes = stream(Some(BomType::UTF8), 64 * 1024);
+    let mut group = c.benchmark_group("small_buffer_reads");
+    group.throughput(Throughput::Bytes(bytes.len() as u64));
+    for buffer_len in [1, 16, 64] {
+        group.bench_with_input(BenchmarkId::new("skip_encoding_bom", buffer_len), &buffer_len, |b, buffer_len| {
+            b.iter(|| read_all(SkipEncodingBom::new(BomType::all(), black_box(bytes.as_slice())), *buffer_len))
+        });
+    }
+    group.finish();
+}
+
+fn detection_byte_by_byte(c: &mut Criterion) {
+    let mut group = c.benchmark_group("detection_byte_by_byte");
+    for bom_type in [None, Some(BomType::UTF8), Some(BomType::UTF16LE), Some(BomType::UTF32BE), Some(BomType::GB1803)] {
+        let bytes = stream(bom_type, 16);
+        group.bench_with_input(BenchmarkId::from_parameter(format!("{:?}", bom_type)), &bytes, |b, bytes| {
+            b.iter(|| SkipEncodingBom::new(BomType::all(), ByteByByteReader(black_box(bytes.as_slice()))).read_bom().unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn steady_state(c: &mut Criterion) {
+    let bytes = stream(Some(BomType::UTF8), 1024 * 1024);
+    let mut group = c.benchmark_group("steady_state");
+    group.throughput(Throughput::Bytes(bytes.len() as u64));
+    group.bench_function("baseline", |b| b.iter(|| read_all(black_box(bytes.as_slice()), 4096)));
+    group.bench_function("skip_encoding_bom", |b| {
+        b.iter(|| read_all(SkipEncodingBom::new(BomType::all(), black_box(bytes.as_slice())), 4096))
+    });
+    group.finish();
+}
+
+fn detect_sample(c: &mut Criterion) {
+    let bytes = stream(None, 4096);
+    c.bench_function("detect", |b| b.iter(|| detect(black_box(bytes.as_slice()), &DetectOptions::default()).unwrap()));
+}
+
+criterion_group!(benches, small_buffer_reads, detection_byte_by_byte, steady_state, detect_sample);
+criterion_main!(benches);
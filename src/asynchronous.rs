@@ -0,0 +1,11 @@
+//! Asynchronous readers and streams skipping the BOM, and web framework integrations. The module is not named `async`,
+//! which is a reserved keyword.
+
+#[cfg(feature = "tokio")]
+pub use super::{poll_skip_bom, AsyncSkipEncodingBom, BomPollState};
+
+#[cfg(feature = "futures-core")]
+pub use super::SkipBomChunks;
+
+#[cfg(feature = "axum")]
+pub use super::{BomCleanText, BomCleanTextRejection};
@@ -0,0 +1,49 @@
+use super::BomType;
+
+/// The character encoded by every BOM: U+FEFF ZERO WIDTH NO-BREAK SPACE.
+///
+/// Once a stream is decoded, a remaining BOM appears as this character at the start of the text.
+pub const BOM_CHAR: char = '\u{FEFF}';
+
+impl BomType {
+    /// Returns the character encoded by the BOM, which is [`BOM_CHAR`] for every BOM type.
+    pub const fn bom_char(&self) -> char {
+        BOM_CHAR
+    }
+
+    /// Returns `true` if the BOM bytes are the complete encoding of [`BOM_CHAR`].
+    ///
+    /// This is the case for every BOM type except UTF-7, whose signature bytes only encode the first bits of U+FEFF:
+    /// the last bits are shared with the next character.
+    pub const fn encodes_feff(&self) -> bool {
+        !matches!(self, BomType::UTF7)
+    }
+}
+
+/// Returns `true` if the decoded text starts with [`BOM_CHAR`].
+pub fn starts_with_bom_char(text: &str) -> bool {
+    text.starts_with(BOM_CHAR)
+}
+
+/// Returns the decoded text without its leading [`BOM_CHAR`] if there is one.
+///
+/// # Examples
+/// ```
+/// use skip_bom::strip_bom_char;
+///
+/// assert_eq!("text", strip_bom_char("\u{FEFF}text"));
+/// assert_eq!("text\u{FEFF}", strip_bom_char("text\u{FEFF}"));
+/// ```
+pub fn strip_bom_char(text: &str) -> &str {
+    text.strip_prefix(BOM_CHAR).unwrap_or(text)
+}
+
+/// Remove the leading [`BOM_CHAR`] of a decoded string in place, returning `true` if there was one.
+pub fn remove_bom_char(text: &mut String) -> bool {
+    if starts_with_bom_char(text) {
+        let _ = text.drain(..BOM_CHAR.len_utf8());
+        true
+    } else {
+        false
+    }
+}
@@ -1,6 +1,7 @@
+use std::fmt::{self, Debug, Formatter};
 use std::io::{Cursor, Read};
 
-use super::{BomsBytesTest, BomType, BomBytesArray, BomBytesPushBuffer, Result};
+use super::{checked_read, BomsBytesTest, BomType, BomBytesArray, BomBytesPushBuffer, Result};
 
 /// Reader BOM skipping state
 #[derive(Debug, Clone)]
@@ -21,6 +22,20 @@ pub enum BomState {
     Final(Option<BomType>),
 }
 
+/// With the `zeroize` feature, the start bytes are zeroed when the state is dropped, including when it is replaced by the
+/// next state.
+#[cfg(feature = "zeroize")]
+impl Drop for BomState {
+    fn drop(&mut self) {
+        use zeroize::Zeroize;
+        match self {
+            BomState::Initial { start_bytes } => start_bytes.zeroize(),
+            BomState::PostInitBuffer { bytes_after_bom, .. } => bytes_after_bom.get_mut().zeroize(),
+            BomState::Final(_) => (),
+        }
+    }
+}
+
 impl Default for BomState {
     fn default() -> Self {
         Self::Initial { start_bytes: Default::default() }
@@ -31,32 +46,64 @@ impl BomState {
     pub fn bom_found(&self) -> Option<Option<BomType>> {
         match self {
             BomState::Initial { .. } => None,
-            BomState::PostInitBuffer { bom_type, .. } => Some(bom_type.clone()),
-            BomState::Final(bom_type) => Some(bom_type.clone()),
+            BomState::PostInitBuffer { bom_type, .. } => Some(*bom_type),
+            BomState::Final(bom_type) => Some(*bom_type),
+        }
+    }
+
+    /// Return a [`Debug`] view of the state showing the number of bytes buffered instead of the bytes.
+    pub fn redacted(&self) -> RedactedBomState<'_> {
+        RedactedBomState(self)
+    }
+
+    /// State once the BOM presence and type are determined, with the start bytes read after the BOM.
+    pub fn after_bom(bom_type: Option<BomType>, bytes_after_bom: BomBytesPushBuffer) -> Self {
+        match bytes_after_bom.byte_count() {
+            0 => BomState::Final(bom_type),
+            _ => BomState::PostInitBuffer { bytes_after_bom: Cursor::new(bytes_after_bom), bom_type },
         }
     }
 
-    pub fn try_read_bom<R: Read>(start_bytes: &BomBytesPushBuffer, reader: &mut R, bom_types: &[BomType]) -> Result<TryReadBomResult> {
+    /// State returning the bytes of the BOM found before the bytes buffered after it. The buffered bytes must not have
+    /// been read yet.
+    pub fn with_bom_bytes(self) -> Self {
+        let (bom_type, bytes_after_bom) = match &self {
+            BomState::PostInitBuffer { bom_type: Some(bom_type), bytes_after_bom } => (*bom_type, *bytes_after_bom.get_ref()),
+            BomState::Final(Some(bom_type)) => (*bom_type, BomBytesPushBuffer::default()),
+            _ => return self,
+        };
+        // the BOM and the bytes read after it were all start bytes, so they fit in the buffer
+        let mut bytes = BomBytesPushBuffer::default();
+        let _ = bytes.push_truncated(bom_type.bom_bytes());
+        let _ = bytes.push_truncated(bytes_after_bom.bytes());
+        BomState::PostInitBuffer { bytes_after_bom: Cursor::new(bytes), bom_type: Some(bom_type) }
+    }
+
+    /// Read the next start bytes from `reader`, without exceeding `probe_len` start bytes in total.
+    pub fn try_read_bom<R: Read + ?Sized>(start_bytes: &BomBytesPushBuffer, reader: &mut R, bom_types: &[BomType], probe_len: usize) -> Result<TryReadBomResult> {
         // read into the start_bytes buffer
         let mut new_start_bytes_buffer = BomBytesArray::default();
         let start_bytes_slice = start_bytes.bytes();
         if !start_bytes_slice.is_empty() {
             new_start_bytes_buffer[..start_bytes_slice.len()].copy_from_slice(start_bytes_slice);
         }
-        let read_slice = &mut new_start_bytes_buffer[start_bytes_slice.len()..];
-        let current_bytes_read = reader.read(read_slice)?;
+        let probe_end = probe_len.min(new_start_bytes_buffer.len()).max(start_bytes_slice.len());
+        let read_slice = &mut new_start_bytes_buffer[start_bytes_slice.len()..probe_end];
+        let current_bytes_read = checked_read(reader, read_slice)?;
         let total_bom_bytes_read = start_bytes_slice.len() + current_bytes_read;
-        match BomType::try_find_bytes_bom(&new_start_bytes_buffer[..total_bom_bytes_read], bom_types) {
+        let result = match BomType::try_find_bytes_bom(&new_start_bytes_buffer[..total_bom_bytes_read], bom_types) {
             // the BOM presence was determined
             BomsBytesTest::Complete { bom_type, additional_bytes } => {
-                let bytes_after_bom = BomBytesPushBuffer::from_slice(additional_bytes);
-                Ok(TryReadBomResult::Complete { bom_type, bytes_after_bom })
+                BomBytesPushBuffer::from_slice(additional_bytes).map(|bytes_after_bom| TryReadBomResult::Complete { bom_type, bytes_after_bom })
             },
             BomsBytesTest::Incomplete => {
-                let bytes_after_bom = BomBytesPushBuffer::from_array(new_start_bytes_buffer, total_bom_bytes_read);
-                Ok(TryReadBomResult::Incomplete(bytes_after_bom))
+                BomBytesPushBuffer::from_array(new_start_bytes_buffer, total_bom_bytes_read).map(TryReadBomResult::Incomplete)
             }
-        }
+        };
+        // the bytes were copied to the new state
+        #[cfg(feature = "zeroize")]
+        zeroize::Zeroize::zeroize(&mut new_start_bytes_buffer);
+        result
     }
 }
 
@@ -64,3 +111,19 @@ pub enum TryReadBomResult {
     Incomplete(BomBytesPushBuffer),
     Complete { bom_type: Option<BomType>, bytes_after_bom: BomBytesPushBuffer },
 }
+
+/// [`Debug`] view of a [`BomState`] without the bytes buffered.
+pub struct RedactedBomState<'s>(&'s BomState);
+
+impl Debug for RedactedBomState<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            BomState::Initial { start_bytes } => f.debug_struct("Initial").field("buffered", &start_bytes.byte_count()).finish(),
+            BomState::PostInitBuffer { bytes_after_bom, bom_type } => {
+                let buffered = bytes_after_bom.get_ref().byte_count().saturating_sub(bytes_after_bom.position() as usize);
+                f.debug_struct("PostInitBuffer").field("buffered", &buffered).field("bom_type", bom_type).finish()
+            },
+            BomState::Final(bom_type) => f.debug_tuple("Final").field(bom_type).finish(),
+        }
+    }
+}
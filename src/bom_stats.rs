@@ -0,0 +1,109 @@
+use super::{BomStatus, BomType, EncodingReport};
+
+use std::iter::FromIterator;
+
+/// Accumulator of the BOMs found across many streams, for instance for a data-quality dashboard.
+/// # Examples
+/// ```
+/// use skip_bom::{BomStats, BomStatus, BomType};
+///
+/// let mut stats = BomStats::default();
+/// stats.add(BomStatus::Bom(BomType::UTF8));
+/// stats.add(BomStatus::NoBom);
+/// stats.add(BomStatus::NoBom);
+/// stats.add(BomStatus::Undetermined);
+/// assert_eq!(4, stats.total());
+/// assert_eq!(1, stats.count(BomType::UTF8));
+/// assert_eq!(50.0, stats.percent_no_bom());
+/// assert_eq!(1, stats.undetermined());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BomStats {
+    /// Number of streams per BOM type, indexed by [`BomType::as_u8`].
+    bom_counts: [u64; BOM_TYPE_COUNT],
+    no_bom: u64,
+    undetermined: u64,
+}
+
+/// Number of BOM types.
+const BOM_TYPE_COUNT: usize = 11;
+
+impl BomStats {
+    /// Count a stream with the BOM presence `status`.
+    ///
+    /// [`BomStatus::Undetermined`] counts the ambiguous cases, such as streams truncated within a possible BOM.
+    pub fn add(&mut self, status: BomStatus) {
+        match status {
+            BomStatus::Bom(bom_type) => self.bom_counts[bom_type.as_u8() as usize] += 1,
+            BomStatus::NoBom => self.no_bom += 1,
+            _ => self.undetermined += 1,
+        }
+    }
+
+    /// Count a stream analyzed by [`detect`](crate::detect()).
+    pub fn add_report(&mut self, report: &EncodingReport) {
+        self.add(report.bom.map_or(BomStatus::NoBom, BomStatus::Bom));
+    }
+
+    /// Add the counts of other statistics, for instance gathered by another thread.
+    pub fn merge(&mut self, other: &BomStats) {
+        for (count, other_count) in self.bom_counts.iter_mut().zip(other.bom_counts.iter()) {
+            *count += other_count;
+        }
+        self.no_bom += other.no_bom;
+        self.undetermined += other.undetermined;
+    }
+
+    /// Returns the number of streams starting with a BOM of type `bom_type`.
+    pub fn count(&self, bom_type: BomType) -> u64 {
+        self.bom_counts[bom_type.as_u8() as usize]
+    }
+
+    /// Returns the BOM types found with their number of streams, in the order of the [`BomType`] variants.
+    pub fn counts(&self) -> impl Iterator<Item = (BomType, u64)> + '_ {
+        BomType::SIGNATURES.iter().map(move |(bom_type, _)| (*bom_type, self.count(*bom_type))).filter(|(_, count)| *count != 0)
+    }
+
+    /// Returns the number of streams starting with a BOM.
+    pub fn with_bom(&self) -> u64 {
+        self.bom_counts.iter().sum()
+    }
+
+    /// Returns the number of streams without a BOM.
+    pub fn no_bom(&self) -> u64 {
+        self.no_bom
+    }
+
+    /// Returns the number of streams whose BOM presence could not be determined.
+    pub fn undetermined(&self) -> u64 {
+        self.undetermined
+    }
+
+    /// Returns the number of streams counted.
+    pub fn total(&self) -> u64 {
+        self.with_bom() + self.no_bom + self.undetermined
+    }
+
+    /// Returns the percentage of streams without a BOM, or 0 if no stream was counted.
+    pub fn percent_no_bom(&self) -> f64 {
+        match self.total() {
+            0 => 0.0,
+            total => self.no_bom as f64 * 100.0 / total as f64,
+        }
+    }
+}
+
+impl Extend<BomStatus> for BomStats {
+    fn extend<T: IntoIterator<Item = BomStatus>>(&mut self, iter: T) {
+        iter.into_iter().for_each(|status| self.add(status));
+    }
+}
+
+impl FromIterator<BomStatus> for BomStats {
+    fn from_iter<T: IntoIterator<Item = BomStatus>>(iter: T) -> Self {
+        let mut stats = BomStats::default();
+        stats.extend(iter);
+        stats
+    }
+}
@@ -0,0 +1,68 @@
+use super::BomType;
+
+/// Presence of a BOM at the start of a stream, as determined so far.
+///
+/// This is the same information as the nested [`Option`] returned by [`SkipEncodingBom::bom_found`](crate::SkipEncodingBom::bom_found),
+/// with a variant for each case.
+/// # Examples
+/// ```
+/// use skip_bom::{BomStatus, BomType, SkipEncodingBom};
+/// use std::io::Read;
+///
+/// let mut reader = SkipEncodingBom::new(BomType::all(), &b"\xEF\xBB\xBFThis stream has a BOM."[..]);
+/// assert_eq!(BomStatus::Undetermined, reader.status());
+/// let _ = reader.read_bom().unwrap();
+/// match reader.status() {
+///     BomStatus::Bom(bom_type) => assert_eq!(BomType::UTF8, bom_type),
+///     status => panic!("unexpected status {:?}", status),
+/// }
+/// ```
+#[must_use]
+#[non_exhaustive]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum BomStatus {
+    /// Not enough bytes were read to determine the presence of a BOM.
+    #[default]
+    Undetermined,
+    /// The stream does not start with a BOM.
+    NoBom,
+    /// The stream starts with a BOM of this type.
+    Bom(BomType),
+}
+
+impl BomStatus {
+    /// Returns `true` if the presence of a BOM is determined.
+    pub fn is_determined(&self) -> bool {
+        !matches!(self, BomStatus::Undetermined)
+    }
+
+    /// Returns the BOM type found, if any.
+    pub fn bom_type(&self) -> Option<BomType> {
+        match self {
+            BomStatus::Bom(bom_type) => Some(*bom_type),
+            _ => None,
+        }
+    }
+}
+
+impl From<Option<Option<BomType>>> for BomStatus {
+    fn from(bom_found: Option<Option<BomType>>) -> Self {
+        match bom_found {
+            None => BomStatus::Undetermined,
+            Some(None) => BomStatus::NoBom,
+            Some(Some(bom_type)) => BomStatus::Bom(bom_type),
+        }
+    }
+}
+
+impl From<BomStatus> for Option<Option<BomType>> {
+    fn from(status: BomStatus) -> Self {
+        match status {
+            BomStatus::Undetermined => None,
+            BomStatus::NoBom => Some(None),
+            BomStatus::Bom(bom_type) => Some(Some(bom_type)),
+        }
+    }
+}
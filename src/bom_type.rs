@@ -1,8 +1,15 @@
+use super::{pack_bytes, BomError, BomErrorCode, BomTypeMask, BomTypeSet, ALL_SIGNATURES, FIRST_BYTE_SIGNATURES, SIGNATURES};
+
+use std::convert::TryFrom;
+use std::io::{Error, ErrorKind};
+
 /// Type of encoding BOM.
 /// 
 /// See [the questions about the BOM in the official Unicode FAQ](https://www.unicode.org/faq/utf_bom.html#bom1).
 #[repr(C)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum BomType {
     /// Unicode with the UTF-8 format.
     UTF8,
@@ -29,6 +36,70 @@ pub enum BomType {
 }
 
 impl BomType {
+    /// All the BOM types with their BOM bytes, in the order of the variants, for instance to list the signatures
+    /// in an editor or a hexdump tool.
+    /// # Examples
+    /// ```
+    /// use skip_bom::BomType;
+    ///
+    /// for (bom_type, bytes) in BomType::SIGNATURES {
+    ///     assert_eq!(bom_type.bom_bytes(), *bytes);
+    /// }
+    /// assert_eq!(Some(&(BomType::UTF8, &[0xEF, 0xBB, 0xBF][..])), BomType::SIGNATURES.first());
+    /// ```
+    pub const SIGNATURES: &'static [(BomType, &'static [u8])] = &[
+        (BomType::UTF8, BomType::UTF8.bom_bytes()),
+        (BomType::UTF16LE, BomType::UTF16LE.bom_bytes()),
+        (BomType::UTF16BE, BomType::UTF16BE.bom_bytes()),
+        (BomType::UTF32LE, BomType::UTF32LE.bom_bytes()),
+        (BomType::UTF32BE, BomType::UTF32BE.bom_bytes()),
+        (BomType::UTF7, BomType::UTF7.bom_bytes()),
+        (BomType::UTF1, BomType::UTF1.bom_bytes()),
+        (BomType::UTFEBDIC, BomType::UTFEBDIC.bom_bytes()),
+        (BomType::SCSU, BomType::SCSU.bom_bytes()),
+        (BomType::BOCU1, BomType::BOCU1.bom_bytes()),
+        (BomType::GB1803, BomType::GB1803.bom_bytes()),
+    ];
+
+    /// Returns the stable numeric code of the BOM type, to store it compactly in a database or a wire protocol.
+    ///
+    /// The code is the discriminant of the `#[repr(C)]` enum: it never changes for an existing variant, new variants
+    /// get new codes. See [`BomType::from_u8`] for the reverse conversion.
+    /// # Examples
+    /// ```
+    /// use skip_bom::BomType;
+    ///
+    /// assert_eq!(0, BomType::UTF8.as_u8());
+    /// assert_eq!(Some(BomType::UTF16LE), BomType::from_u8(BomType::UTF16LE.as_u8()));
+    /// ```
+    pub const fn as_u8(&self) -> u8 {
+        *self as u8
+    }
+
+    /// Returns the BOM type of a numeric code returned by [`BomType::as_u8`], [`None`] for an unknown code.
+    pub const fn from_u8(code: u8) -> Option<BomType> {
+        use BomType::*;
+
+        Some(match code {
+            0 => UTF8,
+            1 => UTF16LE,
+            2 => UTF16BE,
+            3 => UTF32LE,
+            4 => UTF32BE,
+            5 => UTF7,
+            6 => UTF1,
+            7 => UTFEBDIC,
+            8 => SCSU,
+            9 => BOCU1,
+            10 => GB1803,
+            _ => return None,
+        })
+    }
+
+    /// Returns the bytes of the BOM.
+    ///
+    /// For UTF-7, these are the bytes all its signatures start with, which are the ones detected and skipped: see
+    /// [`BomType::signature_pattern`].
     pub const fn bom_bytes(&self) -> &'static [u8] {
         use BomType::*;
 
@@ -36,8 +107,8 @@ impl BomType {
             UTF8 => &[0xEF, 0xBB, 0xBF],
             UTF16LE => &[0xFF, 0xFE],
             UTF16BE => &[0xFE, 0xFF],
-            UTF32LE => &[0x00, 0x00, 0xFF, 0xFE],
-            UTF32BE => &[0xFF, 0xFE, 0x00, 0x00],
+            UTF32LE => &[0xFF, 0xFE, 0x00, 0x00],
+            UTF32BE => &[0x00, 0x00, 0xFE, 0xFF],
             UTF7 => &[0x2B, 0x2F, 0x76],
             UTF1 => &[0xF7, 0x64, 0x4C],
             UTFEBDIC => &[0xDD, 0x73, 0x66, 0x73],
@@ -47,10 +118,45 @@ impl BomType {
         }
     }
 
+    /// Returns the number of bytes of the BOM.
     pub const fn bom_length(&self) -> usize {
         self.bom_bytes().len()
     }
 
+    /// Returns the bytes following the BOM if `bytes` starts with it, [`None`] otherwise.
+    ///
+    /// Only this BOM is tested: `FF FE 00 00` starts with the UTF-16LE BOM although it is the UTF-32LE BOM, see
+    /// [`BomType::try_find_bytes_bom`] to find which of several BOMs a stream starts with.
+    /// # Examples
+    /// ```
+    /// use skip_bom::BomType;
+    ///
+    /// assert_eq!(Some(&b"text"[..]), BomType::UTF8.strip_prefix(b"\xEF\xBB\xBFtext"));
+    /// assert_eq!(None, BomType::UTF8.strip_prefix(b"text"));
+    /// ```
+    pub fn strip_prefix<'a>(&self, bytes: &'a [u8]) -> Option<&'a [u8]> {
+        bytes.strip_prefix(self.bom_bytes())
+    }
+
+    /// Returns `true` if the content following the BOM can be converted to a [`String`] with the standard library alone:
+    /// this is the case of UTF-8, UTF-16 and UTF-32.
+    ///
+    /// Content in other encodings requires a dedicated decoder and should not be passed to parsers expecting text.
+    pub const fn is_supported_by_std_string(&self) -> bool {
+        use BomType::*;
+
+        matches!(self, UTF8 | UTF16LE | UTF16BE | UTF32LE | UTF32BE)
+    }
+
+    /// Returns `true` if decoding the content following the BOM requires a dedicated decoder, as neither the standard
+    /// library nor the [`encoding_rs`](https://docs.rs/encoding_rs) crate can decode it: this is the case of UTF-7, UTF-1,
+    /// UTF-EBCDIC, SCSU and BOCU-1.
+    pub const fn requires_external_decoder(&self) -> bool {
+        use BomType::*;
+
+        matches!(self, UTF7 | UTF1 | UTFEBDIC | SCSU | BOCU1)
+    }
+
     /// Returns:
     /// * `BomBytesTest::StartsWithBom` if `tested_bytes` is certain to start with the BOM.
     /// * `BomBytesTest::NotBom` if `tested_bytes` is certain not to be the BOM.
@@ -72,62 +178,128 @@ impl BomType {
         }
     }
 
+    /// The BOMs are matched in the order of [`BomType::all_sorted_by_length`], whatever the order of `bom_types_tested`.
+    ///
     /// Returns:
     /// * `Ok((Some(bom_type), additional_bytes_slice))` if `tested_bytes` is certain to start with the `bom_type` BOM.
     /// * `Ok((None, bytes_slice))` if `tested_bytes` is certain not to be any BOM.
     /// * `Err(())` otherwise.
     pub fn try_find_bytes_bom<'a>(tested_bytes: &'a [u8], bom_types_tested: &[BomType]) -> BomsBytesTest<'a> {
-        use BomType::*;
-
+        let enabled = BomTypeMask::new(bom_types_tested);
+        // the first bytes are packed to be compared with each signature in a single operation
+        let (tested, tested_mask) = pack_bytes(tested_bytes);
         let mut result = BomsBytesTest::Complete { bom_type: None, additional_bytes: tested_bytes };
-
-        macro_rules! try_encoding {
-            ($encoding:expr) => {
-                if bom_types_tested.contains(&$encoding) {
-                    match $encoding.test_bytes(tested_bytes) {
-                        BomBytesTest::Incomplete => result = BomsBytesTest::Incomplete,
-                        BomBytesTest::NotBom => (),
-                        BomBytesTest::StartsWithBom => return BomsBytesTest::Complete { bom_type: Some($encoding), additional_bytes: &tested_bytes[$encoding.bom_length()..] },
-                    }
-                }
-            };
+        let mut candidates = tested_bytes.first().map_or(ALL_SIGNATURES, |first_byte| FIRST_BYTE_SIGNATURES[*first_byte as usize]);
+        while candidates != 0 {
+            // the candidates are tested in the order of the signatures table
+            let signature = &SIGNATURES[candidates.trailing_zeros() as usize];
+            candidates &= candidates - 1;
+            if !enabled.contains(signature.bom_type) {
+                continue;
+            }
+            if (tested ^ signature.bytes) & signature.mask & tested_mask != 0 {
+                // not this BOM
+                continue;
+            }
+            if signature.mask & !tested_mask != 0 {
+                // the tested bytes are the start of the BOM
+                result = BomsBytesTest::Incomplete;
+            } else if result != BomsBytesTest::Incomplete {
+                // a longer BOM tested before, such as UTF-32LE for UTF-16LE, could still be present otherwise
+                return BomsBytesTest::Complete { bom_type: Some(signature.bom_type), additional_bytes: &tested_bytes[signature.bom_type.bom_length()..] };
+            }
         }
+        result
+    }
 
-        try_encoding!(UTF8);
-        try_encoding!(UTF32LE);
-        try_encoding!(UTF32BE);
-        try_encoding!(UTF16LE);
-        try_encoding!(UTF16BE);
-        try_encoding!(UTF7);
-        try_encoding!(UTF1);
-        try_encoding!(UTFEBDIC);
-        try_encoding!(SCSU);
-        try_encoding!(BOCU1);
-        try_encoding!(GB1803);
+    /// Returns the BOM type `tested_bytes` starts with, knowing that there are no more bytes after them.
+    ///
+    /// Unlike [`BomType::try_find_bytes_bom`], a shorter BOM is accepted even when `tested_bytes` is the start of a
+    /// longer BOM: for instance `FF FE` is the UTF-16LE BOM if the stream ends before a complete UTF-32LE BOM. The
+    /// longest BOM found is returned, see [`BomType::all_sorted_by_length`].
+    pub fn find_bytes_bom_at_end(tested_bytes: &[u8], bom_types_tested: &[BomType]) -> Option<BomType> {
+        BomType::all_sorted_by_length()
+            .iter()
+            .find(|bom_type| bom_types_tested.contains(bom_type) && bom_type.test_bytes(tested_bytes) == BomBytesTest::StartsWithBom)
+            .copied()
+    }
 
-        result
+    /// Returns the number of bytes that must be read from the start of a stream to determine the presence of any of
+    /// `bom_types`: the length of their longest BOM.
+    /// # Examples
+    /// ```
+    /// use skip_bom::BomType;
+    ///
+    /// assert_eq!(3, BomType::max_probe_len(&[BomType::UTF8]));
+    /// assert_eq!(4, BomType::max_probe_len(BomType::all()));
+    /// assert_eq!(0, BomType::max_probe_len(&[]));
+    /// ```
+    pub fn max_probe_len(bom_types: &[BomType]) -> usize {
+        bom_types.iter().map(BomType::bom_length).max().unwrap_or(0)
     }
 
     /// Get a slice containing a list of all BOM types available.
     pub fn all() -> &'static [BomType] {
-        use BomType::*;
-        &[
-            UTF8,
-            UTF32LE,
-            UTF32BE,
-            UTF16LE,
-            UTF16BE,
-            UTF7,
-            UTF1,
-            UTFEBDIC,
-            SCSU,
-            BOCU1,
-            GB1803,
-        ]
+        BomTypeSet::ALL.bom_types()
+    }
+
+    /// Get a slice containing all BOM types available, the longest BOMs first and BOMs of the same length in the order
+    /// of the [`BomType`] variants.
+    ///
+    /// This is the order in which BOMs are matched: a BOM that is the start of a longer one, such as UTF-16LE for
+    /// UTF-32LE, is only found once the longer BOM is ruled out, whatever the order of the BOM types passed.
+    /// # Examples
+    /// ```
+    /// use skip_bom::{BomType, BomsBytesTest};
+    ///
+    /// assert_eq!(4, BomType::all_sorted_by_length()[0].bom_length());
+    /// let test = BomType::try_find_bytes_bom(b"\xFF\xFE\x00\x00", &[BomType::UTF16LE, BomType::UTF32LE]);
+    /// assert_eq!(BomsBytesTest::Complete { bom_type: Some(BomType::UTF32LE), additional_bytes: &[] }, test);
+    /// ```
+    pub fn all_sorted_by_length() -> &'static [BomType] {
+        &ALL_SORTED_BY_LENGTH
     }
 }
 
+/// Parse the exact bytes of a BOM: the conversion fails with an [`ErrorKind::InvalidData`] error if there are other bytes.
+/// # Examples
+/// ```
+/// use skip_bom::BomType;
+/// use std::convert::TryFrom;
+///
+/// assert_eq!(BomType::UTF16LE, BomType::try_from(&b"\xFF\xFE"[..]).unwrap());
+/// assert_eq!(BomType::UTF32LE, BomType::try_from(&b"\xFF\xFE\x00\x00"[..]).unwrap());
+/// assert!(BomType::try_from(&b"\xFF\xFEa"[..]).is_err());
+/// ```
+impl TryFrom<&[u8]> for BomType {
+    type Error = Error;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        BomType::all()
+            .iter()
+            .find(|bom_type| bom_type.bom_bytes() == bytes)
+            .copied()
+            .ok_or_else(|| BomError::new_io(ErrorKind::InvalidData, BomErrorCode::NotBom, None, "the bytes are not a BOM"))
+    }
+}
+
+/// All the BOM types, the longest BOMs first.
+pub(crate) const ALL_SORTED_BY_LENGTH: [BomType; 11] = [
+    BomType::UTF32LE,
+    BomType::UTF32BE,
+    BomType::UTFEBDIC,
+    BomType::GB1803,
+    BomType::UTF8,
+    BomType::UTF7,
+    BomType::UTF1,
+    BomType::SCSU,
+    BomType::BOCU1,
+    BomType::UTF16LE,
+    BomType::UTF16BE,
+];
+
 /// Test result for the compatibility with a single BOM.
+#[must_use]
 #[repr(C)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BomBytesTest {
@@ -140,6 +312,7 @@ pub enum BomBytesTest {
 }
 
 /// Test result for the compatibility with multiple BOMs.
+#[must_use]
 #[repr(C)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BomsBytesTest<'a> {
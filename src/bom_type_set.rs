@@ -0,0 +1,65 @@
+use super::BomType;
+
+use std::ops::Deref;
+
+/// Curated set of BOM types realistically occurring in a given environment, to pass where a `&[BomType]` is expected.
+///
+/// # Examples
+/// ```
+/// use skip_bom::{BomType, BomTypeSet, SkipEncodingBom};
+/// use std::io::Read;
+///
+/// let mut reader = SkipEncodingBom::new(BomTypeSet::WINDOWS_TEXT.bom_types(), &b"\xFF\xFEh\x00i\x00"[..]);
+/// assert_eq!(Some(BomType::UTF16LE), reader.read_bom().unwrap());
+/// assert!(!BomTypeSet::WINDOWS_TEXT.contains(&BomType::UTF16BE));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BomTypeSet(&'static [BomType]);
+
+impl BomTypeSet {
+    /// Text written by Windows tools: UTF-8 (Notepad, `Out-File -Encoding utf8`) and UTF-16LE (PowerShell, `regedit`).
+    pub const WINDOWS_TEXT: Self = BomTypeSet(&[BomType::UTF8, BomType::UTF16LE]);
+    /// Web content and interchange formats: UTF-8, UTF-16 and UTF-32 in both byte orders.
+    pub const WEB: Self = BomTypeSet(&[BomType::UTF8, BomType::UTF32LE, BomType::UTF32BE, BomType::UTF16LE, BomType::UTF16BE]);
+    /// Only the UTF-8 BOM, for inputs that must be UTF-8.
+    pub const STRICT_UTF8: Self = BomTypeSet(&[BomType::UTF8]);
+    /// All the supported BOM types: see [`BomType::all`].
+    pub const ALL: Self = BomTypeSet(&[
+        BomType::UTF8,
+        BomType::UTF32LE,
+        BomType::UTF32BE,
+        BomType::UTF16LE,
+        BomType::UTF16BE,
+        BomType::UTF7,
+        BomType::UTF1,
+        BomType::UTFEBDIC,
+        BomType::SCSU,
+        BomType::BOCU1,
+        BomType::GB1803,
+    ]);
+
+    /// Returns the BOM types of the set.
+    pub const fn bom_types(&self) -> &'static [BomType] {
+        self.0
+    }
+}
+
+impl Deref for BomTypeSet {
+    type Target = [BomType];
+
+    fn deref(&self) -> &[BomType] {
+        self.0
+    }
+}
+
+impl AsRef<[BomType]> for BomTypeSet {
+    fn as_ref(&self) -> &[BomType] {
+        self.0
+    }
+}
+
+impl From<BomTypeSet> for &'static [BomType] {
+    fn from(set: BomTypeSet) -> Self {
+        set.0
+    }
+}
@@ -0,0 +1,151 @@
+use super::{BufferCursor, BomStatus, BomType, BomsBytesTest, Result};
+
+use std::io::{BufRead, Read};
+
+/// Skip the initial encoding BOM of a [`BufRead`] by operating on its internal buffer.
+///
+/// When the buffer of the reader holds enough bytes to determine the BOM presence, which is the case unless it is
+/// smaller than a BOM or the stream comes in very small chunks, the BOM is skipped with [`BufRead::consume`] and the
+/// content is read straight from the buffer of the reader: no byte is copied. Otherwise the start bytes are buffered
+/// inline until the BOM presence is determined.
+///
+/// Unlike [`SkipEncodingBom`](crate::SkipEncodingBom), the start bytes of a stream ending before the BOM presence is
+/// determined are returned as content.
+///
+/// With the `zeroize` feature, the start bytes buffered inline are zeroed when the struct is dropped.
+/// # Examples
+/// ```
+/// use skip_bom::{BomType, SkipEncodingBomBuf};
+/// use std::io::{BufRead, BufReader};
+///
+/// let mut reader = SkipEncodingBomBuf::new(BomType::all(), BufReader::new(&b"\xEF\xBB\xBFfirst line\nsecond line\n"[..]));
+/// let mut line = String::new();
+/// let _ = reader.read_line(&mut line).unwrap();
+/// assert_eq!("first line\n", &line);
+/// assert_eq!(Some(Some(BomType::UTF8)), reader.bom_found());
+/// ```
+#[derive(Debug, Clone)]
+pub struct SkipEncodingBomBuf<'a, R: BufRead> {
+    bom_types: &'a [BomType],
+    probe_len: usize,
+    bom_found: Option<Option<BomType>>,
+    /// Bytes consumed from the reader while determining the BOM presence across its buffer boundaries.
+    pending: BufferCursor,
+    reader: R,
+}
+
+impl<'a, R: BufRead> SkipEncodingBomBuf<'a, R> {
+    /// Initialize an encoding BOM skip struct given any buffered stream reader. Its buffer may already hold bytes.
+    ///
+    /// # Arguments
+    ///
+    /// * `bom_types` - a slice with the types of BOM to check for. To skip any of the supported BOMs, pass [`BomType::all`].
+    /// * `reader` - the underlying buffered input stream reader.
+    pub fn new(bom_types: &'a [BomType], reader: R) -> Self {
+        Self {
+            bom_types,
+            probe_len: BomType::max_probe_len(bom_types),
+            bom_found: None,
+            pending: BufferCursor::default(),
+            reader,
+        }
+    }
+    /// Determine the BOM presence if needed and return the BOM found, skipping it.
+    #[must_use = "the BOM found is returned: use `let _ =` to only skip it"]
+    pub fn read_bom(&mut self) -> Result<Option<BomType>> {
+        match self.bom_found {
+            Some(bom_type) => Ok(bom_type),
+            None => self.detect(),
+        }
+    }
+    /// Return the BOM found, or [`None`] for the outer option if the presence of a BOM was not determined yet: see
+    /// [`SkipEncodingBom::bom_found`](crate::SkipEncodingBom::bom_found).
+    #[must_use]
+    pub fn bom_found(&self) -> Option<Option<BomType>> {
+        self.bom_found
+    }
+    /// Return the presence of a BOM as determined so far: see [`BomStatus`].
+    pub fn status(&self) -> BomStatus {
+        self.bom_found.into()
+    }
+    /// Unwraps this `SkipEncodingBomBuf<R>`, returning the underlying reader. Start bytes buffered after the BOM are lost.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+    /// Get a shared reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.reader
+    }
+    /// Get a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.reader
+    }
+
+    /// Bytes buffered after the BOM that were not returned yet.
+    fn pending_bytes(&self) -> &[u8] {
+        let bytes = self.pending.0.get_ref().bytes();
+        &bytes[(self.pending.0.position() as usize).min(bytes.len())..]
+    }
+
+    #[cold]
+    fn detect(&mut self) -> Result<Option<BomType>> {
+        loop {
+            let available = self.reader.fill_buf()?;
+            let start_bytes = self.pending.0.get_ref().bytes();
+            if start_bytes.is_empty() {
+                // the buffer of the reader is tested without copying it
+                if let BomsBytesTest::Complete { bom_type, .. } = BomType::try_find_bytes_bom(available, self.bom_types) {
+                    self.reader.consume(bom_type.map_or(0, |bom_type| bom_type.bom_length()));
+                    return Ok(*self.bom_found.insert(bom_type));
+                }
+            }
+            if available.is_empty() {
+                // the stream ended inside a possible BOM
+                let bom_type = BomType::find_bytes_bom_at_end(start_bytes, self.bom_types);
+                self.pending.0.set_position(bom_type.map_or(0, |bom_type| bom_type.bom_length()) as u64);
+                return Ok(*self.bom_found.insert(bom_type));
+            }
+            let count = self.probe_len.saturating_sub(start_bytes.len()).min(available.len());
+            let pushed = self.pending.0.get_mut().push_truncated(&available[..count]);
+            self.reader.consume(pushed);
+            if let BomsBytesTest::Complete { bom_type, .. } = BomType::try_find_bytes_bom(self.pending.0.get_ref().bytes(), self.bom_types) {
+                self.pending.0.set_position(bom_type.map_or(0, |bom_type| bom_type.bom_length()) as u64);
+                return Ok(*self.bom_found.insert(bom_type));
+            }
+        }
+    }
+}
+
+impl<'a, R: BufRead> Read for SkipEncodingBomBuf<'a, R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let available = self.fill_buf()?;
+        let count = available.len().min(buf.len());
+        buf[..count].copy_from_slice(&available[..count]);
+        self.consume(count);
+        Ok(count)
+    }
+}
+
+impl<'a, R: BufRead> BufRead for SkipEncodingBomBuf<'a, R> {
+    fn fill_buf(&mut self) -> Result<&[u8]> {
+        if self.bom_found.is_none() {
+            let _ = self.detect()?;
+        }
+        if self.pending_bytes().is_empty() {
+            self.reader.fill_buf()
+        } else {
+            Ok(self.pending_bytes())
+        }
+    }
+
+    fn consume(&mut self, amt: usize) {
+        let pending_count = self.pending_bytes().len();
+        if pending_count > 0 {
+            let count = amt.min(pending_count);
+            self.pending.0.set_position(self.pending.0.position() + count as u64);
+            self.reader.consume(amt - count);
+        } else {
+            self.reader.consume(amt);
+        }
+    }
+}
@@ -1,4 +1,23 @@
+use super::{BomError, BomErrorCode, Result};
 
+use std::io::{Cursor, Error, ErrorKind};
+
+/// Small fixed-size buffer holding the bytes read at the start of a stream, with room for the longest BOM.
+///
+/// Pushing more bytes than the buffer can hold fails with an [`ErrorKind::InvalidInput`] error and leaves the buffer unchanged.
+/// With the `zeroize` feature, the buffer implements `Zeroize`: as it is [`Copy`], it is not zeroed when dropped, and
+/// the structs holding it zero it explicitly instead.
+/// # Examples
+/// ```
+/// use skip_bom::BomBytesPushBuffer;
+///
+/// let mut buffer = BomBytesPushBuffer::from_slice(b"\xEF\xBB").unwrap();
+/// buffer.push(b"\xBF").unwrap();
+/// assert_eq!(b"\xEF\xBB\xBF", buffer.bytes());
+/// assert_eq!(BomBytesPushBuffer::CAPACITY - 3, buffer.available_bytes());
+/// assert!(buffer.push(b"too long").is_err());
+/// assert_eq!(b"\xEF\xBB\xBF", buffer.bytes());
+/// ```
 #[derive(Default, Debug, Clone, Copy)]
 pub struct BomBytesPushBuffer {
     buffer: BomBytesArray,
@@ -6,32 +25,95 @@ pub struct BomBytesPushBuffer {
 }
 
 impl BomBytesPushBuffer {
-    pub fn from_slice(slice: &[u8]) -> Self {
+    /// Maximum number of bytes a buffer can hold.
+    pub const CAPACITY: usize = crate::MAX_BOM_LENGTH as usize;
+
+    /// Initialize a buffer with the bytes of a slice, failing if the buffer cannot hold all of them.
+    pub fn from_slice(slice: &[u8]) -> Result<Self> {
         let mut bom_bytes_push_buffer = Self::default();
-        bom_bytes_push_buffer.push(slice);
-        bom_bytes_push_buffer
+        bom_bytes_push_buffer.push(slice)?;
+        Ok(bom_bytes_push_buffer)
     }
-    pub fn from_array(array: BomBytesArray, byte_count: usize) -> Self {
-        Self {
+    /// Initialize a buffer with the `byte_count` first bytes of an array, failing if `byte_count` exceeds the array length.
+    pub fn from_array(array: BomBytesArray, byte_count: usize) -> Result<Self> {
+        if byte_count > array.len() {
+            return Err(Self::overflow_error(byte_count));
+        }
+        let bom_bytes_push_buffer = Self {
             buffer: array,
             position: byte_count,
-        }
+        };
+        bom_bytes_push_buffer.debug_check_invariants();
+        Ok(bom_bytes_push_buffer)
+    }
+    /// Maximum number of bytes the buffer can hold: see [`BomBytesPushBuffer::CAPACITY`].
+    pub fn capacity(&self) -> usize {
+        Self::CAPACITY
     }
+    /// Number of bytes that can still be pushed.
     pub fn available_bytes(&self) -> usize {
-        self.buffer.len() - self.position
+        self.capacity() - self.position
     }
-    pub fn push(&mut self, bytes: &[u8]) -> usize {
+    /// Returns `true` if no more bytes can be pushed.
+    pub fn is_full(&self) -> bool {
+        self.available_bytes() == 0
+    }
+    /// Returns `true` if the buffer holds no bytes.
+    pub fn is_empty(&self) -> bool {
+        self.position == 0
+    }
+    /// Push all the bytes at the end of the buffer, or fail without pushing any of them if they do not fit.
+    pub fn push(&mut self, bytes: &[u8]) -> Result<()> {
+        if bytes.len() > self.available_bytes() {
+            return Err(Self::overflow_error(self.position + bytes.len()));
+        }
+        self.push_truncated(bytes);
+        Ok(())
+    }
+    /// Push as many bytes as possible at the end of the buffer and return how many were pushed.
+    pub fn push_truncated(&mut self, bytes: &[u8]) -> usize {
         let count = bytes.len().min(self.available_bytes());
         self.buffer[self.position..(self.position + count)].copy_from_slice(&bytes[..count]);
         self.position += count;
+        self.debug_check_invariants();
         count
     }
+    /// The bytes held by the buffer.
     pub fn bytes(&self) -> &[u8] {
         &self.buffer[..self.position]
     }
+    /// Number of bytes held by the buffer.
     pub fn byte_count(&self) -> usize {
         self.position
     }
+
+    fn overflow_error(byte_count: usize) -> Error {
+        BomError::new_io(ErrorKind::InvalidInput, BomErrorCode::BufferOverflow, None, format!("{} bytes do not fit in a BOM bytes buffer of {} bytes", byte_count, Self::CAPACITY))
+    }
+
+    fn debug_check_invariants(&self) {
+        debug_assert!(self.position <= self.buffer.len(), "BOM bytes buffer position {} out of bounds", self.position);
+        debug_assert_eq!(Self::CAPACITY, self.buffer.len());
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for BomBytesPushBuffer {
+    fn zeroize(&mut self) {
+        self.buffer.zeroize();
+        self.position.zeroize();
+    }
+}
+
+/// Cursor over a BOM bytes buffer, zeroing the bytes when dropped with the `zeroize` feature.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct BufferCursor(pub Cursor<BomBytesPushBuffer>);
+
+#[cfg(feature = "zeroize")]
+impl Drop for BufferCursor {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(self.0.get_mut());
+    }
 }
 
 impl AsRef<[u8]> for BomBytesPushBuffer {
@@ -40,4 +122,5 @@ impl AsRef<[u8]> for BomBytesPushBuffer {
     }
 }
 
+/// Array with room for the longest BOM.
 pub type BomBytesArray = [u8; crate::MAX_BOM_LENGTH as usize];
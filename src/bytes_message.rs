@@ -0,0 +1,23 @@
+use super::{BomType, NoBomSlice};
+
+use bytes::Bytes;
+
+/// Remove the encoding BOM of a message payload, for instance from an MQTT or Kafka client, and return the BOM found
+/// along with the payload without it.
+///
+/// The payload is the whole message: a payload ending inside a possible BOM is returned as it is, see
+/// [`NoBomSlice`]. The bytes are not copied: the returned [`Bytes`] shares the buffer of the payload.
+/// # Examples
+/// ```
+/// use bytes::Bytes;
+/// use skip_bom::{clean_message, BomType};
+///
+/// let (bom, payload) = clean_message(Bytes::from_static(b"\xEF\xBB\xBF{\"temperature\": 21}"));
+/// assert_eq!(Some(BomType::UTF8), bom);
+/// assert_eq!(&b"{\"temperature\": 21}"[..], payload);
+/// ```
+pub fn clean_message(mut payload: Bytes) -> (Option<BomType>, Bytes) {
+    let bom = NoBomSlice::new(BomType::all(), &payload).bom();
+    let _ = payload.split_to(bom.map_or(0, |bom_type| bom_type.bom_length()));
+    (bom, payload)
+}
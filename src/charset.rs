@@ -0,0 +1,113 @@
+use super::BomType;
+
+/// Result of the comparison between the BOM found in a stream and a declared charset.
+///
+/// Following the [WHATWG Encoding Standard](https://encoding.spec.whatwg.org/#decode), the BOM always wins over
+/// a charset declared out-of-band (for example in an HTTP `Content-Type` header).
+#[must_use]
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CharsetReconciliation {
+    /// The declared charset is compatible with the BOM found.
+    Agree(BomType),
+    /// The declared charset is not compatible with the BOM found: the BOM type should be used to decode the stream.
+    Conflict(BomType),
+    /// No conclusion can be drawn: there is no BOM, its presence is not determined yet, or the declared charset is not recognized.
+    Unknown,
+}
+
+impl CharsetReconciliation {
+    /// Compare a BOM found (or not) with a declared charset.
+    ///
+    /// `declared` can either be a bare charset label such as `utf-8` or a full MIME type such as `text/csv; charset="UTF-16"`.
+    pub fn new(bom_type: Option<BomType>, declared: &str) -> Self {
+        match bom_type {
+            Some(bom_type) => match charset_label_matches(bom_type, charset_label(declared)) {
+                Some(true) => CharsetReconciliation::Agree(bom_type),
+                Some(false) => CharsetReconciliation::Conflict(bom_type),
+                None => CharsetReconciliation::Unknown,
+            },
+            None => CharsetReconciliation::Unknown,
+        }
+    }
+
+    /// Return the BOM type that should be used to decode the stream if it is known.
+    pub fn bom_type(&self) -> Option<BomType> {
+        match self {
+            CharsetReconciliation::Agree(bom_type) | CharsetReconciliation::Conflict(bom_type) => Some(*bom_type),
+            CharsetReconciliation::Unknown => None,
+        }
+    }
+}
+
+impl BomType {
+    /// Compare the BOM type with a declared charset, either as a bare label or as a MIME type with a `charset=` parameter.
+    ///
+    /// # Examples
+    /// ```
+    /// use skip_bom::{BomType, CharsetReconciliation};
+    ///
+    /// assert_eq!(CharsetReconciliation::Agree(BomType::UTF8), BomType::UTF8.reconcile_with_charset("text/plain; charset=utf-8"));
+    /// assert_eq!(CharsetReconciliation::Agree(BomType::UTF16BE), BomType::UTF16BE.reconcile_with_charset("UTF-16"));
+    /// assert_eq!(CharsetReconciliation::Conflict(BomType::UTF16LE), BomType::UTF16LE.reconcile_with_charset("iso-8859-1"));
+    /// assert_eq!(CharsetReconciliation::Unknown, BomType::UTF8.reconcile_with_charset("x-made-up"));
+    /// ```
+    pub fn reconcile_with_charset(&self, declared: &str) -> CharsetReconciliation {
+        CharsetReconciliation::new(Some(*self), declared)
+    }
+}
+
+/// Extract the charset label from a `Content-Type` value, or return the trimmed value if there is no `charset=` parameter.
+pub(crate) fn charset_label(declared: &str) -> &str {
+    let label = declared
+        .split(';')
+        .map(str::trim)
+        .find_map(|parameter| {
+            let (name, value) = parameter.split_at(parameter.find('=')?);
+            if name.trim().eq_ignore_ascii_case("charset") { Some(&value[1..]) } else { None }
+        })
+        .unwrap_or(declared);
+    label.trim().trim_matches('"').trim()
+}
+
+/// Labels of common encodings that cannot start with any BOM, from the WHATWG Encoding Standard.
+const OTHER_CHARSET_LABELS: &[&str] = &[
+    "us-ascii", "ascii", "iso-8859-1", "iso_8859-1", "latin1", "l1", "iso-8859-2", "iso-8859-3", "iso-8859-4",
+    "iso-8859-5", "iso-8859-6", "iso-8859-7", "iso-8859-8", "iso-8859-8-i", "iso-8859-10", "iso-8859-13",
+    "iso-8859-14", "iso-8859-15", "iso-8859-16", "ibm866", "koi8-r", "koi8-u", "macintosh", "x-mac-cyrillic",
+    "windows-874", "windows-1250", "windows-1251", "windows-1252", "windows-1253", "windows-1254", "windows-1255",
+    "windows-1256", "windows-1257", "windows-1258", "gbk", "gb2312", "big5", "big5-hkscs", "euc-jp", "iso-2022-jp",
+    "shift_jis", "sjis", "euc-kr", "ks_c_5601-1987",
+];
+
+/// Returns the charset labels designating the encoding of a BOM type.
+fn bom_type_charset_labels(bom_type: BomType) -> &'static [&'static str] {
+    use BomType::*;
+
+    match bom_type {
+        UTF8 => &["utf-8", "utf8", "unicode-1-1-utf-8", "unicode11utf8", "unicode20utf8", "x-unicode20utf8"],
+        UTF16LE => &["utf-16le", "utf-16", "utf16", "unicode", "unicodefeff", "csunicode", "ucs-2", "iso-10646-ucs-2"],
+        UTF16BE => &["utf-16be", "utf-16", "utf16", "unicodefffe"],
+        UTF32LE => &["utf-32le", "utf-32", "utf32"],
+        UTF32BE => &["utf-32be", "utf-32", "utf32"],
+        UTF7 => &["utf-7", "utf7", "csutf7", "unicode-1-1-utf-7"],
+        UTF1 => &["utf-1", "iso-10646-utf-1", "csiso10646utf1"],
+        UTFEBDIC => &["utf-ebcdic"],
+        SCSU => &["scsu", "csscsu"],
+        BOCU1 => &["bocu-1", "csbocu-1", "csbocu1"],
+        GB1803 => &["gb18030", "csgb18030"],
+    }
+}
+
+/// Returns `Some(true)` if the label designates the encoding of the BOM type, `Some(false)` if it designates another
+/// known encoding and `None` if the label is not recognized.
+fn charset_label_matches(bom_type: BomType, label: &str) -> Option<bool> {
+    let is_in = |labels: &[&str]| labels.iter().any(|known| known.eq_ignore_ascii_case(label));
+    if is_in(bom_type_charset_labels(bom_type)) {
+        Some(true)
+    } else if is_in(OTHER_CHARSET_LABELS) || BomType::all().iter().any(|other| is_in(bom_type_charset_labels(*other))) {
+        Some(false)
+    } else {
+        None
+    }
+}
@@ -0,0 +1,14 @@
+use super::{BomError, BomErrorCode, Result};
+
+use std::io::{ErrorKind, Read};
+
+/// Read from `reader` into `buf`, failing with an [`ErrorKind::InvalidData`] error instead of returning more bytes than
+/// `buf` can hold, so that the byte count can be used to index `buf`.
+pub(crate) fn checked_read<R: Read + ?Sized>(reader: &mut R, buf: &mut [u8]) -> Result<usize> {
+    let bytes_read = reader.read(buf)?;
+    if bytes_read > buf.len() {
+        Err(BomError::new_io(ErrorKind::InvalidData, BomErrorCode::ReaderOverReported, None, format!("the reader returned {} bytes for a buffer of {} bytes", bytes_read, buf.len())))
+    } else {
+        Ok(bytes_read)
+    }
+}
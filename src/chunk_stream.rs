@@ -0,0 +1,131 @@
+use super::{BomType, BomsBytesTest};
+
+use futures_core::Stream;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// Skip the initial encoding BOM of a [`Stream`] of byte chunks, such as the file chunks of a streaming upload received
+/// with tonic, yielding the chunks without the BOM.
+///
+/// The first chunks are held back until the BOM presence is determined, which happens on the first chunk unless it is
+/// smaller than a BOM: they are then yielded as one chunk without the BOM. The following chunks and errors are passed
+/// through. Empty chunks are not yielded while the BOM presence is not determined, and a stream ending inside a
+/// possible BOM yields the bytes held back as content.
+/// # Examples
+/// ```
+/// use futures_core::Stream;
+/// use skip_bom::{BomType, SkipBomChunks};
+/// use std::future::Future;
+/// use std::pin::Pin;
+/// use std::task::{Context, Poll};
+///
+/// /// Future of the next item of a stream.
+/// struct Next<'s, S>(&'s mut S);
+///
+/// impl<S: Stream + Unpin> Future for Next<'_, S> {
+///     type Output = Option<S::Item>;
+///
+///     fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+///         Pin::new(&mut *self.0).poll_next(cx)
+///     }
+/// }
+///
+/// /// Stream of chunks received in a message.
+/// struct Chunks(Vec<Vec<u8>>);
+///
+/// impl Stream for Chunks {
+///     type Item = Result<Vec<u8>, std::io::Error>;
+///
+///     fn poll_next(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+///         Poll::Ready((!self.0.is_empty()).then(|| Ok(self.0.remove(0))))
+///     }
+/// }
+///
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// let mut chunks = SkipBomChunks::new(BomType::all(), Chunks(vec![b"\xEF\xBB".to_vec(), b"\xBFfirst".to_vec(), b" second".to_vec()]));
+/// let mut content = Vec::new();
+/// while let Some(chunk) = Next(&mut chunks).await {
+///     content.push(chunk.unwrap());
+/// }
+/// assert_eq!(vec![b"first".to_vec(), b" second".to_vec()], content);
+/// assert_eq!(Some(Some(BomType::UTF8)), chunks.bom_found());
+/// # });
+/// ```
+#[derive(Debug, Clone)]
+pub struct SkipBomChunks<'a, S> {
+    bom_types: &'a [BomType],
+    bom_found: Option<Option<BomType>>,
+    /// Start bytes held back while the BOM presence is not determined.
+    pending: Vec<u8>,
+    stream: S,
+}
+
+impl<'a, S> SkipBomChunks<'a, S> {
+    /// Initialize an encoding BOM skip struct given any stream of byte chunks.
+    ///
+    /// # Arguments
+    ///
+    /// * `bom_types` - a slice with the types of BOM to check for. To skip any of the supported BOMs, pass [`BomType::all`].
+    /// * `stream` - the underlying stream of chunks.
+    pub fn new(bom_types: &'a [BomType], stream: S) -> Self {
+        Self { bom_types, bom_found: None, pending: Vec::new(), stream }
+    }
+    /// Return the BOM found, or [`None`] for the outer option if the presence of a BOM was not determined yet: see
+    /// [`SkipEncodingBom::bom_found`](crate::SkipEncodingBom::bom_found).
+    pub fn bom_found(&self) -> Option<Option<BomType>> {
+        self.bom_found
+    }
+    /// Unwraps this `SkipBomChunks<S>`, returning the underlying stream. Start bytes held back are lost.
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+    /// Get a shared reference to the underlying stream.
+    pub fn get_ref(&self) -> &S {
+        &self.stream
+    }
+    /// Get a mutable reference to the underlying stream.
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.stream
+    }
+
+    /// Record the BOM found and return the start bytes held back after it, if any.
+    fn resolve(&mut self, bom_type: Option<BomType>) -> Option<Vec<u8>> {
+        self.bom_found = Some(bom_type);
+        let _ = self.pending.drain(..bom_type.map_or(0, |bom_type| bom_type.bom_length()));
+        Some(std::mem::take(&mut self.pending)).filter(|content| !content.is_empty())
+    }
+}
+
+impl<S: Stream<Item = Result<Vec<u8>, E>> + Unpin, E> Stream for SkipBomChunks<'_, S> {
+    type Item = Result<Vec<u8>, E>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        while self.bom_found.is_none() {
+            let chunk = match Pin::new(&mut self.stream).poll_next(cx) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(Some(Ok(chunk))) => chunk,
+                Poll::Ready(Some(Err(e))) => return Poll::Ready(Some(Err(e))),
+                Poll::Ready(None) => {
+                    let bom_type = BomType::find_bytes_bom_at_end(&self.pending, self.bom_types);
+                    return Poll::Ready(self.resolve(bom_type).map(Ok));
+                },
+            };
+            if self.pending.is_empty() {
+                self.pending = chunk;
+            } else {
+                self.pending.extend_from_slice(&chunk);
+            }
+            if let BomsBytesTest::Complete { bom_type, .. } = BomType::try_find_bytes_bom(&self.pending, self.bom_types) {
+                if let Some(content) = self.resolve(bom_type) {
+                    return Poll::Ready(Some(Ok(content)));
+                }
+            }
+        }
+        Pin::new(&mut self.stream).poll_next(cx)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (_, upper) = self.stream.size_hint();
+        (0, upper.and_then(|upper| upper.checked_add(usize::from(!self.pending.is_empty()))))
+    }
+}
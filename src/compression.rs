@@ -0,0 +1,31 @@
+use super::{BomType, SkipEncodingBom};
+
+use std::io::Read;
+
+#[cfg(feature = "flate2")]
+impl<'a, R: Read> SkipEncodingBom<'a, flate2::read::MultiGzDecoder<R>> {
+    /// Initialize an encoding BOM skip struct over a gzip-compressed stream: the BOM is looked for in the decompressed data.
+    ///
+    /// Streams made of several concatenated gzip members are supported.
+    ///
+    /// # Arguments
+    ///
+    /// * `bom_types` - a slice with the types of BOM to check for. To skip any of the supported BOMs, pass [`BomType::all`].
+    /// * `reader` - the underlying gzip-compressed input stream reader.
+    pub fn new_gz(bom_types: &'a [BomType], reader: R) -> Self {
+        Self::new(bom_types, flate2::read::MultiGzDecoder::new(reader))
+    }
+}
+
+#[cfg(feature = "zstd")]
+impl<'a, R: Read> SkipEncodingBom<'a, zstd::stream::read::Decoder<'static, std::io::BufReader<R>>> {
+    /// Initialize an encoding BOM skip struct over a zstd-compressed stream: the BOM is looked for in the decompressed data.
+    ///
+    /// # Arguments
+    ///
+    /// * `bom_types` - a slice with the types of BOM to check for. To skip any of the supported BOMs, pass [`BomType::all`].
+    /// * `reader` - the underlying zstd-compressed input stream reader.
+    pub fn new_zstd(bom_types: &'a [BomType], reader: R) -> super::Result<Self> {
+        Ok(Self::new(bom_types, zstd::stream::read::Decoder::new(reader)?))
+    }
+}
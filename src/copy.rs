@@ -0,0 +1,133 @@
+use super::{find_misplaced_bom, BomError, BomErrorCode, BomType, SkipEncodingBom, Result};
+
+use std::io::{self, Read, Write};
+use std::num::NonZeroU64;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Options of [`copy_without_bom`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CopyOptions<'a> {
+    /// Flag checked before each chunk copied: once it is set, the copy stops with an
+    /// [`ErrorKind::Other`](io::ErrorKind::Other) error of code [`BomErrorCode::Cancelled`].
+    pub cancel: Option<&'a AtomicBool>,
+    /// Maximum average throughput of the copy in bytes per second, for background jobs that must not saturate shared
+    /// storage. The copy sleeps between chunks to keep under it.
+    pub max_bytes_per_second: Option<NonZeroU64>,
+    /// Maximum number of ASCII bytes before a misplaced UTF-8 BOM removed by [`copy_without_bom`], see
+    /// [`find_misplaced_bom`]. With the default of 0, only a BOM at the start of the stream is removed. Windows over
+    /// [`MAX_MISPLACED_BOM_WINDOW`] bytes are clamped to it. [`strip_bom_file`](crate::strip_bom_file) ignores this option.
+    pub misplaced_bom_window: usize,
+}
+
+/// Largest [`CopyOptions::misplaced_bom_window`] taken into account, bounding the bytes buffered to look for a
+/// misplaced BOM.
+pub const MAX_MISPLACED_BOM_WINDOW: usize = 256;
+
+/// Copy the content of a reader to a writer, skipping its initial encoding BOM if present, and return the number of
+/// bytes written along with the BOM found.
+///
+/// Unlike [`io::copy`] over a [`SkipEncodingBom`], the copy can be cancelled between chunks and its throughput limited:
+/// see [`CopyOptions`]. With [`CopyOptions::misplaced_bom_window`], a UTF-8 BOM following a few ASCII bytes at the start
+/// is removed and returned as the BOM found if the stream does not start with one.
+/// # Examples
+/// ```
+/// use skip_bom::{copy_without_bom, BomType, CopyOptions};
+/// use std::sync::atomic::{AtomicBool, Ordering};
+///
+/// let mut output = Vec::new();
+/// let copied = copy_without_bom(BomType::all(), &b"\xEF\xBB\xBFtext"[..], &mut output, &CopyOptions::default()).unwrap();
+/// assert_eq!((4, Some(BomType::UTF8)), copied);
+/// assert_eq!(b"text", output.as_slice());
+///
+/// let cancel = AtomicBool::new(true);
+/// let options = CopyOptions { cancel: Some(&cancel), ..Default::default() };
+/// assert!(copy_without_bom(BomType::all(), &b"text"[..], &mut Vec::new(), &options).is_err());
+///
+/// let mut output = Vec::new();
+/// let options = CopyOptions { misplaced_bom_window: 8, ..Default::default() };
+/// let copied = copy_without_bom(BomType::all(), &b"# \xEF\xBB\xBFheader"[..], &mut output, &options).unwrap();
+/// assert_eq!((8, Some(BomType::UTF8)), copied);
+/// assert_eq!(b"# header", output.as_slice());
+/// ```
+pub fn copy_without_bom<R: Read, W: Write + ?Sized>(bom_types: &[BomType], reader: R, writer: &mut W, options: &CopyOptions) -> Result<(u64, Option<BomType>)> {
+    let mut reader = SkipEncodingBom::new(bom_types, reader);
+    let mut start = Vec::new();
+    let misplaced_bom_window = options.misplaced_bom_window.min(MAX_MISPLACED_BOM_WINDOW);
+    if misplaced_bom_window > 0 && bom_types.contains(&BomType::UTF8) {
+        let start_len = misplaced_bom_window.saturating_add(BomType::UTF8.bom_length());
+        let _ = (&mut reader).take(start_len as u64).read_to_end(&mut start)?;
+    }
+    let mut bom = reader.bom_found().flatten();
+    if let (None, Some(offset)) = (bom, find_misplaced_bom(&start, misplaced_bom_window)) {
+        let _ = start.drain(offset..offset + BomType::UTF8.bom_length());
+        bom = Some(BomType::UTF8);
+    }
+    if !start.is_empty() {
+        check_cancelled(options.cancel)?;
+        writer.write_all(&start)?;
+    }
+    let written = copy_chunks(&mut reader, writer, options, &mut |_| {})?;
+    Ok((start.len() as u64 + written, bom.or_else(|| reader.bom_found().flatten())))
+}
+
+/// Size of the chunks copied, between which the cancellation is checked and the progress reported.
+pub(crate) const COPY_BUFFER_LEN: usize = 64 * 1024;
+
+/// Copy `reader` to `writer` by chunks of [`COPY_BUFFER_LEN`] bytes, calling `progress` with the number of bytes
+/// written after each chunk.
+pub(crate) fn copy_chunks<R: Read + ?Sized, W: Write + ?Sized>(reader: &mut R, writer: &mut W, options: &CopyOptions, progress: &mut dyn FnMut(u64)) -> Result<u64> {
+    let throttle = Throttle::new(options.max_bytes_per_second);
+    let mut buf = vec![0; throttle.chunk_len()];
+    let mut written = 0;
+    loop {
+        throttle.wait(written);
+        check_cancelled(options.cancel)?;
+        let len = match reader.read(&mut buf) {
+            Ok(0) => return Ok(written),
+            Ok(len) => len,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        writer.write_all(&buf[..len])?;
+        written += len as u64;
+        progress(written);
+    }
+}
+
+/// Limit of the throughput of a copy by chunks.
+pub(crate) struct Throttle {
+    start: Instant,
+    max_bytes_per_second: Option<NonZeroU64>,
+}
+
+impl Throttle {
+    pub fn new(max_bytes_per_second: Option<NonZeroU64>) -> Self {
+        Self { start: Instant::now(), max_bytes_per_second }
+    }
+
+    /// Returns the length of the chunks to copy: at most a second of throughput, so that the copy does not come in bursts.
+    pub fn chunk_len(&self) -> usize {
+        self.max_bytes_per_second.map_or(COPY_BUFFER_LEN, |max| max.get().min(COPY_BUFFER_LEN as u64) as usize)
+    }
+
+    /// Sleep until copying `written` bytes since the start is within the throughput limit.
+    pub fn wait(&self, written: u64) {
+        if let Some(max) = self.max_bytes_per_second {
+            let due = Duration::from_secs_f64(written as f64 / max.get() as f64);
+            if let Some(early) = due.checked_sub(self.start.elapsed()) {
+                thread::sleep(early);
+            }
+        }
+    }
+}
+
+/// Fail if the operation was cancelled.
+pub(crate) fn check_cancelled(cancel: Option<&AtomicBool>) -> Result<()> {
+    if cancel.is_some_and(|cancel| cancel.load(Ordering::Relaxed)) {
+        Err(BomError::new_io(io::ErrorKind::Other, BomErrorCode::Cancelled, None, "the operation was cancelled"))
+    } else {
+        Ok(())
+    }
+}
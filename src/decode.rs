@@ -0,0 +1,276 @@
+//! Decoding of the content after the BOM to UTF-8 text, and deserialization of documents starting with a BOM.
+//! # Examples
+//! ```
+//! use skip_bom::decode::DecodeReader;
+//! use skip_bom::BomType;
+//! use std::io::Read;
+//!
+//! let mut text = String::new();
+//! let _ = DecodeReader::new(BomType::all(), &b"\xFE\xFF\x00H\x00i"[..]).read_to_string(&mut text).unwrap();
+//! assert_eq!("Hi", text);
+//! ```
+
+use super::{checked_read, reject_external_decoder, BomError, BomErrorCode, BomStatus, BomType, Result, SkipEncodingBom};
+
+pub use super::{from_bom_reader, read_text_limited};
+#[cfg(all(feature = "serde", feature = "serde_json"))]
+pub use super::from_json_reader;
+#[cfg(all(feature = "serde", feature = "serde_yaml"))]
+pub use super::from_yaml_reader;
+#[cfg(all(feature = "serde", feature = "toml"))]
+pub use super::from_toml_reader;
+#[cfg(feature = "quick-xml")]
+pub use super::{xml_reader_no_bom, XmlReaderNoBom};
+#[cfg(all(feature = "encoding_rs", feature = "encoding_rs_io"))]
+pub use super::{decode_mime_part, decode_reader_bytes, encoding_rs_encoding, BomDecodeReaderBytes};
+
+use std::fmt::{self, Debug, Formatter};
+use std::io::{Error, ErrorKind, Read};
+
+const INPUT_BUFFER_LENGTH: usize = 1024;
+
+const OUTPUT_BUFFER_LENGTH: usize = 2 * INPUT_BUFFER_LENGTH;
+
+fn push_char(output: &mut [u8], written: &mut usize, c: char) {
+    *written += c.encode_utf8(&mut output[*written..]).len();
+}
+
+/// Decoder for the content after the BOM.
+#[derive(Debug)]
+enum Decoder {
+    /// The content is already UTF-8 or its encoding is unknown.
+    Passthrough,
+    Utf16 { big_endian: bool, high_surrogate: Option<u16> },
+    Utf32 { big_endian: bool },
+    #[cfg(feature = "encoding_rs")]
+    EncodingRs(encoding_rs::Decoder),
+    /// The content cannot be decoded.
+    Unsupported(BomType),
+}
+
+impl Decoder {
+    fn new(bom_type: Option<BomType>) -> Self {
+        match bom_type {
+            None | Some(BomType::UTF8) => Decoder::Passthrough,
+            Some(BomType::UTF16LE) => Decoder::Utf16 { big_endian: false, high_surrogate: None },
+            Some(BomType::UTF16BE) => Decoder::Utf16 { big_endian: true, high_surrogate: None },
+            Some(BomType::UTF32LE) => Decoder::Utf32 { big_endian: false },
+            Some(BomType::UTF32BE) => Decoder::Utf32 { big_endian: true },
+            #[cfg(feature = "encoding_rs")]
+            Some(BomType::GB1803) => Decoder::EncodingRs(encoding_rs::GB18030.new_decoder_without_bom_handling()),
+            Some(bom_type) => Decoder::Unsupported(bom_type),
+        }
+    }
+
+    fn unsupported_error(bom_type: BomType) -> Error {
+        match reject_external_decoder(bom_type) {
+            Err(error) => error,
+            Ok(()) => BomError::new_io(ErrorKind::InvalidData, BomErrorCode::DecoderUnavailable, Some(bom_type), format!("decoding {:?} content requires the encoding_rs feature", bom_type)),
+        }
+    }
+
+    /// Decode as much of `input` as possible to `output` and return the number of bytes read and written, and whether
+    /// the decoding is finished.
+    ///
+    /// If `last` is `true`, the input is the end of the stream and incomplete sequences are decoded as U+FFFD. The
+    /// decoding is finished once the whole input was decoded with `last`: the decoder must not be used anymore.
+    fn decode(&mut self, input: &[u8], output: &mut [u8], last: bool) -> Result<(usize, usize, bool)> {
+        // room for a replacement character followed by any character
+        const MAX_CHARS_BYTES: usize = 3 + 4;
+        let mut read = 0;
+        let mut written = 0;
+        match self {
+            Decoder::Passthrough => {
+                let count = input.len().min(output.len());
+                output[..count].copy_from_slice(&input[..count]);
+                return Ok((count, count, last && count == input.len()));
+            },
+            Decoder::Utf16 { big_endian, high_surrogate } => {
+                while input.len() - read >= 2 && output.len() - written >= MAX_CHARS_BYTES {
+                    let unit_bytes = [input[read], input[read + 1]];
+                    let unit = if *big_endian { u16::from_be_bytes(unit_bytes) } else { u16::from_le_bytes(unit_bytes) };
+                    read += 2;
+                    let pending_high_surrogate = high_surrogate.take();
+                    match (pending_high_surrogate, unit) {
+                        (Some(high), 0xDC00..=0xDFFF) => {
+                            let code_point = 0x10000 + (((high as u32) - 0xD800) << 10) + ((unit as u32) - 0xDC00);
+                            push_char(output, &mut written, std::char::from_u32(code_point).unwrap_or(char::REPLACEMENT_CHARACTER));
+                            continue;
+                        },
+                        (Some(_), _) => push_char(output, &mut written, char::REPLACEMENT_CHARACTER),
+                        (None, _) => (),
+                    }
+                    match unit {
+                        0xD800..=0xDBFF => *high_surrogate = Some(unit),
+                        _ => push_char(output, &mut written, std::char::from_u32(unit as u32).unwrap_or(char::REPLACEMENT_CHARACTER)),
+                    }
+                }
+                if last && input.len() - read < 2 && output.len() - written >= MAX_CHARS_BYTES {
+                    if high_surrogate.take().is_some() {
+                        push_char(output, &mut written, char::REPLACEMENT_CHARACTER);
+                    }
+                    if read < input.len() {
+                        read = input.len();
+                        push_char(output, &mut written, char::REPLACEMENT_CHARACTER);
+                    }
+                }
+            },
+            Decoder::Utf32 { big_endian } => {
+                while input.len() - read >= 4 && output.len() - written >= MAX_CHARS_BYTES {
+                    let unit_bytes = [input[read], input[read + 1], input[read + 2], input[read + 3]];
+                    let unit = if *big_endian { u32::from_be_bytes(unit_bytes) } else { u32::from_le_bytes(unit_bytes) };
+                    read += 4;
+                    push_char(output, &mut written, std::char::from_u32(unit).unwrap_or(char::REPLACEMENT_CHARACTER));
+                }
+                if last && read < input.len() && input.len() - read < 4 && output.len() - written >= MAX_CHARS_BYTES {
+                    read = input.len();
+                    push_char(output, &mut written, char::REPLACEMENT_CHARACTER);
+                }
+            },
+            #[cfg(feature = "encoding_rs")]
+            Decoder::EncodingRs(decoder) => {
+                let (result, decoder_read, decoder_written, _) = decoder.decode_to_utf8(input, output, last);
+                return Ok((decoder_read, decoder_written, last && result == encoding_rs::CoderResult::InputEmpty));
+            },
+            Decoder::Unsupported(bom_type) => return Err(Self::unsupported_error(*bom_type)),
+        }
+        Ok((read, written, last && read == input.len()))
+    }
+}
+
+/// Read from I/O, skip the initial encoding BOM if present and decode the rest of the stream to UTF-8 according to the BOM.
+///
+/// Streams without a BOM or with a UTF-8 BOM are passed through as-is. Streams with a UTF-16 or UTF-32 BOM are decoded to
+/// UTF-8, with the `encoding_rs` feature streams with a GB18030 BOM are decoded too. Invalid sequences are replaced
+/// with U+FFFD REPLACEMENT CHARACTER. Reading a stream with another BOM fails with an [`ErrorKind::InvalidData`] error.
+/// # Examples
+/// ```
+/// use skip_bom::{BomType, DecodeReader};
+/// use std::io::Read;
+///
+/// let mut reader = DecodeReader::new(BomType::all(), &b"\xFF\xFEH\x00\xE9\x00l\x00l\x00o\x00"[..]);
+/// let mut string = String::new();
+/// let _ = reader.read_to_string(&mut string).unwrap();
+/// assert_eq!("Héllo", &string);
+/// assert_eq!(Some(Some(BomType::UTF16LE)), reader.bom_found());
+/// ```
+pub struct DecodeReader<'a, R: Read> {
+    inner: SkipEncodingBom<'a, R>,
+    decoder: Option<Decoder>,
+    finished: bool,
+    input: [u8; INPUT_BUFFER_LENGTH],
+    input_length: usize,
+    output: [u8; OUTPUT_BUFFER_LENGTH],
+    output_start: usize,
+    output_end: usize,
+}
+
+impl<'a, R: Read> DecodeReader<'a, R> {
+    /// Initialize a decoding reader given any stream reader.
+    ///
+    /// # Arguments
+    ///
+    /// * `bom_types` - a slice with the types of BOM to check for. To skip any of the supported BOMs, pass [`BomType::all`].
+    /// * `reader` - the underlying input stream reader.
+    pub fn new(bom_types: &'a [BomType], reader: R) -> Self {
+        Self::from_skip_encoding_bom(SkipEncodingBom::new(bom_types, reader))
+    }
+    /// Initialize a decoding reader from an encoding BOM skip struct. Reading from it must not have started yet, or only
+    /// through [`SkipEncodingBom::read_bom`].
+    pub fn from_skip_encoding_bom(inner: SkipEncodingBom<'a, R>) -> Self {
+        Self {
+            inner,
+            decoder: None,
+            finished: false,
+            input: [0; INPUT_BUFFER_LENGTH],
+            input_length: 0,
+            output: [0; OUTPUT_BUFFER_LENGTH],
+            output_start: 0,
+            output_end: 0,
+        }
+    }
+    /// Return the BOM found: see [`SkipEncodingBom::bom_found`].
+    pub fn bom_found(&self) -> Option<Option<BomType>> {
+        self.inner.bom_found()
+    }
+    /// Return the presence of a BOM as determined so far: see [`SkipEncodingBom::status`].
+    pub fn status(&self) -> BomStatus {
+        self.inner.status()
+    }
+    /// Unwraps this `DecodeReader<R>`, returning the encoding BOM skip struct. Bytes buffered for decoding are lost.
+    pub fn into_inner(self) -> SkipEncodingBom<'a, R> {
+        self.inner
+    }
+    /// Get a shared reference to the encoding BOM skip struct.
+    pub fn get_ref(&self) -> &SkipEncodingBom<'a, R> {
+        &self.inner
+    }
+    /// Get a mutable reference to the encoding BOM skip struct.
+    pub fn get_mut(&mut self) -> &mut SkipEncodingBom<'a, R> {
+        &mut self.inner
+    }
+}
+
+impl<'a, R: Read + Debug> Debug for DecodeReader<'a, R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.inner.redacts_debug() {
+            // the decoder may hold a part of a character
+            return f
+                .debug_struct("DecodeReader")
+                .field("inner", &self.inner)
+                .field("finished", &self.finished)
+                .field("input_length", &self.input_length)
+                .field("output_length", &(self.output_end - self.output_start))
+                .finish_non_exhaustive();
+        }
+        f.debug_struct("DecodeReader")
+            .field("inner", &self.inner)
+            .field("decoder", &self.decoder)
+            .field("finished", &self.finished)
+            .field("input", &self.input)
+            .field("input_length", &self.input_length)
+            .field("output", &self.output)
+            .field("output_start", &self.output_start)
+            .field("output_end", &self.output_end)
+            .finish()
+    }
+}
+
+impl<'a, R: Read> Read for DecodeReader<'a, R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        loop {
+            if self.output_start < self.output_end {
+                let count = buf.len().min(self.output_end - self.output_start);
+                buf[..count].copy_from_slice(&self.output[self.output_start..(self.output_start + count)]);
+                self.output_start += count;
+                break Ok(count);
+            }
+            if self.finished {
+                break Ok(0);
+            }
+            let decoder = match &mut self.decoder {
+                Some(decoder) => decoder,
+                None => match self.inner.read_bom() {
+                    Ok(_) => match self.inner.bom_found() {
+                        Some(bom_type) => self.decoder.get_or_insert(Decoder::new(bom_type)),
+                        // the BOM presence is not determined yet
+                        None => break Ok(0),
+                    },
+                    Err(error) => break Err(error),
+                },
+            };
+            if let Decoder::Passthrough = decoder {
+                break self.inner.read(buf);
+            }
+            let bytes_read = checked_read(&mut self.inner, &mut self.input[self.input_length..])?;
+            self.input_length += bytes_read;
+            let last = bytes_read == 0;
+            let (decoded, written, finished) = decoder.decode(&self.input[..self.input_length], &mut self.output, last)?;
+            self.input.copy_within(decoded..self.input_length, 0);
+            self.input_length -= decoded;
+            self.output_start = 0;
+            self.output_end = written;
+            self.finished = finished;
+        }
+    }
+}
@@ -0,0 +1,94 @@
+use super::{charset_label, reject_external_decoder, BomError, BomErrorCode, BomType, CharsetReconciliation, Result, SkipEncodingBom};
+
+use encoding_rs::Encoding;
+use encoding_rs_io::{DecodeReaderBytes, DecodeReaderBytesBuilder};
+use std::io::{ErrorKind, Read};
+
+/// Return the `encoding_rs` encoding of the content after a BOM, if `encoding_rs` can decode it.
+pub fn encoding_rs_encoding(bom_type: BomType) -> Option<&'static Encoding> {
+    match bom_type {
+        BomType::UTF8 => Some(encoding_rs::UTF_8),
+        BomType::UTF16LE => Some(encoding_rs::UTF_16LE),
+        BomType::UTF16BE => Some(encoding_rs::UTF_16BE),
+        BomType::GB1803 => Some(encoding_rs::GB18030),
+        _ => None,
+    }
+}
+
+/// `encoding_rs_io` decoder over a reader detecting its BOM, returned by [`decode_reader_bytes`].
+pub type BomDecodeReaderBytes<'a, R> = DecodeReaderBytes<SkipEncodingBom<'a, R>, Vec<u8>>;
+
+/// Stack an `encoding_rs_io` [`DecodeReaderBytes`] over a reader skipping its BOM, and return the BOM found.
+///
+/// The BOM is detected by this crate among `bom_types`, then the decoder is built with the encoding of the BOM found and
+/// its own BOM sniffing disabled. As a decoder with an explicit UTF-8 or UTF-16 encoding removes a leading BOM, these
+/// BOMs are left for it to remove, while the GB18030 BOM is skipped here: exactly one BOM is removed, and a second
+/// U+FEFF at the start of the content is kept. Content without a BOM is passed through unchanged. A BOM that `encoding_rs` cannot decode, such as UTF-32, fails with an
+/// [`ErrorKind::InvalidData`] error. To let `DecodeReaderBytes` own the BOM instead, stack it over a reader built with
+/// [`SkipEncodingBom::with_bom_kept`].
+/// # Examples
+/// ```
+/// use skip_bom::{decode_reader_bytes, BomType};
+/// use std::io::Read;
+///
+/// let (bom, mut reader) = decode_reader_bytes(BomType::all(), &b"\xFF\xFEH\x00i\x00"[..]).unwrap();
+/// assert_eq!(Some(BomType::UTF16LE), bom);
+/// let mut text = String::new();
+/// let _ = reader.read_to_string(&mut text).unwrap();
+/// assert_eq!("Hi", text);
+/// ```
+pub fn decode_reader_bytes<R: Read>(bom_types: &[BomType], reader: R) -> Result<(Option<BomType>, BomDecodeReaderBytes<'_, R>)> {
+    let mut reader = SkipEncodingBom::new(bom_types, reader).with_bom_kept();
+    let bom = reader.read_bom()?;
+    Ok((bom, build_decoder(bom, None, reader)?))
+}
+
+/// Stack an `encoding_rs_io` [`DecodeReaderBytes`] over the reader of a MIME text part, decoding it with the encoding
+/// of its BOM, or of the charset declared in its `Content-Type` if it has no BOM.
+///
+/// `content_type` can either be a full MIME type such as `text/plain; charset="windows-1252"` or a bare charset label.
+/// Following the WHATWG Encoding Standard, the BOM always wins over the declared charset: the returned
+/// [`CharsetReconciliation`] tells whether they conflict. Content without a BOM whose charset is missing or not
+/// recognized by `encoding_rs` is passed through unchanged. The BOM is removed as by [`decode_reader_bytes`], and bytes
+/// that are invalid in the encoding are replaced with U+FFFD.
+/// # Examples
+/// ```
+/// use skip_bom::{decode_mime_part, BomType, CharsetReconciliation};
+/// use std::io::Read;
+///
+/// let (reconciliation, mut reader) = decode_mime_part("text/plain; charset=iso-8859-1", &b"\xFF\xFEH\x00i\x00"[..]).unwrap();
+/// assert_eq!(CharsetReconciliation::Conflict(BomType::UTF16LE), reconciliation);
+/// let mut text = String::new();
+/// let _ = reader.read_to_string(&mut text).unwrap();
+/// assert_eq!("Hi", text);
+///
+/// let (_, mut reader) = decode_mime_part("text/plain; charset=iso-8859-1", &b"caf\xE9"[..]).unwrap();
+/// let mut text = String::new();
+/// let _ = reader.read_to_string(&mut text).unwrap();
+/// assert_eq!("café", text);
+/// ```
+pub fn decode_mime_part<R: Read>(content_type: &str, reader: R) -> Result<(CharsetReconciliation, BomDecodeReaderBytes<'static, R>)> {
+    let mut reader = SkipEncodingBom::new(BomType::all(), reader).with_bom_kept();
+    let bom = reader.read_bom()?;
+    let declared = Encoding::for_label(charset_label(content_type).as_bytes());
+    Ok((CharsetReconciliation::new(bom, content_type), build_decoder(bom, declared, reader)?))
+}
+
+/// Build the decoder of the content after the BOM `bom` kept by `reader`, or with the encoding `declared` if there is no BOM.
+fn build_decoder<'a, R: Read>(bom: Option<BomType>, declared: Option<&'static Encoding>, mut reader: SkipEncodingBom<'a, R>) -> Result<BomDecodeReaderBytes<'a, R>> {
+    let encoding = match bom {
+        Some(bom_type) => match encoding_rs_encoding(bom_type) {
+            Some(encoding) => Some(encoding),
+            None => {
+                reject_external_decoder(bom_type)?;
+                return Err(BomError::new_io(ErrorKind::InvalidData, BomErrorCode::DecoderUnavailable, Some(bom_type), format!("encoding_rs cannot decode {:?} content", bom_type)));
+            },
+        },
+        None => declared,
+    };
+    if let Some(bom_type @ BomType::GB1803) = bom {
+        // encoding_rs only removes the UTF-8 and UTF-16 BOMs: skip the other ones, still buffered at the start
+        reader.read_exact(&mut [0; crate::MAX_BOM_LENGTH as usize][..bom_type.bom_length()])?;
+    }
+    Ok(DecodeReaderBytesBuilder::new().encoding(encoding).bom_sniffing(false).build(reader))
+}
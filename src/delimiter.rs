@@ -0,0 +1,55 @@
+/// Column delimiters recognized by [`guess_delimiter`], in order of preference on a tie.
+pub const DELIMITER_CANDIDATES: [char; 4] = [',', ';', '\t', '|'];
+
+/// Maximum number of rows examined by [`guess_delimiter`].
+const MAX_ROWS: usize = 20;
+
+/// Guess the column delimiter of delimited text such as CSV from the frequency of the [candidates](DELIMITER_CANDIDATES)
+/// over its first rows.
+///
+/// The delimiter is the candidate found in the first row that occurs the same number of times in the most rows.
+/// Delimiters within double-quoted fields are ignored, and an incomplete last row is only examined if it is the only
+/// one, so that a truncated sample can be passed. Returns [`None`] if no candidate occurs in the first row.
+/// # Examples
+/// ```
+/// use skip_bom::guess_delimiter;
+///
+/// assert_eq!(Some(';'), guess_delimiter("id;name;price\n1;\"a, b\";1,50\n2;c;2,00\n"));
+/// assert_eq!(Some('\t'), guess_delimiter("id\tname\n1\ta"));
+/// assert_eq!(None, guess_delimiter("single column\n"));
+/// ```
+pub fn guess_delimiter(text: &str) -> Option<char> {
+    let mut rows: Vec<&str> = text.split_terminator(['\n', '\r']).filter(|row| !row.is_empty()).collect();
+    if rows.len() > 1 && !text.ends_with(['\n', '\r']) {
+        let _ = rows.pop();
+    }
+    rows.truncate(MAX_ROWS);
+    let header = rows.first()?;
+    let mut best = None;
+    for candidate in DELIMITER_CANDIDATES.iter() {
+        let header_count = count_unquoted(header, *candidate);
+        if header_count == 0 {
+            continue;
+        }
+        let consistent_rows = rows.iter().filter(|row| count_unquoted(row, *candidate) == header_count).count();
+        match best {
+            Some((_, best_rows, best_count)) if (best_rows, best_count) >= (consistent_rows, header_count) => (),
+            _ => best = Some((*candidate, consistent_rows, header_count)),
+        }
+    }
+    best.map(|(delimiter, _, _)| delimiter)
+}
+
+/// Count the occurrences of `delimiter` in `row` outside double-quoted fields.
+fn count_unquoted(row: &str, delimiter: char) -> usize {
+    let mut quoted = false;
+    let mut count = 0;
+    for c in row.chars() {
+        match c {
+            '"' => quoted = !quoted,
+            c if c == delimiter && !quoted => count += 1,
+            _ => (),
+        }
+    }
+    count
+}
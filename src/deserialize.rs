@@ -0,0 +1,64 @@
+use super::{BomType, DecodeReader, Result};
+
+use std::io::{Error, ErrorKind, Read};
+
+/// Deserialize a value from a reader with `format_fn` after skipping the BOM and transcoding UTF-16 and UTF-32 content
+/// to UTF-8: see [`DecodeReader`].
+///
+/// Streams starting with a BOM that cannot be decoded fail with an [`ErrorKind::InvalidData`] error, as do the errors
+/// returned by `format_fn`.
+/// # Examples
+/// ```
+/// use skip_bom::from_bom_reader;
+/// use std::io::Read;
+///
+/// let value: u32 = from_bom_reader(&b"\xFF\xFE4\x002\x00"[..], |mut reader| {
+///     let mut string = String::new();
+///     reader.read_to_string(&mut string)?;
+///     string.trim().parse::<u32>().map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+/// }).unwrap();
+/// assert_eq!(42, value);
+/// ```
+pub fn from_bom_reader<T, R, D, E>(reader: R, format_fn: D) -> Result<T>
+where
+    R: Read,
+    D: FnOnce(DecodeReader<'static, R>) -> std::result::Result<T, E>,
+    E: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    format_fn(DecodeReader::new(BomType::all(), reader)).map_err(|e| {
+        let e = e.into();
+        match e.downcast::<Error>() {
+            Ok(io_error) => *io_error,
+            Err(e) => Error::new(ErrorKind::InvalidData, e),
+        }
+    })
+}
+
+/// Deserialize a JSON value from a reader whose content may start with a BOM: see [`from_bom_reader`].
+/// # Examples
+/// ```
+/// use skip_bom::from_json_reader;
+///
+/// let values: Vec<u32> = from_json_reader(&b"\xEF\xBB\xBF[1, 2, 3]"[..]).unwrap();
+/// assert_eq!(vec![1, 2, 3], values);
+/// ```
+#[cfg(all(feature = "serde", feature = "serde_json"))]
+pub fn from_json_reader<T: serde::de::DeserializeOwned, R: Read>(reader: R) -> Result<T> {
+    from_bom_reader(reader, serde_json::from_reader)
+}
+
+/// Deserialize a YAML value from a reader whose content may start with a BOM: see [`from_bom_reader`].
+#[cfg(all(feature = "serde", feature = "serde_yaml"))]
+pub fn from_yaml_reader<T: serde::de::DeserializeOwned, R: Read>(reader: R) -> Result<T> {
+    from_bom_reader(reader, serde_yaml::from_reader)
+}
+
+/// Deserialize a TOML value from a reader whose content may start with a BOM: see [`from_bom_reader`].
+#[cfg(all(feature = "serde", feature = "toml"))]
+pub fn from_toml_reader<T: serde::de::DeserializeOwned, R: Read>(reader: R) -> Result<T> {
+    from_bom_reader(reader, |mut reader| {
+        let mut string = String::new();
+        reader.read_to_string(&mut string)?;
+        toml::from_str(&string).map_err(|e| Error::new(ErrorKind::InvalidData, e))
+    })
+}
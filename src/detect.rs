@@ -0,0 +1,556 @@
+//! Detection and characterization of the encoding of a stream, without reading its content.
+//! # Examples
+//! ```
+//! use skip_bom::detect::{detect, explain, DetectOptions};
+//! use skip_bom::{BomStatus, BomType};
+//!
+//! let report = detect(&b"\xFF\xFEH\x00i\x00"[..], &DetectOptions::default()).unwrap();
+//! assert_eq!(Some(BomType::UTF16LE), report.bom);
+//! assert_eq!(BomStatus::Bom(BomType::UTF16LE), explain(b"\xFF\xFEH\x00i\x00", BomType::all()).verdict);
+//! ```
+
+use super::{checked_read, BomBytesPushBuffer, BomError, BomErrorCode, BomType, BomsBytesTest, CharsetReconciliation, Result};
+
+pub use super::{explain, find_misplaced_bom, BomStats, DetectionTrace, TraceStep};
+pub use super::{preflight, preflight_reader, LineEnding, LineEndingCounts, PreflightReport};
+#[cfg(feature = "delimiter")]
+pub use super::{guess_delimiter, DELIMITER_CANDIDATES};
+
+use std::borrow::Cow;
+use std::io::{ErrorKind, Read};
+
+/// Heuristic used to guess the encoding of a stream when it does not start with a BOM.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum BuiltinSniffer {
+    /// Check that the sample is valid UTF-8.
+    Utf8,
+    /// Look for the NUL byte patterns of mostly-ASCII UTF-16 text.
+    Utf16,
+    /// Look for 32-bit code units in the Unicode range.
+    Utf32,
+    /// Look for the start of an XML declaration, `<?`, in UTF-16 or UTF-32, following the
+    /// [XML specification](https://www.w3.org/TR/xml/#sec-guessing-no-ext-info).
+    XmlDeclaration,
+}
+
+impl BuiltinSniffer {
+    /// Get a slice containing a list of all the built-in sniffers.
+    pub fn all() -> &'static [BuiltinSniffer] {
+        use BuiltinSniffer::*;
+        &[Utf8, Utf16, Utf32, XmlDeclaration]
+    }
+
+    /// Run the sniffer on a sample of the stream content, returning the candidate encoding and its confidence.
+    pub fn sniff(&self, sample: &[u8]) -> Option<(BomType, u8)> {
+        match self {
+            BuiltinSniffer::Utf8 => sniff_utf8(sample),
+            BuiltinSniffer::Utf16 => sniff_utf16(sample),
+            BuiltinSniffer::Utf32 => sniff_utf32(sample),
+            BuiltinSniffer::XmlDeclaration => sniff_xml_declaration(sample),
+        }
+    }
+}
+
+/// Heuristic guessing the encoding of a stream without a BOM, to extend the detection with formats the built-in sniffers
+/// do not recognize: see [`detect_with_sniffers`].
+///
+/// Closures taking the sample and returning the verdict implement this trait. Experimental: requires the `unstable`
+/// feature.
+#[cfg(feature = "unstable")]
+#[cfg_attr(docsrs, doc(cfg(feature = "unstable")))]
+pub trait Sniffer {
+    /// Run the sniffer on a sample of the stream content, returning the candidate encoding and its confidence from 0 to
+    /// 100, or [`None`] if the sample is not recognized.
+    fn sniff(&self, sample: &[u8]) -> Option<(BomType, u8)>;
+}
+
+#[cfg(feature = "unstable")]
+impl Sniffer for BuiltinSniffer {
+    fn sniff(&self, sample: &[u8]) -> Option<(BomType, u8)> {
+        BuiltinSniffer::sniff(self, sample)
+    }
+}
+
+#[cfg(feature = "unstable")]
+impl<F: Fn(&[u8]) -> Option<(BomType, u8)>> Sniffer for F {
+    fn sniff(&self, sample: &[u8]) -> Option<(BomType, u8)> {
+        self(sample)
+    }
+}
+
+/// Requirement on the presence of a BOM.
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BomPolicy {
+    /// The BOM is optional.
+    #[default]
+    Optional,
+    /// A BOM is required: its absence is an error.
+    Required,
+    /// A BOM is forbidden: its presence is an error.
+    Forbidden,
+}
+
+/// Behavior of [`detect`] for streams consisting solely of a BOM, without any content after it.
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BomOnlyPolicy {
+    /// The stream is treated like an empty stream.
+    #[default]
+    Empty,
+    /// A stream consisting solely of a BOM is an error.
+    Error,
+    /// The stream is flagged with [`EncodingReport::empty_with_bom`].
+    Report,
+}
+
+/// Behavior of [`detect`] for streams that appear to contain binary content: see [`detect_binary`].
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BinaryPolicy {
+    /// The content is not checked.
+    #[default]
+    Ignore,
+    /// Binary content is an error.
+    Error,
+    /// Binary content is flagged with [`EncodingReport::probably_binary`].
+    Report,
+}
+
+/// Options for [`detect`].
+///
+/// With the `serde` feature, the options can be deserialized from a configuration file. Missing fields take their default value.
+/// # Examples
+/// ```
+/// use skip_bom::{BomPolicy, BomType, DetectOptions};
+///
+/// let options = DetectOptions {
+///     bom_types: [BomType::UTF8, BomType::UTF16LE].as_slice().into(),
+///     policy: BomPolicy::Required,
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct DetectOptions<'a> {
+    /// BOM types to check for.
+    pub bom_types: Cow<'a, [BomType]>,
+    /// Requirement on the presence of a BOM.
+    pub policy: BomPolicy,
+    /// Behavior for streams consisting solely of a BOM.
+    pub bom_only: BomOnlyPolicy,
+    /// Behavior for streams that appear to contain binary content.
+    pub binary: BinaryPolicy,
+    /// Sniffers to run on the content after the BOM.
+    pub sniffers: Cow<'a, [BuiltinSniffer]>,
+    /// Encoding added as the last candidate when neither a BOM nor a sniffer found any.
+    pub fallback: Option<BomType>,
+    /// Maximum number of bytes read from the start of the stream.
+    pub sample_len: usize,
+    /// Number of bytes of content after the BOM recorded in [`EncodingReport::fingerprint`].
+    pub fingerprint_len: usize,
+    /// Maximum number of ASCII whitespace bytes tolerated before the BOM, for generators emitting spaces or newlines
+    /// before it. With the default of 0, the BOM must be at the very start of the stream.
+    pub leading_whitespace: usize,
+}
+
+impl Default for DetectOptions<'_> {
+    fn default() -> Self {
+        Self {
+            bom_types: BomType::all().into(),
+            policy: BomPolicy::default(),
+            bom_only: BomOnlyPolicy::default(),
+            binary: BinaryPolicy::default(),
+            sniffers: BuiltinSniffer::all().into(),
+            fallback: None,
+            sample_len: 4096,
+            fingerprint_len: 16,
+            leading_whitespace: 0,
+        }
+    }
+}
+
+/// Origin of an encoding candidate.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum CandidateSource {
+    /// The stream starts with the BOM of the encoding.
+    Bom,
+    /// The encoding was guessed by a sniffer.
+    Sniffer(BuiltinSniffer),
+    /// The encoding was guessed by the sniffer at this index of the chain passed to `detect_with_sniffers`, which
+    /// requires the `unstable` feature.
+    Custom(usize),
+    /// The encoding is the fallback of the detection options.
+    Fallback,
+}
+
+/// Encoding candidate found by [`detect`].
+#[must_use]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct EncodingCandidate {
+    /// The candidate encoding.
+    pub encoding: BomType,
+    /// Confidence score, from 0 to 100.
+    pub confidence: u8,
+    /// How the candidate was found.
+    pub source: CandidateSource,
+}
+
+impl EncodingCandidate {
+    /// Returns `true` if the candidate encoding cannot be decoded without a dedicated decoder: see [`BomType::requires_external_decoder`].
+    pub fn requires_external_decoder(&self) -> bool {
+        self.encoding.requires_external_decoder()
+    }
+}
+
+/// Function that can be passed to [`SkipEncodingBom::with_bom_hook`](crate::SkipEncodingBom::with_bom_hook) to reject
+/// the BOM types that [require an external decoder](BomType::requires_external_decoder) with an [`ErrorKind::InvalidData`] error.
+pub fn reject_external_decoder(bom_type: BomType) -> Result<()> {
+    if bom_type.requires_external_decoder() {
+        Err(BomError::new_io(ErrorKind::InvalidData, BomErrorCode::ExternalDecoderRequired, Some(bom_type), format!("the stream starts with a {:?} BOM and requires an external {:?} decoder", bom_type, bom_type)))
+    } else {
+        Ok(())
+    }
+}
+
+/// Confidence score of the candidates found with a BOM.
+pub const BOM_CONFIDENCE: u8 = 100;
+
+/// Result of [`detect`].
+#[must_use]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct EncodingReport {
+    /// The BOM found at the start of the stream.
+    pub bom: Option<BomType>,
+    /// Number of bytes sampled from the start of the stream, including the BOM.
+    pub sample_len: usize,
+    /// `true` if the stream consists solely of a BOM and [`DetectOptions::bom_only`] is [`BomOnlyPolicy::Report`].
+    pub empty_with_bom: bool,
+    /// `true` if the sample appears to contain binary content and [`DetectOptions::binary`] is [`BinaryPolicy::Report`].
+    pub probably_binary: bool,
+    /// Encoding candidates, ranked by decreasing confidence. There is at most one candidate per encoding.
+    pub candidates: Vec<EncodingCandidate>,
+    /// First bytes of the content after the BOM in lowercase hexadecimal, for instance to show in an audit log what
+    /// the stream started with. At most [`DetectOptions::fingerprint_len`] bytes within the sample are recorded.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub fingerprint: String,
+    /// Number of ASCII whitespace bytes skipped before the BOM found, at most [`DetectOptions::leading_whitespace`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub leading_whitespace: usize,
+}
+
+impl EncodingReport {
+    /// Return the candidate with the highest confidence.
+    pub fn best(&self) -> Option<&EncodingCandidate> {
+        self.candidates.first()
+    }
+
+    /// Compare the BOM found with a declared charset: see [`BomType::reconcile_with_charset`].
+    pub fn reconcile_with_charset(&self, declared: &str) -> CharsetReconciliation {
+        CharsetReconciliation::new(self.bom, declared)
+    }
+
+    /// Returns `true` if the BOM found requires an external decoder: such streams should be rejected rather than decoded as text.
+    pub fn decode_unavailable(&self) -> bool {
+        self.bom.is_some_and(|bom_type| bom_type.requires_external_decoder())
+    }
+
+    /// Fail with an [`ErrorKind::InvalidData`] error if the BOM found requires an external decoder.
+    /// # Examples
+    /// ```
+    /// use skip_bom::{detect, DetectOptions};
+    ///
+    /// let report = detect(&b"\xFB\xEE\x28BOCU-1 content"[..], &DetectOptions::default()).unwrap();
+    /// assert!(report.decode_unavailable());
+    /// let error = report.ensure_decodable().unwrap_err();
+    /// assert_eq!("the stream starts with a BOCU1 BOM and requires an external BOCU1 decoder", error.to_string());
+    /// ```
+    pub fn ensure_decodable(&self) -> Result<()> {
+        self.bom.map_or(Ok(()), reject_external_decoder)
+    }
+
+    fn add_candidate(&mut self, candidate: EncodingCandidate) {
+        match self.candidates.iter_mut().find(|existing| existing.encoding == candidate.encoding) {
+            Some(existing) if existing.confidence < candidate.confidence => *existing = candidate,
+            Some(_) => (),
+            None => self.candidates.push(candidate),
+        }
+    }
+}
+
+/// Characterize the start of a stream: look for a BOM then run the enabled sniffers on the content that follows.
+///
+/// At most `options.sample_len` bytes are read from `reader`, and one more if the sample is not longer than a BOM and
+/// its leading whitespace, to tell whether the stream ends with it. Byte slices implement [`Read`] and can be passed
+/// directly. The BOM is only considered found if it is complete within the sample.
+///
+/// In lenient mode, when `options.leading_whitespace` is not 0, the BOM may follow up to this number of ASCII whitespace
+/// bytes: their count is reported in [`EncodingReport::leading_whitespace`], and the content starts after the BOM.
+///
+/// An error of kind [`ErrorKind::InvalidData`] is returned if the presence of a BOM does not follow `options.policy`, or
+/// if the stream consists solely of a BOM and `options.bom_only` is [`BomOnlyPolicy::Error`], or if the stream appears to
+/// contain binary content and `options.binary` is [`BinaryPolicy::Error`].
+/// # Examples
+/// ```
+/// use skip_bom::{detect, BomType, CandidateSource, DetectOptions};
+///
+/// let report = detect(&b"\xFF\xFEH\x00e\x00l\x00l\x00o\x00"[..], &DetectOptions::default()).unwrap();
+/// assert_eq!(Some(BomType::UTF16LE), report.bom);
+/// let best = report.best().unwrap();
+/// assert_eq!((BomType::UTF16LE, CandidateSource::Bom), (best.encoding, best.source));
+///
+/// let report = detect(&b"H\x00e\x00l\x00l\x00o\x00"[..], &DetectOptions::default()).unwrap();
+/// assert_eq!(None, report.bom);
+/// assert_eq!(BomType::UTF16LE, report.best().unwrap().encoding);
+///
+/// let options = DetectOptions { leading_whitespace: 8, ..Default::default() };
+/// let report = detect(&b"\r\n \xEF\xBB\xBFtext"[..], &options).unwrap();
+/// assert_eq!((Some(BomType::UTF8), 3), (report.bom, report.leading_whitespace));
+/// ```
+#[must_use = "the detection report is returned"]
+pub fn detect<R: Read>(reader: R, options: &DetectOptions) -> Result<EncodingReport> {
+    detect_extended(reader, options, |_, _| ())
+}
+
+/// Characterize the start of a stream like [`detect`], running the `sniffers` chain after the built-in sniffers when no
+/// BOM is found.
+///
+/// Each sniffer of the chain is given the sample, and the candidates it returns have the source
+/// [`CandidateSource::Custom`] with its index in the chain.
+/// # Examples
+/// ```
+/// use skip_bom::{detect_with_sniffers, BomType, CandidateSource, DetectOptions, Sniffer};
+///
+/// // proprietary format: UTF-16LE records starting with a magic number
+/// let records = |sample: &[u8]| if sample.starts_with(b"REC1") { Some((BomType::UTF16LE, 90)) } else { None };
+/// let sniffers: [&dyn Sniffer; 1] = [&records];
+/// let report = detect_with_sniffers(&b"REC1\x00\x00"[..], &DetectOptions::default(), &sniffers).unwrap();
+/// let best = report.best().unwrap();
+/// assert_eq!((BomType::UTF16LE, CandidateSource::Custom(0)), (best.encoding, best.source));
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(docsrs, doc(cfg(feature = "unstable")))]
+#[must_use = "the detection report is returned"]
+pub fn detect_with_sniffers<R: Read>(reader: R, options: &DetectOptions, sniffers: &[&dyn Sniffer]) -> Result<EncodingReport> {
+    detect_extended(reader, options, |content, report| {
+        for (index, sniffer) in sniffers.iter().enumerate() {
+            if let Some((encoding, confidence)) = sniffer.sniff(content) {
+                report.add_candidate(EncodingCandidate { encoding, confidence, source: CandidateSource::Custom(index) });
+            }
+        }
+    })
+}
+
+/// Characterize the start of a stream, calling `extend` with the content sampled and the report after the built-in
+/// sniffers when no BOM is found.
+fn detect_extended<R: Read, F: FnOnce(&[u8], &mut EncodingReport)>(reader: R, options: &DetectOptions, extend: F) -> Result<EncodingReport> {
+    let (sample, ended) = read_sample(reader, options)?;
+    detect_sample(&sample[..sample.len().min(options.sample_len)], ended, options, extend)
+}
+
+/// Characterize a sample read by [`read_sample`], `ended` telling whether it is the whole stream.
+pub(crate) fn detect_sample<F: FnOnce(&[u8], &mut EncodingReport)>(sample: &[u8], ended: bool, options: &DetectOptions, extend: F) -> Result<EncodingReport> {
+    let mut report = EncodingReport { sample_len: sample.len(), ..Default::default() };
+    let find_bom = |start: &[u8]| match BomType::try_find_bytes_bom(start, &options.bom_types) {
+        BomsBytesTest::Complete { bom_type, .. } => bom_type,
+        // the stream ended inside a possible BOM
+        BomsBytesTest::Incomplete if ended => BomType::find_bytes_bom_at_end(start, &options.bom_types),
+        // the sample ended inside a possible BOM
+        BomsBytesTest::Incomplete => None,
+    };
+    let whitespace_len = sample.iter().take(options.leading_whitespace).take_while(|byte| byte.is_ascii_whitespace()).count();
+    // the whitespace is only skipped if a BOM follows it
+    let (whitespace_len, bom) = match find_bom(&sample[whitespace_len..]) {
+        Some(bom_type) => (whitespace_len, Some(bom_type)),
+        None => (0, find_bom(sample)),
+    };
+    if let Some(bom_type) = bom {
+        report.bom = Some(bom_type);
+        report.leading_whitespace = whitespace_len;
+        report.add_candidate(EncodingCandidate { encoding: bom_type, confidence: BOM_CONFIDENCE, source: CandidateSource::Bom });
+    }
+    let content = &sample[whitespace_len + bom.map_or(0, |bom_type| bom_type.bom_length())..];
+    report.fingerprint = content.iter().take(options.fingerprint_len).map(|byte| format!("{:02x}", byte)).collect();
+    match (options.policy, report.bom) {
+        (BomPolicy::Required, None) => return Err(BomError::new_io(ErrorKind::InvalidData, BomErrorCode::BomRequired, None, "the stream does not start with a BOM")),
+        (BomPolicy::Forbidden, Some(bom_type)) => return Err(BomError::new_io(ErrorKind::InvalidData, BomErrorCode::BomForbidden, Some(bom_type), "the stream starts with a BOM")),
+        _ => (),
+    }
+    // the whole stream was sampled and it ends with the BOM
+    if report.bom.is_some() && content.is_empty() && ended {
+        match options.bom_only {
+            BomOnlyPolicy::Empty => (),
+            BomOnlyPolicy::Error => return Err(BomError::new_io(ErrorKind::InvalidData, BomErrorCode::BomOnly, report.bom, "the stream consists solely of a BOM")),
+            BomOnlyPolicy::Report => report.empty_with_bom = true,
+        }
+    }
+    // a BOM is a strong sign of text on its own
+    if options.binary != BinaryPolicy::Ignore && report.bom.is_none() && detect_binary(content) {
+        match options.binary {
+            BinaryPolicy::Error => return Err(BomError::new_io(ErrorKind::InvalidData, BomErrorCode::BinaryContent, None, "the stream appears to contain binary content")),
+            _ => report.probably_binary = true,
+        }
+    }
+    for sniffer in options.sniffers.iter() {
+        if let Some((encoding, confidence)) = sniffer.sniff(content) {
+            report.add_candidate(EncodingCandidate { encoding, confidence, source: CandidateSource::Sniffer(*sniffer) });
+        }
+    }
+    if report.bom.is_none() {
+        extend(content, &mut report);
+    }
+    report.candidates.sort_by_key(|candidate| std::cmp::Reverse(candidate.confidence));
+    if let (true, Some(encoding)) = (report.candidates.is_empty(), options.fallback) {
+        report.add_candidate(EncodingCandidate { encoding, confidence: 0, source: CandidateSource::Fallback });
+    }
+    Ok(report)
+}
+
+/// Returns `true` if `sample`, taken from the start of a stream without a BOM, probably contains binary content rather
+/// than text.
+///
+/// Samples recognized as UTF-16 or UTF-32 text are not binary. Other samples are binary if more than 1% of their bytes
+/// are NUL, or if more than 30% of their bytes are not valid UTF-8: legacy 8-bit encodings stay below this ratio.
+/// # Examples
+/// ```
+/// use skip_bom::detect_binary;
+///
+/// assert!(detect_binary(b"\x89PNG\r\n\x1A\n\x00\x00\x00\rIHDR"));
+/// assert!(!detect_binary("Hello, wörld!".as_bytes()));
+/// assert!(!detect_binary(b"H\x00e\x00l\x00l\x00o\x00"));
+/// ```
+pub fn detect_binary(sample: &[u8]) -> bool {
+    if sample.is_empty() || sniff_utf16(sample).is_some() || sniff_utf32(sample).is_some() {
+        return false;
+    }
+    let nul_count = sample.iter().filter(|byte| **byte == 0).count();
+    if nul_count * 100 > sample.len() {
+        return true;
+    }
+    let mut invalid_count = 0;
+    let mut remaining = sample;
+    while let Err(e) = std::str::from_utf8(remaining) {
+        match e.error_len() {
+            Some(error_len) => {
+                invalid_count += error_len;
+                remaining = &remaining[e.valid_up_to() + error_len..];
+            },
+            // the sample may have been cut in the middle of a character
+            None => break,
+        }
+    }
+    invalid_count * 10 > sample.len() * 3
+}
+
+/// Read at most `options.sample_len` bytes from the start of `reader`, and return them with `true` if they are the
+/// whole stream.
+///
+/// A full sample is only told apart from the whole stream if it is not longer than a BOM and its leading whitespace,
+/// as the BOM detection depends on it then: one more byte is read, and appended to the sample if the stream goes on.
+pub(crate) fn read_sample<R: Read>(mut reader: R, options: &DetectOptions) -> Result<(Vec<u8>, bool)> {
+    let mut sample = Vec::new();
+    let mut chunk = [0u8; 1024];
+    let end_check_len = options.leading_whitespace.saturating_add(BomBytesPushBuffer::CAPACITY);
+    let read_len = if options.sample_len <= end_check_len { options.sample_len.saturating_add(1) } else { options.sample_len };
+    while sample.len() < read_len {
+        let chunk_len = chunk.len().min(read_len - sample.len());
+        match checked_read(&mut reader, &mut chunk[..chunk_len]) {
+            Ok(0) => return Ok((sample, true)),
+            Ok(bytes_read) => sample.extend_from_slice(&chunk[..bytes_read]),
+            Err(e) if e.kind() == ErrorKind::Interrupted => (),
+            Err(e) => return Err(e),
+        }
+    }
+    Ok((sample, false))
+}
+
+fn sniff_utf8(sample: &[u8]) -> Option<(BomType, u8)> {
+    let valid = match std::str::from_utf8(sample) {
+        Ok(valid) => valid,
+        // the sample may have been cut in the middle of a character
+        Err(e) if e.error_len().is_none() => std::str::from_utf8(&sample[..e.valid_up_to()]).ok()?,
+        Err(_) => return None,
+    };
+    if valid.is_empty() || valid.contains('\0') {
+        None
+    } else if valid.is_ascii() {
+        // ASCII text is valid UTF-8 but does not prove anything
+        Some((BomType::UTF8, 50))
+    } else {
+        Some((BomType::UTF8, 90))
+    }
+}
+
+/// Confidence for a fraction `matching / total` of code units matching the pattern of an encoding, if it is high enough.
+fn fraction_confidence(matching: usize, total: usize, max_confidence: u8) -> Option<u8> {
+    if total == 0 || matching * 10 < total * 9 {
+        None
+    } else {
+        Some((matching * max_confidence as usize / total) as u8)
+    }
+}
+
+fn sniff_utf16(sample: &[u8]) -> Option<(BomType, u8)> {
+    let units = sample.chunks_exact(2);
+    let total = units.len();
+    let (mut le, mut be) = (0, 0);
+    for unit in units {
+        match unit {
+            [low, 0] if *low != 0 => le += 1,
+            [0, low] if *low != 0 => be += 1,
+            _ => (),
+        }
+    }
+    if le >= be {
+        fraction_confidence(le, total, 80).map(|confidence| (BomType::UTF16LE, confidence))
+    } else {
+        fraction_confidence(be, total, 80).map(|confidence| (BomType::UTF16BE, confidence))
+    }
+}
+
+fn sniff_utf32(sample: &[u8]) -> Option<(BomType, u8)> {
+    let units = sample.chunks_exact(4);
+    let total = units.len();
+    let is_char = |code_point: u32| code_point != 0 && std::char::from_u32(code_point).is_some();
+    let (mut le, mut be) = (0, 0);
+    for unit in units {
+        let unit = [unit[0], unit[1], unit[2], unit[3]];
+        if is_char(u32::from_le_bytes(unit)) {
+            le += 1;
+        }
+        if is_char(u32::from_be_bytes(unit)) {
+            be += 1;
+        }
+    }
+    if le >= be {
+        fraction_confidence(le, total, 85).map(|confidence| (BomType::UTF32LE, confidence))
+    } else {
+        fraction_confidence(be, total, 85).map(|confidence| (BomType::UTF32BE, confidence))
+    }
+}
+
+fn sniff_xml_declaration(sample: &[u8]) -> Option<(BomType, u8)> {
+    let encoding = match sample.get(..4)? {
+        [0x3C, 0x00, 0x3F, 0x00] => BomType::UTF16LE,
+        [0x00, 0x3C, 0x00, 0x3F] => BomType::UTF16BE,
+        [0x3C, 0x00, 0x00, 0x00] => BomType::UTF32LE,
+        [0x00, 0x00, 0x00, 0x3C] => BomType::UTF32BE,
+        // an XML declaration in an ASCII-compatible encoding does not tell which one
+        _ => return None,
+    };
+    Some((encoding, 95))
+}
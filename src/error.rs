@@ -0,0 +1,112 @@
+use super::BomType;
+
+use std::borrow::Cow;
+use std::{error, fmt, io};
+
+/// Stable code of the errors returned by this crate, to handle or translate them without matching their message.
+///
+/// The errors are [`io::Error`]s wrapping a [`BomError`]: see [`BomErrorCode::of`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BomErrorCode {
+    /// A BOM is required but the stream does not start with one.
+    BomRequired,
+    /// A BOM is forbidden but the stream starts with one.
+    BomForbidden,
+    /// The stream consists solely of a BOM.
+    BomOnly,
+    /// The stream appears to contain binary content.
+    BinaryContent,
+    /// The stream contains control characters.
+    ControlCharacters,
+    /// The stream starts with a BOM whose encoding requires an external decoder.
+    ExternalDecoderRequired,
+    /// Decoding the stream requires an optional feature of this crate.
+    DecoderUnavailable,
+    /// The underlying reader returned more bytes than the buffer it was given can hold.
+    ReaderOverReported,
+    /// More bytes were pushed to a BOM bytes buffer than it can hold.
+    BufferOverflow,
+    /// The bytes converted to a [`BomType`] are not exactly a BOM.
+    NotBom,
+    /// The operation was cancelled by the caller.
+    Cancelled,
+    /// The stream was rejected by a [`ReadPolicy`](crate::ReadPolicy).
+    PolicyRejected,
+    /// The stream exceeds the size limit set by the caller.
+    LimitExceeded,
+}
+
+impl BomErrorCode {
+    /// Returns the code as a stable identifier, for instance to look up a translated message in a catalog.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            BomErrorCode::BomRequired => "bom-required",
+            BomErrorCode::BomForbidden => "bom-forbidden",
+            BomErrorCode::BomOnly => "bom-only",
+            BomErrorCode::BinaryContent => "binary-content",
+            BomErrorCode::ControlCharacters => "control-characters",
+            BomErrorCode::ExternalDecoderRequired => "external-decoder-required",
+            BomErrorCode::DecoderUnavailable => "decoder-unavailable",
+            BomErrorCode::ReaderOverReported => "reader-over-reported",
+            BomErrorCode::BufferOverflow => "buffer-overflow",
+            BomErrorCode::NotBom => "not-bom",
+            BomErrorCode::Cancelled => "cancelled",
+            BomErrorCode::PolicyRejected => "policy-rejected",
+            BomErrorCode::LimitExceeded => "limit-exceeded",
+        }
+    }
+
+    /// Returns the code of an error returned by this crate, or [`None`] for other errors such as those of the
+    /// underlying reader.
+    /// # Examples
+    /// ```
+    /// use skip_bom::{detect, BomErrorCode, BomPolicy, DetectOptions};
+    ///
+    /// let options = DetectOptions { policy: BomPolicy::Required, ..Default::default() };
+    /// let error = detect(&b"no BOM"[..], &options).unwrap_err();
+    /// assert_eq!(Some(BomErrorCode::BomRequired), BomErrorCode::of(&error));
+    /// assert_eq!("bom-required", BomErrorCode::of(&error).unwrap().as_str());
+    /// ```
+    pub fn of(error: &io::Error) -> Option<Self> {
+        BomError::of(error).map(BomError::code)
+    }
+}
+
+/// Error returned by this crate, wrapped in an [`io::Error`]: see [`BomError::of`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BomError {
+    code: BomErrorCode,
+    bom_type: Option<BomType>,
+    message: Cow<'static, str>,
+}
+
+impl BomError {
+    /// Create an [`io::Error`] of kind `kind` wrapping a `BomError`.
+    pub(crate) fn new_io<M: Into<Cow<'static, str>>>(kind: io::ErrorKind, code: BomErrorCode, bom_type: Option<BomType>, message: M) -> io::Error {
+        io::Error::new(kind, BomError { code, bom_type, message: message.into() })
+    }
+
+    /// Returns the `BomError` wrapped by an error returned by this crate, or [`None`] for other errors.
+    pub fn of(error: &io::Error) -> Option<&BomError> {
+        error.get_ref()?.downcast_ref::<BomError>()
+    }
+
+    /// Returns the stable code of the error.
+    pub fn code(&self) -> BomErrorCode {
+        self.code
+    }
+
+    /// Returns the BOM type the error is about, if any, to be used as a parameter of a translated message.
+    pub fn bom_type(&self) -> Option<BomType> {
+        self.bom_type
+    }
+}
+
+impl fmt::Display for BomError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl error::Error for BomError {}
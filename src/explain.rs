@@ -0,0 +1,96 @@
+use super::{BomStatus, BomType, BomsBytesTest};
+
+use std::fmt;
+
+/// Step of a [`DetectionTrace`]: the BOM types ruled out by one more byte.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TraceStep {
+    /// The byte examined.
+    pub byte: u8,
+    /// BOM types this byte does not match, while the previous bytes did.
+    pub eliminated: Vec<BomType>,
+    /// BOM types still matching the bytes examined so far.
+    pub remaining: Vec<BomType>,
+}
+
+/// Trace of the BOM detection on the start of a stream, returned by [`explain`].
+///
+/// Its [`Display`](fmt::Display) implementation gives a one-line summary to embed in error messages.
+#[must_use]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DetectionTrace {
+    /// Bytes examined to reach the verdict.
+    pub examined: Vec<u8>,
+    /// One step per byte examined.
+    pub steps: Vec<TraceStep>,
+    /// The BOM presence determined, [`BomStatus::Undetermined`] if the bytes end inside a possible BOM.
+    pub verdict: BomStatus,
+}
+
+/// Trace the BOM detection on `start_bytes`, the start of a stream, with the BOM types `bom_types`.
+///
+/// The bytes are examined one by one until the BOM presence is determined, as [`BomType::try_find_bytes_bom`] would.
+/// # Examples
+/// ```
+/// use skip_bom::{explain, BomStatus, BomType};
+///
+/// let trace = explain(b"\xFE\xFF\x00H\x00i", BomType::all());
+/// assert_eq!(BomStatus::Bom(BomType::UTF16BE), trace.verdict);
+/// assert_eq!(b"\xFE\xFF", trace.examined.as_slice());
+/// assert_eq!(
+///     "file rejected: starts with FE FF (UTF-16BE BOM) but UTF-8 required",
+///     format!("file rejected: {} but UTF-8 required", trace),
+/// );
+/// ```
+pub fn explain(start_bytes: &[u8], bom_types: &[BomType]) -> DetectionTrace {
+    let mut remaining: Vec<BomType> = BomType::all_sorted_by_length().iter().copied().filter(|bom_type| bom_types.contains(bom_type)).collect();
+    let mut steps = Vec::new();
+    let mut verdict = BomStatus::Undetermined;
+    for (index, byte) in start_bytes.iter().enumerate() {
+        let examined = &start_bytes[..=index];
+        let (still_matching, eliminated) = remaining.iter().partition(|bom_type| examined.starts_with(bom_type.bom_bytes()) || bom_type.bom_bytes().starts_with(examined));
+        remaining = still_matching;
+        steps.push(TraceStep { byte: *byte, eliminated, remaining: remaining.clone() });
+        if let BomsBytesTest::Complete { bom_type, .. } = BomType::try_find_bytes_bom(examined, bom_types) {
+            verdict = bom_type.map_or(BomStatus::NoBom, BomStatus::Bom);
+            break;
+        }
+    }
+    let examined = start_bytes[..steps.len()].to_vec();
+    DetectionTrace { examined, steps, verdict }
+}
+
+/// Conventional name of the encoding of a BOM type.
+fn encoding_name(bom_type: BomType) -> &'static str {
+    match bom_type {
+        BomType::UTF8 => "UTF-8",
+        BomType::UTF16LE => "UTF-16LE",
+        BomType::UTF16BE => "UTF-16BE",
+        BomType::UTF32LE => "UTF-32LE",
+        BomType::UTF32BE => "UTF-32BE",
+        BomType::UTF7 => "UTF-7",
+        BomType::UTF1 => "UTF-1",
+        BomType::UTFEBDIC => "UTF-EBCDIC",
+        BomType::SCSU => "SCSU",
+        BomType::BOCU1 => "BOCU-1",
+        BomType::GB1803 => "GB18030",
+    }
+}
+
+impl fmt::Display for DetectionTrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.examined.is_empty() {
+            f.write_str("is empty")?;
+        } else {
+            f.write_str("starts with")?;
+            for byte in &self.examined {
+                write!(f, " {:02X}", byte)?;
+            }
+        }
+        match self.verdict {
+            BomStatus::Bom(bom_type) => write!(f, " ({} BOM)", encoding_name(bom_type)),
+            BomStatus::NoBom => f.write_str(" (no BOM)"),
+            _ => f.write_str(" (BOM presence undetermined)"),
+        }
+    }
+}
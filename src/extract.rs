@@ -0,0 +1,95 @@
+use super::{BomErrorCode, EncodingReport, ManagedReader, PolicyAction, PolicyCondition, ReadPolicy};
+
+use axum::body::Bytes;
+use axum::extract::rejection::BytesRejection;
+use axum::extract::{FromRequest, Request};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use std::{error, fmt, io};
+
+/// Axum extractor buffering the request body and handing the handler its text without the BOM, decoded to UTF-8, along
+/// with the detection report.
+///
+/// The body is handled by a [`ManagedReader`] with the [`ReadPolicy`] of the request extensions, which can be set for a
+/// router with an `Extension` layer. Without one, the BOMs of UTF-16 and UTF-32 are transcoded and the others skipped.
+/// # Examples
+/// ```
+/// use axum::body::Body;
+/// use axum::extract::{FromRequest, Request};
+/// use skip_bom::{BomCleanText, BomType};
+///
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// let request = Request::new(Body::from(&b"\xFF\xFEH\x00i\x00"[..]));
+/// let BomCleanText { text, report } = BomCleanText::from_request(request, &()).await.unwrap();
+/// assert_eq!("Hi", text);
+/// assert_eq!(Some(BomType::UTF16LE), report.bom);
+/// # });
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct BomCleanText {
+    /// Text of the body, without its BOM.
+    pub text: String,
+    /// Detection report on the start of the body.
+    pub report: EncodingReport,
+}
+
+impl BomCleanText {
+    /// Return the policy applied to bodies when the request extensions hold none.
+    pub fn default_policy() -> ReadPolicy {
+        ReadPolicy::default().rule(PolicyCondition::Utf16, PolicyAction::Transcode).rule(PolicyCondition::Utf32, PolicyAction::Transcode)
+    }
+}
+
+impl<S: Send + Sync> FromRequest<S> for BomCleanText {
+    type Rejection = BomCleanTextRejection;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let policy = req.extensions().get::<ReadPolicy>().cloned().unwrap_or_else(Self::default_policy);
+        let body = Bytes::from_request(req, state).await.map_err(BomCleanTextRejection::Body)?;
+        let (text, report) = ManagedReader::new(&body[..], &policy).and_then(ManagedReader::into_utf8_string).map_err(BomCleanTextRejection::Content)?;
+        Ok(Self { text, report })
+    }
+}
+
+/// Rejection of the [`BomCleanText`] extractor.
+#[derive(Debug)]
+pub enum BomCleanTextRejection {
+    /// The body could not be buffered.
+    Body(BytesRejection),
+    /// The body was rejected by the policy or is not valid text.
+    Content(io::Error),
+}
+
+impl fmt::Display for BomCleanTextRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BomCleanTextRejection::Body(rejection) => rejection.fmt(f),
+            BomCleanTextRejection::Content(e) => e.fmt(f),
+        }
+    }
+}
+
+impl error::Error for BomCleanTextRejection {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            BomCleanTextRejection::Body(rejection) => Some(rejection),
+            BomCleanTextRejection::Content(e) => Some(e),
+        }
+    }
+}
+
+impl IntoResponse for BomCleanTextRejection {
+    /// Bodies rejected by the policy get a `415 Unsupported Media Type` response and invalid text a `400 Bad Request`.
+    fn into_response(self) -> Response {
+        match self {
+            BomCleanTextRejection::Body(rejection) => rejection.into_response(),
+            BomCleanTextRejection::Content(e) => {
+                let status = match BomErrorCode::of(&e) {
+                    Some(BomErrorCode::PolicyRejected) => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                    _ => StatusCode::BAD_REQUEST,
+                };
+                (status, e.to_string()).into_response()
+            },
+        }
+    }
+}
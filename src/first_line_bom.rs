@@ -0,0 +1,117 @@
+use super::{BomType, BomsBytesTest, Result};
+
+use std::io::{Cursor, Read};
+
+/// Read from I/O and remove an encoding BOM found within the first line, leaving everything from the first CR or LF on
+/// untouched.
+///
+/// This is meant for line-based protocol servers that must tolerate clients prefixing their requests with a BOM, for
+/// instance after leading whitespace, but must never modify the stream past the first line. Once the first CR or LF
+/// or a BOM is found, reads go directly to the underlying reader.
+/// # Examples
+/// ```
+/// use skip_bom::{BomType, FirstLineBom};
+/// use std::io::Read;
+///
+/// let mut reader = FirstLineBom::new(&[BomType::UTF8], &b"  \xEF\xBB\xBFHELO example.org\r\n\xEF\xBB\xBFdata"[..]);
+/// let mut request = Vec::new();
+/// let _ = reader.read_to_end(&mut request).unwrap();
+/// assert_eq!(b"  HELO example.org\r\n\xEF\xBB\xBFdata", request.as_slice());
+/// assert_eq!(Some(Some(BomType::UTF8)), reader.bom_found());
+/// ```
+#[derive(Debug, Clone)]
+pub struct FirstLineBom<'a, R: Read> {
+    bom_types: &'a [BomType],
+    /// `true` while the first line is being scanned for a BOM.
+    scanning: bool,
+    /// Bytes of the first line that may be the start of a BOM, not returned yet.
+    carry: Vec<u8>,
+    /// Bytes scanned that did not fit in the caller buffer.
+    pending: Cursor<Vec<u8>>,
+    bom_found: Option<Option<BomType>>,
+    reader: R,
+}
+
+impl<'a, R: Read> FirstLineBom<'a, R> {
+    /// Initialize a first line BOM removing reader given any stream reader.
+    ///
+    /// # Arguments
+    ///
+    /// * `bom_types` - a slice with the types of BOM to check for. To skip any of the supported BOMs, pass [`BomType::all`].
+    /// * `reader` - the underlying input stream reader.
+    pub fn new(bom_types: &'a [BomType], reader: R) -> Self {
+        Self { bom_types, scanning: true, carry: Vec::new(), pending: Cursor::new(Vec::new()), bom_found: None, reader }
+    }
+
+    /// Return the BOM removed as an inner [`Option`], [`None`] if the first line had none, or [`None`] for the outer
+    /// option while the first line is still being scanned.
+    #[must_use]
+    pub fn bom_found(&self) -> Option<Option<BomType>> {
+        self.bom_found
+    }
+
+    /// Unwraps this `FirstLineBom<R>`, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Get a shared reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.reader
+    }
+
+    /// Scan the bytes read, returning the bytes to return to the caller.
+    fn scan(&mut self, mut bytes: Vec<u8>) -> Vec<u8> {
+        let line_end = bytes.iter().position(|byte| *byte == b'\r' || *byte == b'\n');
+        for start in 0..line_end.unwrap_or(bytes.len()) {
+            match BomType::try_find_bytes_bom(&bytes[start..], self.bom_types) {
+                BomsBytesTest::Complete { bom_type: Some(bom_type), .. } => {
+                    let _ = bytes.drain(start..start + bom_type.bom_length());
+                    self.stop_scanning(Some(bom_type));
+                    return bytes;
+                },
+                BomsBytesTest::Complete { bom_type: None, .. } => (),
+                // the end of the bytes read may be the start of a BOM
+                BomsBytesTest::Incomplete => {
+                    self.carry = bytes.split_off(start);
+                    return bytes;
+                },
+            }
+        }
+        if line_end.is_some() {
+            self.stop_scanning(None);
+        }
+        bytes
+    }
+
+    fn stop_scanning(&mut self, bom_type: Option<BomType>) {
+        self.scanning = false;
+        self.bom_found = Some(bom_type);
+    }
+}
+
+impl<'a, R: Read> Read for FirstLineBom<'a, R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        if (self.pending.position() as usize) < self.pending.get_ref().len() {
+            return self.pending.read(buf);
+        }
+        loop {
+            if !self.scanning || buf.is_empty() {
+                return self.reader.read(buf);
+            }
+            let bytes_read = self.reader.read(buf)?;
+            let mut bytes = std::mem::take(&mut self.carry);
+            if bytes_read == 0 {
+                // the stream ended within the first line: the bytes carried are not a BOM
+                self.stop_scanning(None);
+            } else {
+                bytes.extend_from_slice(&buf[..bytes_read]);
+                bytes = self.scan(bytes);
+            }
+            if !bytes.is_empty() || bytes_read == 0 {
+                self.pending = Cursor::new(bytes);
+                return self.pending.read(buf);
+            }
+        }
+    }
+}
@@ -0,0 +1,94 @@
+use super::{BomType, ProgressiveRead, Result, SkipEncodingBom};
+
+use std::fs::File;
+use std::io::{ErrorKind, Read, Seek};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+
+/// Default interval between two polls of a followed file: see [`FollowNoBom::with_poll_interval`].
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Reader following a file as it grows, like `tail -f`, without its initial encoding BOM: see [`follow_no_bom`].
+#[derive(Debug)]
+pub struct FollowNoBom {
+    path: PathBuf,
+    poll_interval: Duration,
+    reader: Option<SkipEncodingBom<'static, File>>,
+}
+
+/// Follow a file as it grows, such as a log file written by a Windows service, skipping its BOM.
+///
+/// The file does not need to exist yet: it is opened once created. Reads then block until data is appended, polling
+/// the file: they never return 0 bytes, except for an empty buffer. The BOM is looked for when the file is first
+/// created, and again from the start if the file is truncated, for instance when a log is rotated in place.
+/// # Examples
+/// ```no_run
+/// use skip_bom::follow_no_bom;
+/// use std::io::{BufRead, BufReader};
+///
+/// for line in BufReader::new(follow_no_bom("service.log")).lines() {
+///     println!("{}", line.unwrap());
+/// }
+/// ```
+pub fn follow_no_bom<P: AsRef<Path>>(path: P) -> FollowNoBom {
+    FollowNoBom { path: path.as_ref().to_path_buf(), poll_interval: DEFAULT_POLL_INTERVAL, reader: None }
+}
+
+impl FollowNoBom {
+    /// Set the interval between two polls of the file, [`DEFAULT_POLL_INTERVAL`] by default.
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Returns the path of the file followed.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Return the BOM found as for [`SkipEncodingBom::bom_found`]: [`None`] until the file is created and its BOM
+    /// presence determined.
+    #[must_use]
+    pub fn bom_found(&self) -> Option<Option<BomType>> {
+        self.reader.as_ref().and_then(SkipEncodingBom::bom_found)
+    }
+
+    /// Read the data available, returning 0 bytes if there is none yet.
+    fn poll(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let reader = match &mut self.reader {
+            Some(reader) => reader,
+            None => match File::open(&self.path) {
+                Ok(file) => self.reader.get_or_insert(SkipEncodingBom::new(BomType::all(), file)),
+                Err(e) if e.kind() == ErrorKind::NotFound => return Ok(0),
+                Err(e) => return Err(e),
+            },
+        };
+        if let ProgressiveRead::Read(bytes_read) = reader.poll_more(buf)? {
+            return Ok(bytes_read);
+        }
+        let position = reader.get_mut().stream_position()?;
+        if reader.get_ref().metadata()?.len() < position {
+            // truncated: start over
+            reader.rewind()?;
+            if let ProgressiveRead::Read(bytes_read) = reader.poll_more(buf)? {
+                return Ok(bytes_read);
+            }
+        }
+        Ok(0)
+    }
+}
+
+impl Read for FollowNoBom {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        loop {
+            match self.poll(buf)? {
+                0 => thread::sleep(self.poll_interval),
+                bytes_read => return Ok(bytes_read),
+            }
+        }
+    }
+}
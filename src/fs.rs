@@ -0,0 +1,76 @@
+//! Files and paths: opening text files, stripping the BOM of files in place and reading lists of paths.
+//! # Examples
+//! ```
+//! use skip_bom::fs::PathExt;
+//! use std::path::Path;
+//!
+//! assert!(Path::new("missing.txt").read_text_no_bom().is_err());
+//! ```
+
+use super::{BomType, DecodeReader, Result};
+
+pub use super::{path_lines, read_path_list, PathLines};
+pub use super::{strip_bom_file, strip_bom_file_with_progress, StripFileOptions, StripFileReport};
+#[cfg(feature = "follow")]
+pub use super::{follow_no_bom, FollowNoBom, DEFAULT_POLL_INTERVAL};
+
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+/// Extension trait opening text files while skipping their initial encoding BOM.
+///
+/// It is implemented for [`Path`], so the methods can also be called on a [`PathBuf`](std::path::PathBuf).
+/// # Examples
+/// ```no_run
+/// use skip_bom::PathExt;
+/// use std::path::Path;
+///
+/// let text = Path::new("notes.txt").read_text_no_bom().unwrap();
+/// println!("{}", text);
+/// ```
+pub trait PathExt {
+    /// Open the file for reading, skip its BOM if present and decode it to UTF-8 according to the BOM: see [`DecodeReader`].
+    fn open_text_no_bom(&self) -> Result<DecodeReader<'static, File>>;
+    /// Read the whole file to a string, skipping its BOM if present and decoding it to UTF-8 according to the BOM.
+    ///
+    /// Like [`std::fs::read_to_string`], this fails with an [`ErrorKind::InvalidData`](std::io::ErrorKind::InvalidData)
+    /// error if a file without a BOM or with a UTF-8 BOM is not valid UTF-8.
+    fn read_text_no_bom(&self) -> Result<String>;
+}
+
+impl PathExt for Path {
+    fn open_text_no_bom(&self) -> Result<DecodeReader<'static, File>> {
+        Ok(DecodeReader::new(BomType::all(), File::open(self)?))
+    }
+
+    fn read_text_no_bom(&self) -> Result<String> {
+        let mut text = String::new();
+        let _ = self.open_text_no_bom()?.read_to_string(&mut text)?;
+        Ok(text)
+    }
+}
+
+/// Load a configuration file to a string, for instance before parsing it as TOML or INI, and return it along with the
+/// BOM found.
+///
+/// The BOM is skipped, UTF-16 and UTF-32 content is decoded to UTF-8 and CRLF line endings are normalized to LF, so
+/// that files edited with Windows tools such as Notepad parse the same as any other.
+/// # Examples
+/// ```no_run
+/// use skip_bom::load_config_str;
+///
+/// let (config, bom) = load_config_str("config.toml").unwrap();
+/// if let Some(bom_type) = bom {
+///     eprintln!("config.toml starts with a {:?} BOM", bom_type);
+/// }
+/// ```
+pub fn load_config_str<P: AsRef<Path>>(path: P) -> Result<(String, Option<BomType>)> {
+    let mut reader = path.as_ref().open_text_no_bom()?;
+    let mut text = String::new();
+    let _ = reader.read_to_string(&mut text)?;
+    if text.contains('\r') {
+        text = text.replace("\r\n", "\n");
+    }
+    Ok((text, reader.bom_found().flatten()))
+}
@@ -0,0 +1,64 @@
+use super::SkipEncodingBom;
+
+use std::io::Read;
+use std::ops::{Deref, DerefMut};
+
+/// Mutable access to the reader underlying a [`SkipEncodingBom`], returned by [`SkipEncodingBom::inner_mut`].
+///
+/// If the reader is mutably accessed while the BOM detection is in progress, the detection restarts when the guard is
+/// dropped: see [`SkipEncodingBom::inner_mut`].
+#[derive(Debug)]
+pub struct InnerGuard<'r, 'a, R: Read + ?Sized> {
+    skip_encoding_bom: &'r mut SkipEncodingBom<'a, R>,
+    touched: bool,
+}
+
+impl<'a, R: Read + ?Sized> SkipEncodingBom<'a, R> {
+    /// Get guarded mutable access to the underlying reader.
+    ///
+    /// Unlike with [`SkipEncodingBom::get_mut`], touching the reader while the BOM presence is not determined or while
+    /// start bytes are still buffered marks the detection state stale: the start bytes buffered are discarded and the
+    /// BOM is looked for again from the position of the reader once the guard is dropped, see
+    /// [`SkipEncodingBom::restart_detection`]. Once the buffered bytes are all returned, touching the reader has no
+    /// effect on the detection. Use [`SkipEncodingBom::get_mut`] to deliberately keep the state, for instance to append
+    /// bytes to a stream whose BOM presence could not be determined yet.
+    /// # Examples
+    /// ```
+    /// use skip_bom::{BomType, SkipEncodingBom};
+    /// use std::io::{Cursor, Read};
+    ///
+    /// let mut reader = SkipEncodingBom::new(BomType::all(), Cursor::new(b"\xEF\xBB".to_vec()));
+    /// assert_eq!(None, reader.read_bom().unwrap());
+    /// // the stream is replaced: the start bytes buffered are discarded
+    /// *reader.inner_mut() = Cursor::new(b"\xEF\xBB\xBFtext".to_vec());
+    /// let mut text = String::new();
+    /// let _ = reader.read_to_string(&mut text).unwrap();
+    /// assert_eq!("text", text);
+    /// ```
+    pub fn inner_mut(&mut self) -> InnerGuard<'_, 'a, R> {
+        InnerGuard { skip_encoding_bom: self, touched: false }
+    }
+}
+
+impl<R: Read + ?Sized> Deref for InnerGuard<'_, '_, R> {
+    type Target = R;
+
+    fn deref(&self) -> &R {
+        self.skip_encoding_bom.get_ref()
+    }
+}
+
+impl<R: Read + ?Sized> DerefMut for InnerGuard<'_, '_, R> {
+    fn deref_mut(&mut self) -> &mut R {
+        self.touched = true;
+        self.skip_encoding_bom.get_mut()
+    }
+}
+
+impl<R: Read + ?Sized> Drop for InnerGuard<'_, '_, R> {
+    fn drop(&mut self) {
+        if self.touched && self.skip_encoding_bom.detection_in_progress() {
+            self.skip_encoding_bom.restart_detection();
+        }
+    }
+}
@@ -30,6 +30,19 @@
 //! // Check the BOM after the read is over.
 //! assert_eq!(Some(Some(BomType::UTF8)), reader.bom_found());
 //! ```
+//!
+//! ## Module layout
+//! The items are grouped by subsystem in the [`reader`], [`writer`], [`detect`](mod@detect), [`decode`], [`fs`] and
+//! `asynchronous` modules. The core types such as [`BomType`] and [`BomError`] stay at the root of the crate.
+//!
+//! All the items are also re-exported at the root of the crate, where the 0.5 releases exported [`SkipEncodingBom`], so
+//! that existing paths keep compiling. No deprecated shims are provided for the root paths: `#[deprecated]` has no
+//! effect on a re-export, and the other grouped items were never released at the root. New code should prefer the
+//! module paths: the root re-exports of the grouped items may be removed in the next breaking release.
+
+#![forbid(unsafe_code)]
+#![warn(missing_docs)]
+#![cfg_attr(docsrs, feature(doc_cfg))]
 
 #[cfg(doctest)]
 doc_comment::doctest!("../README.md");
@@ -40,11 +53,191 @@ pub type Result<T> = std::io::Result<T>;
 mod bom_type;
 pub use bom_type::*;
 
+mod signature;
+pub(crate) use signature::*;
+
+mod signature_pattern;
+pub use signature_pattern::*;
+
+mod bom_type_set;
+pub use bom_type_set::*;
+
+mod bom_status;
+pub use bom_status::*;
+
+mod bom_char;
+pub use bom_char::*;
+
+mod lines;
+pub use lines::*;
+
+mod utf8_chunks;
+pub use utf8_chunks::*;
+
+mod first_line_bom;
+pub use first_line_bom::*;
+
+mod shebang_bom;
+pub use shebang_bom::*;
+
 mod skip_encoding_bom;
 pub use skip_encoding_bom::*;
 
+mod inner_guard;
+pub use inner_guard::*;
+
+mod prelude_bytes;
+
+mod buf_read;
+pub use buf_read::*;
+
+mod no_bom_slice;
+pub use no_bom_slice::*;
+
+#[cfg(feature = "bytes")]
+mod bytes_message;
+#[cfg(feature = "bytes")]
+pub use bytes_message::*;
+
+mod charset;
+pub use charset::*;
+
+pub mod detect;
+pub use detect::*;
+
+mod explain;
+pub use explain::*;
+
+mod bom_stats;
+pub use bom_stats::*;
+
+mod managed;
+pub use managed::*;
+
+mod multipart;
+pub use multipart::*;
+
+mod read_limited;
+pub use read_limited::*;
+
+mod error;
+pub use error::*;
+
+#[cfg(any(feature = "flate2", feature = "zstd"))]
+mod compression;
+
+#[cfg(feature = "zip")]
+mod zip_archive;
+#[cfg(feature = "zip")]
+pub use zip_archive::*;
+
+mod resolved_bom;
+pub use resolved_bom::*;
+
+pub mod typestate;
+
+#[cfg(feature = "magic")]
+pub mod magic;
+
+#[cfg(feature = "rspfile")]
+pub mod rspfile;
+
+#[cfg(feature = "websocket")]
+pub mod websocket;
+
+mod strip_bom_writer;
+pub use strip_bom_writer::*;
+
+mod text_sanitizer;
+pub use text_sanitizer::*;
+
+mod tee;
+pub use tee::*;
+
+pub mod decode;
+pub use decode::*;
+
+mod deserialize;
+pub use deserialize::*;
+
+#[cfg(all(feature = "encoding_rs", feature = "encoding_rs_io"))]
+mod decode_reader_bytes;
+#[cfg(all(feature = "encoding_rs", feature = "encoding_rs_io"))]
+pub use decode_reader_bytes::*;
+
+#[cfg(feature = "quick-xml")]
+mod xml;
+#[cfg(feature = "quick-xml")]
+pub use xml::*;
+
+mod copy;
+pub use copy::*;
+
+mod misplaced_bom;
+pub use misplaced_bom::*;
+
+pub mod fs;
+pub use fs::*;
+
+#[cfg(feature = "follow")]
+mod follow;
+#[cfg(feature = "follow")]
+pub use follow::*;
+
+#[cfg(feature = "tokio")]
+mod poll;
+#[cfg(feature = "tokio")]
+pub use poll::*;
+
+#[cfg(feature = "futures-core")]
+mod chunk_stream;
+#[cfg(feature = "futures-core")]
+pub use chunk_stream::*;
+
+#[cfg(feature = "axum")]
+mod extract;
+#[cfg(feature = "axum")]
+pub use extract::*;
+
+mod strip_file;
+pub use strip_file::*;
+
+mod preflight;
+pub use preflight::*;
+
+#[cfg(feature = "delimiter")]
+mod delimiter;
+#[cfg(feature = "delimiter")]
+pub use delimiter::*;
+
+mod path_list;
+pub use path_list::*;
+
+mod process;
+pub use process::*;
+
+pub mod prelude;
+
+pub mod reader;
+
+pub mod writer;
+
+#[cfg(any(feature = "tokio", feature = "futures-core", feature = "axum"))]
+pub mod asynchronous;
+
+#[cfg(windows)]
+mod windows_console;
+#[cfg(windows)]
+pub use windows_console::*;
+
+mod checked_read;
+pub(crate) use checked_read::*;
+
 mod bom_state;
 pub(crate) use bom_state::*;
 
+mod read_bom_from;
+pub use read_bom_from::*;
+
 mod byte_push_buffer;
-pub(crate) use byte_push_buffer::*;
+pub use byte_push_buffer::*;
@@ -0,0 +1,44 @@
+use super::{remove_bom_char, Result};
+
+/// Iterator adapter removing a leading U+FEFF from the first line yielded: see [`strip_bom_from_first_line`].
+#[derive(Debug, Clone)]
+pub struct StripBomFromFirstLine<I> {
+    lines: I,
+    first: bool,
+}
+
+impl<I: Iterator<Item = Result<String>>> Iterator for StripBomFromFirstLine<I> {
+    type Item = Result<String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let line = self.lines.next()?;
+        if !std::mem::replace(&mut self.first, false) {
+            return Some(line);
+        }
+        Some(line.map(|mut line| {
+            let _ = remove_bom_char(&mut line);
+            line
+        }))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.lines.size_hint()
+    }
+}
+
+/// Remove a leading U+FEFF from the first line of an iterator of lines such as [`BufRead::lines`](std::io::BufRead::lines).
+///
+/// This is meant for code that cannot easily wrap its reader in a [`SkipEncodingBom`](crate::SkipEncodingBom). Only a
+/// UTF-8 BOM can be removed this way, and only from the first item: if it is an error, the following lines are left untouched.
+/// # Examples
+/// ```
+/// use skip_bom::strip_bom_from_first_line;
+/// use std::io::{BufRead, Cursor};
+///
+/// let reader = Cursor::new("\u{FEFF}first line\n\u{FEFF}second line\n");
+/// let lines: Vec<String> = strip_bom_from_first_line(reader.lines()).collect::<Result<_, _>>().unwrap();
+/// assert_eq!(vec!["first line", "\u{FEFF}second line"], lines);
+/// ```
+pub fn strip_bom_from_first_line<I: IntoIterator<Item = Result<String>>>(lines: I) -> StripBomFromFirstLine<I::IntoIter> {
+    StripBomFromFirstLine { lines: lines.into_iter(), first: true }
+}
@@ -0,0 +1,51 @@
+//! Recognition of common non-text file signatures, or magic numbers, besides the encoding BOMs.
+//!
+//! This is meant to route streams that are not text, such as compressed archives or images, away from text processing.
+//! The non-text signatures are kept separate from [`BomType`]: they are never skipped by the readers of this crate.
+//! # Examples
+//! ```
+//! use skip_bom::magic::{find_signature, Signature};
+//! use skip_bom::BomType;
+//!
+//! assert_eq!(Some(Signature::Bom(BomType::UTF8)), find_signature(b"\xEF\xBB\xBFtext", BomType::all()));
+//! assert_eq!(Some(Signature::Other("png")), find_signature(b"\x89PNG\r\n\x1A\n", BomType::all()));
+//! assert_eq!(None, find_signature(b"text", BomType::all()));
+//! ```
+
+use super::{BomType, BomsBytesTest};
+
+/// Non-text signatures recognized by [`find_signature`], with their name.
+pub const MAGIC_NUMBERS: &[(&str, &[u8])] = &[
+    ("gzip", &[0x1F, 0x8B]),
+    ("zip", b"PK\x03\x04"),
+    // empty archive
+    ("zip", b"PK\x05\x06"),
+    ("pdf", b"%PDF-"),
+    ("png", b"\x89PNG\r\n\x1A\n"),
+];
+
+/// Number of bytes from the start of a stream needed to recognize any signature.
+pub const MAGIC_PROBE_LEN: usize = 8;
+
+/// Signature found at the start of a stream.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Signature {
+    /// An encoding BOM: the stream is text.
+    Bom(BomType),
+    /// A non-text signature from [`MAGIC_NUMBERS`], with its name.
+    Other(&'static str),
+}
+
+/// Return the signature `bytes` starts with, checking for the BOMs of `bom_types` first.
+///
+/// `bytes` are the start of a stream: [`MAGIC_PROBE_LEN`] bytes are enough unless the stream is shorter.
+pub fn find_signature(bytes: &[u8], bom_types: &[BomType]) -> Option<Signature> {
+    let bom_type = match BomType::try_find_bytes_bom(bytes, bom_types) {
+        BomsBytesTest::Complete { bom_type, .. } => bom_type,
+        BomsBytesTest::Incomplete => BomType::find_bytes_bom_at_end(bytes, bom_types),
+    };
+    bom_type.map(Signature::Bom).or_else(|| {
+        MAGIC_NUMBERS.iter().find(|(_, magic)| bytes.starts_with(magic)).map(|(name, _)| Signature::Other(name))
+    })
+}
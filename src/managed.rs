@@ -0,0 +1,214 @@
+use super::{detect_sample, read_sample, BomError, BomErrorCode, BomType, DecodeReader, DetectOptions, EncodingReport, Result};
+
+use std::io::{Chain, Cursor, ErrorKind, Read};
+
+/// Detection outcome a [`PolicyRule`] applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PolicyCondition {
+    /// The stream starts with the BOM of this type.
+    Bom(BomType),
+    /// The stream starts with a UTF-16 BOM, little or big endian.
+    Utf16,
+    /// The stream starts with a UTF-32 BOM, little or big endian.
+    Utf32,
+    /// The stream starts with a BOM whose encoding [requires an external decoder](BomType::requires_external_decoder).
+    ExternalDecoder,
+    /// The stream starts with any BOM.
+    AnyBom,
+    /// The stream does not start with a BOM.
+    NoBom,
+    /// Any stream.
+    Any,
+}
+
+impl PolicyCondition {
+    /// Returns `true` if a stream starting with the BOM `bom`, if any, meets the condition.
+    pub fn matches(&self, bom: Option<BomType>) -> bool {
+        match (self, bom) {
+            (PolicyCondition::Bom(expected), Some(bom_type)) => *expected == bom_type,
+            (PolicyCondition::Utf16, Some(bom_type)) => matches!(bom_type, BomType::UTF16LE | BomType::UTF16BE),
+            (PolicyCondition::Utf32, Some(bom_type)) => matches!(bom_type, BomType::UTF32LE | BomType::UTF32BE),
+            (PolicyCondition::ExternalDecoder, Some(bom_type)) => bom_type.requires_external_decoder(),
+            (PolicyCondition::AnyBom, Some(_)) | (PolicyCondition::NoBom, None) | (PolicyCondition::Any, _) => true,
+            _ => false,
+        }
+    }
+}
+
+/// Handling of a stream by a [`ManagedReader`].
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PolicyAction {
+    /// Skip the BOM and pass the content through unchanged: for a stream without a BOM, assume it is UTF-8.
+    #[default]
+    Skip,
+    /// Skip the BOM and transcode the content to UTF-8 with [`DecodeReader`].
+    Transcode,
+    /// Pass the stream through unchanged, BOM included.
+    Keep,
+    /// Reject the stream with an [`ErrorKind::InvalidData`] error of code [`BomErrorCode::PolicyRejected`].
+    Reject,
+}
+
+/// Rule of a [`ReadPolicy`]: streams meeting the condition are handled with the action.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PolicyRule {
+    /// Condition on the detection outcome.
+    pub when: PolicyCondition,
+    /// Action for the streams meeting the condition.
+    pub action: PolicyAction,
+}
+
+/// Rules mapping the BOM found at the start of a stream to its handling, executed by a [`ManagedReader`].
+///
+/// The first rule whose condition is met applies, or [`ReadPolicy::default_action`] if none is. With the `serde` feature,
+/// the policy can be deserialized from a configuration file. Missing fields take their default value.
+/// # Examples
+/// ```
+/// use skip_bom::{BomType, PolicyAction, PolicyCondition, ReadPolicy};
+///
+/// let policy = ReadPolicy::default()
+///     .rule(PolicyCondition::Utf16, PolicyAction::Transcode)
+///     .rule(PolicyCondition::NoBom, PolicyAction::Skip)
+///     .rule(PolicyCondition::Bom(BomType::SCSU), PolicyAction::Reject);
+/// assert_eq!(PolicyAction::Transcode, policy.action(Some(BomType::UTF16BE)));
+/// assert_eq!(PolicyAction::Reject, policy.action(Some(BomType::SCSU)));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct ReadPolicy {
+    /// Rules, in the order they are tried.
+    pub rules: Vec<PolicyRule>,
+    /// Action for the streams that meet no rule.
+    pub default_action: PolicyAction,
+}
+
+impl ReadPolicy {
+    /// Add a rule after the existing ones.
+    pub fn rule(mut self, when: PolicyCondition, action: PolicyAction) -> Self {
+        self.rules.push(PolicyRule { when, action });
+        self
+    }
+
+    /// Return the action for a stream starting with the BOM `bom`, if any.
+    pub fn action(&self, bom: Option<BomType>) -> PolicyAction {
+        self.rules.iter().find(|rule| rule.when.matches(bom)).map_or(self.default_action, |rule| rule.action)
+    }
+}
+
+/// Stream read back after its start was sampled for the detection.
+type SampledReader<R> = Chain<Cursor<Vec<u8>>, R>;
+
+#[derive(Debug)]
+enum ManagedInner<R: Read> {
+    Bytes(SampledReader<R>),
+    // the decoder holds its buffers inline
+    Decode(Box<DecodeReader<'static, SampledReader<R>>>),
+}
+
+/// Reader detecting the encoding of a stream then handling it as a [`ReadPolicy`] prescribes, wiring the BOM skipping
+/// and transcoding itself.
+///
+/// The start of the stream is sampled and characterized with [`detect()`](crate::detect()), then read again as content.
+/// # Examples
+/// ```
+/// use skip_bom::{BomType, ManagedReader, PolicyAction, PolicyCondition, ReadPolicy};
+/// use std::io::Read;
+///
+/// let policy = ReadPolicy::default().rule(PolicyCondition::Utf16, PolicyAction::Transcode);
+/// let mut reader = ManagedReader::new(&b"\xFF\xFEH\x00i\x00"[..], &policy).unwrap();
+/// assert_eq!(Some(BomType::UTF16LE), reader.report().bom);
+/// let mut text = String::new();
+/// let _ = reader.read_to_string(&mut text).unwrap();
+/// assert_eq!("Hi", text);
+/// ```
+#[derive(Debug)]
+pub struct ManagedReader<R: Read> {
+    report: EncodingReport,
+    action: PolicyAction,
+    inner: ManagedInner<R>,
+}
+
+impl<R: Read> ManagedReader<R> {
+    /// Detect the encoding of the stream with the default [`DetectOptions`] and apply `policy`.
+    ///
+    /// Fails with an [`ErrorKind::InvalidData`] error if the action is [`PolicyAction::Reject`].
+    pub fn new(reader: R, policy: &ReadPolicy) -> Result<Self> {
+        Self::with_detect_options(reader, policy, &DetectOptions::default())
+    }
+
+    /// Detect the encoding of the stream with `options` and apply `policy`.
+    ///
+    /// Fails like [`detect()`](crate::detect()) if the stream does not meet the requirements of `options`.
+    pub fn with_detect_options(mut reader: R, policy: &ReadPolicy, options: &DetectOptions) -> Result<Self> {
+        let (mut sample, ended) = read_sample(&mut reader, options)?;
+        let report = detect_sample(&sample[..sample.len().min(options.sample_len)], ended, options, |_, _| ())?;
+        let action = policy.action(report.bom);
+        if matches!(action, PolicyAction::Transcode | PolicyAction::Skip) {
+            // the whitespace tolerated before the BOM is removed with it
+            let _ = sample.drain(..report.leading_whitespace);
+        }
+        let inner = match (action, report.bom) {
+            (PolicyAction::Reject, bom) => {
+                let message = match bom {
+                    Some(bom_type) => format!("streams starting with a {:?} BOM are rejected", bom_type),
+                    None => "streams without a BOM are rejected".to_string(),
+                };
+                return Err(BomError::new_io(ErrorKind::InvalidData, BomErrorCode::PolicyRejected, bom, message));
+            },
+            (PolicyAction::Transcode, Some(bom_type)) => {
+                let all = BomType::all();
+                let index = all.iter().position(|candidate| *candidate == bom_type).unwrap_or_default();
+                ManagedInner::Decode(Box::new(DecodeReader::new(&all[index..=index], Cursor::new(sample).chain(reader))))
+            },
+            (PolicyAction::Skip, Some(bom_type)) => {
+                let _ = sample.drain(..bom_type.bom_length());
+                ManagedInner::Bytes(Cursor::new(sample).chain(reader))
+            },
+            _ => ManagedInner::Bytes(Cursor::new(sample).chain(reader)),
+        };
+        Ok(Self { report, action, inner })
+    }
+
+    /// Return the detection report on the start of the stream.
+    pub fn report(&self) -> &EncodingReport {
+        &self.report
+    }
+
+    /// Return the action applied to the stream.
+    pub fn action(&self) -> PolicyAction {
+        self.action
+    }
+
+    /// Read the whole stream as handled by the policy into a [`String`], and return it with the detection report.
+    ///
+    /// Fails with an [`ErrorKind::InvalidData`] error if the content read is not valid UTF-8, for instance a UTF-16
+    /// stream that the policy does not transcode.
+    /// # Examples
+    /// ```
+    /// use skip_bom::{BomType, ManagedReader, PolicyAction, PolicyCondition, ReadPolicy};
+    ///
+    /// let policy = ReadPolicy::default().rule(PolicyCondition::Utf16, PolicyAction::Transcode);
+    /// let (text, report) = ManagedReader::new(&b"\xFE\xFF\x00H\x00i"[..], &policy).unwrap().into_utf8_string().unwrap();
+    /// assert_eq!("Hi", text);
+    /// assert_eq!(Some(BomType::UTF16BE), report.bom);
+    /// ```
+    pub fn into_utf8_string(mut self) -> Result<(String, EncodingReport)> {
+        let mut text = String::new();
+        let _ = self.read_to_string(&mut text)?;
+        Ok((text, self.report))
+    }
+}
+
+impl<R: Read> Read for ManagedReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        match &mut self.inner {
+            ManagedInner::Bytes(reader) => reader.read(buf),
+            ManagedInner::Decode(reader) => reader.read(buf),
+        }
+    }
+}
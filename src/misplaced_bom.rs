@@ -0,0 +1,25 @@
+use super::BomType;
+
+/// Returns the offset of a UTF-8 BOM misplaced after a few ASCII bytes at the start of `bytes`, if the BOM follows at
+/// most `window` ASCII bytes.
+///
+/// Tools concatenating headers before BOM-prefixed templates produce such streams, for instance PowerShell or Batch
+/// scripts where the BOM ends up inside the first token. A BOM at the very start is not misplaced: see
+/// [`BomType::try_find_bytes_bom`]. [`CopyOptions::misplaced_bom_window`](crate::CopyOptions::misplaced_bom_window)
+/// removes such BOMs with [`copy_without_bom`](crate::copy_without_bom) only.
+/// # Examples
+/// ```
+/// use skip_bom::find_misplaced_bom;
+///
+/// assert_eq!(Some(2), find_misplaced_bom(b"@e\xEF\xBB\xBFcho off", 8));
+/// assert_eq!(None, find_misplaced_bom(b"@echo\xEF\xBB\xBF off", 4));
+/// assert_eq!(None, find_misplaced_bom(b"\xEF\xBB\xBF@echo off", 8));
+/// ```
+pub fn find_misplaced_bom(bytes: &[u8], window: usize) -> Option<usize> {
+    let offset = bytes.iter().take(window).take_while(|byte| byte.is_ascii()).count();
+    if offset > 0 && bytes[offset..].starts_with(BomType::UTF8.bom_bytes()) {
+        Some(offset)
+    } else {
+        None
+    }
+}
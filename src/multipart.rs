@@ -0,0 +1,93 @@
+use super::{DetectOptions, EncodingReport, ManagedReader, ReadPolicy, Result};
+
+use std::collections::BTreeMap;
+use std::io::Read;
+
+/// Sanitizer of the parts of a multipart upload, such as a `multipart/form-data` request, whatever the multipart library.
+///
+/// Each part reader is wrapped in a [`ManagedReader`] applying the same [`ReadPolicy`]: its BOM is skipped and its
+/// content optionally transcoded. The detection report of each part is recorded under its field name. When several
+/// parts have the same field name, the report of the last one is kept.
+/// # Examples
+/// ```
+/// use skip_bom::{BomType, MultipartSanitizer, PolicyAction, PolicyCondition, ReadPolicy};
+/// use std::io::Read;
+///
+/// let mut sanitizer = MultipartSanitizer::new(ReadPolicy::default().rule(PolicyCondition::Utf16, PolicyAction::Transcode));
+/// let mut text = String::new();
+/// let _ = sanitizer.sanitize("notes", &b"\xFF\xFEH\x00i\x00"[..]).unwrap().read_to_string(&mut text).unwrap();
+/// assert_eq!("Hi", text);
+/// let _ = sanitizer.sanitize("title", &b"\xEF\xBB\xBFtitle"[..]).unwrap();
+/// assert_eq!(Some(BomType::UTF16LE), sanitizer.reports()["notes"].bom);
+/// assert_eq!(Some(BomType::UTF8), sanitizer.reports()["title"].bom);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MultipartSanitizer<'a> {
+    policy: ReadPolicy,
+    options: DetectOptions<'a>,
+    reports: BTreeMap<String, EncodingReport>,
+}
+
+impl<'a> MultipartSanitizer<'a> {
+    /// Initialize a sanitizer applying `policy` to the parts, detected with the default [`DetectOptions`].
+    pub fn new(policy: ReadPolicy) -> Self {
+        Self::with_detect_options(policy, DetectOptions::default())
+    }
+
+    /// Initialize a sanitizer applying `policy` to the parts, detected with `options`.
+    pub fn with_detect_options(policy: ReadPolicy, options: DetectOptions<'a>) -> Self {
+        Self { policy, options, reports: BTreeMap::new() }
+    }
+
+    /// Wrap the reader of the part named `field_name` in a [`ManagedReader`] and record its detection report.
+    ///
+    /// Fails like [`ManagedReader::with_detect_options`], in which case no report is recorded.
+    pub fn sanitize<R: Read>(&mut self, field_name: &str, part: R) -> Result<ManagedReader<R>> {
+        let reader = ManagedReader::with_detect_options(part, &self.policy, &self.options)?;
+        let _ = self.reports.insert(field_name.to_string(), reader.report().clone());
+        Ok(reader)
+    }
+
+    /// Sanitize the [`AsyncRead`](tokio::io::AsyncRead) of the part named `field_name` and record its detection report.
+    ///
+    /// The start of the part is read and handled as [`sanitize`](Self::sanitize) would, then chained with the rest of
+    /// the part. As the decoder is synchronous, a part to transcode is read in full and transcoded at once.
+    /// # Examples
+    /// ```
+    /// use skip_bom::{BomType, MultipartSanitizer, ReadPolicy};
+    /// use tokio::io::AsyncReadExt;
+    ///
+    /// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+    /// let mut sanitizer = MultipartSanitizer::new(ReadPolicy::default());
+    /// let mut text = String::new();
+    /// let _ = sanitizer.sanitize_async("title", &b"\xEF\xBB\xBFtitle"[..]).await.unwrap().read_to_string(&mut text).await.unwrap();
+    /// assert_eq!("title", text);
+    /// assert_eq!(Some(BomType::UTF8), sanitizer.reports()["title"].bom);
+    /// # });
+    /// ```
+    #[cfg(feature = "tokio")]
+    pub async fn sanitize_async<R: tokio::io::AsyncRead + Unpin>(&mut self, field_name: &str, mut part: R) -> Result<tokio::io::Chain<std::io::Cursor<Vec<u8>>, R>> {
+        use tokio::io::AsyncReadExt;
+
+        let mut bytes = Vec::new();
+        // one more byte than the sample tells whether the part ends with it, see `detect`
+        let _ = (&mut part).take((self.options.sample_len as u64).saturating_add(1)).read_to_end(&mut bytes).await?;
+        if self.policy.action(super::detect(bytes.as_slice(), &self.options)?.bom) == super::PolicyAction::Transcode {
+            let _ = part.read_to_end(&mut bytes).await?;
+        }
+        let mut reader = self.sanitize(field_name, bytes.as_slice())?;
+        let mut content = Vec::new();
+        let _ = reader.read_to_end(&mut content)?;
+        Ok(AsyncReadExt::chain(std::io::Cursor::new(content), part))
+    }
+
+    /// Return the detection reports of the parts sanitized so far, by field name.
+    pub fn reports(&self) -> &BTreeMap<String, EncodingReport> {
+        &self.reports
+    }
+
+    /// Unwraps this `MultipartSanitizer`, returning the detection reports of the parts by field name.
+    pub fn into_reports(self) -> BTreeMap<String, EncodingReport> {
+        self.reports
+    }
+}
@@ -0,0 +1,57 @@
+use super::{BomType, SkipEncodingBom};
+
+use std::ops::Deref;
+
+/// Borrowed view of bytes, for instance from a memory-mapped file, without their initial encoding BOM.
+///
+/// It dereferences to the bytes after the BOM and keeps the BOM found, so that zero-copy parsers get clean input along
+/// with the encoding information.
+/// # Examples
+/// ```
+/// use skip_bom::{BomType, NoBomSlice};
+///
+/// let slice = NoBomSlice::new(BomType::all(), b"\xEF\xBB\xBF{\"key\": 1}");
+/// assert_eq!(Some(BomType::UTF8), slice.bom());
+/// assert_eq!(b"{\"key\": 1}", &*slice);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NoBomSlice<'a> {
+    bom: Option<BomType>,
+    content: &'a [u8],
+}
+
+impl<'a> NoBomSlice<'a> {
+    /// Look for a BOM at the start of `bytes`, the whole input: see [`SkipEncodingBom::from_slice`].
+    ///
+    /// # Arguments
+    ///
+    /// * `bom_types` - a slice with the types of BOM to check for. To skip any of the supported BOMs, pass [`BomType::all`].
+    /// * `bytes` - the input bytes.
+    pub fn new(bom_types: &[BomType], bytes: &'a [u8]) -> Self {
+        let reader = SkipEncodingBom::from_slice(bom_types, bytes);
+        Self { bom: reader.bom_found().flatten(), content: reader.into_inner() }
+    }
+    /// Return the BOM found, or [`None`] if the bytes do not start with a BOM.
+    #[must_use]
+    pub fn bom(&self) -> Option<BomType> {
+        self.bom
+    }
+    /// Return the bytes after the BOM, borrowed for the lifetime of the input rather than of the view.
+    pub fn content(&self) -> &'a [u8] {
+        self.content
+    }
+}
+
+impl Deref for NoBomSlice<'_> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.content
+    }
+}
+
+impl AsRef<[u8]> for NoBomSlice<'_> {
+    fn as_ref(&self) -> &[u8] {
+        self.content
+    }
+}
@@ -0,0 +1,60 @@
+use super::{BomType, DecodeReader, Result};
+
+use std::fs::File;
+use std::io::{BufRead, BufReader, Lines, Read};
+use std::path::{Path, PathBuf};
+
+/// Iterator over the paths of a list file, one per line: see [`path_lines`].
+#[derive(Debug)]
+pub struct PathLines<R: Read> {
+    lines: Lines<BufReader<DecodeReader<'static, R>>>,
+}
+
+impl<R: Read> Iterator for PathLines<R> {
+    type Item = Result<PathBuf>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let line = match self.lines.next()? {
+                Ok(line) => line,
+                Err(e) => return Some(Err(e)),
+            };
+            let path = clean_path_line(&line);
+            if !path.is_empty() {
+                return Some(Ok(PathBuf::from(path)));
+            }
+        }
+    }
+}
+
+/// Remove what is not part of the path from a line of a list file.
+fn clean_path_line(line: &str) -> &str {
+    // U+FEFF left by the concatenation of files with BOMs, then CR and spaces
+    let path = line.trim_start_matches('\u{FEFF}').trim();
+    match (path.len() >= 2, path.strip_prefix('"').and_then(|path| path.strip_suffix('"'))) {
+        (true, Some(unquoted)) => unquoted,
+        _ => path,
+    }
+}
+
+/// Iterate over the paths listed in a stream, one per line, such as a file list generated by a build tool.
+///
+/// The BOM is skipped and UTF-16 and UTF-32 content is decoded, see [`DecodeReader`]. Each line is cleaned up: the
+/// line ending, surrounding whitespace, surrounding double quotes and stray U+FEFF characters are removed, and empty
+/// lines are skipped.
+/// # Examples
+/// ```
+/// use skip_bom::path_lines;
+/// use std::path::PathBuf;
+///
+/// let paths: Vec<PathBuf> = path_lines(&b"\xEF\xBB\xBFsrc\\main.c\r\n\r\n  \"include\\my header.h\"  \r\n"[..]).collect::<Result<_, _>>().unwrap();
+/// assert_eq!(vec![PathBuf::from("src\\main.c"), PathBuf::from("include\\my header.h")], paths);
+/// ```
+pub fn path_lines<R: Read>(reader: R) -> PathLines<R> {
+    PathLines { lines: BufReader::new(DecodeReader::new(BomType::all(), reader)).lines() }
+}
+
+/// Read the paths listed in a file, one per line: see [`path_lines`].
+pub fn read_path_list<P: AsRef<Path>>(path: P) -> Result<Vec<PathBuf>> {
+    path_lines(File::open(path)?).collect()
+}
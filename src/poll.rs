@@ -0,0 +1,284 @@
+use super::{BomBytesPushBuffer, BomState, BomType, BomsBytesTest, Result};
+
+use std::future::Future;
+use std::io::Read;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::io::{AsyncBufRead, AsyncRead, ReadBuf};
+
+/// BOM detection state owned by the caller of [`poll_skip_bom`], to skip the BOM of an [`AsyncRead`] without
+/// wrapping it.
+///
+/// It holds the start bytes read while the BOM presence is not determined: it must be kept along with the reader and
+/// passed to each poll of this reader.
+#[derive(Debug, Clone)]
+pub struct BomPollState<'a> {
+    state: BomState,
+    bom_types: &'a [BomType],
+    probe_len: usize,
+}
+
+impl<'a> BomPollState<'a> {
+    /// Initialize the detection state of a stream, with the types of BOM to skip.
+    pub fn new(bom_types: &'a [BomType]) -> Self {
+        Self { state: BomState::default(), bom_types, probe_len: BomType::max_probe_len(bom_types) }
+    }
+
+    /// Return the BOM found, or [`None`] for the outer option if the presence of a BOM was not determined yet: see
+    /// [`SkipEncodingBom::bom_found`](crate::SkipEncodingBom::bom_found).
+    pub fn bom_found(&self) -> Option<Option<BomType>> {
+        self.state.bom_found()
+    }
+
+    /// Returns `true` once the BOM presence is determined and no start bytes are buffered: polls then go directly to
+    /// the reader.
+    pub fn is_resolved(&self) -> bool {
+        matches!(self.state, BomState::Final(_))
+    }
+
+    /// Take the start bytes buffered and not returned yet, which were read after the BOM or could not be confirmed to
+    /// be a BOM: the next polls go directly to the reader.
+    pub fn take_leftover(&mut self) -> Vec<u8> {
+        let leftover = match &self.state {
+            BomState::Initial { start_bytes } => start_bytes.bytes().to_vec(),
+            BomState::PostInitBuffer { bytes_after_bom, .. } => {
+                let position = (bytes_after_bom.position() as usize).min(bytes_after_bom.get_ref().byte_count());
+                bytes_after_bom.get_ref().bytes()[position..].to_vec()
+            },
+            BomState::Final(_) => Vec::new(),
+        };
+        self.state = BomState::Final(self.state.bom_found().flatten());
+        leftover
+    }
+}
+
+/// Poll `reader` for the content after its BOM, like [`AsyncRead::poll_read`], with the detection state kept by the
+/// caller: async runtimes and manual future implementations can skip the BOM without a wrapper type.
+///
+/// The start bytes read while the BOM presence is not determined are kept in `state`, so that no byte is lost if the
+/// poll returns [`Poll::Pending`] and the future polling it is dropped. As with [`SkipEncodingBom::end_of_stream`](crate::SkipEncodingBom::end_of_stream),
+/// a stream ending inside a possible BOM returns the bytes read as content.
+/// # Examples
+/// ```
+/// use skip_bom::{poll_skip_bom, BomPollState, BomType};
+/// use std::future::Future;
+/// use std::pin::Pin;
+/// use std::task::{Context, Poll};
+/// use tokio::io::{AsyncRead, ReadBuf};
+///
+/// /// Future reading the whole content of a stream.
+/// struct ReadContent<'a, R> {
+///     reader: R,
+///     state: BomPollState<'a>,
+///     content: Vec<u8>,
+/// }
+///
+/// impl<R: AsyncRead + Unpin> Future for ReadContent<'_, R> {
+///     type Output = std::io::Result<Vec<u8>>;
+///
+///     fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+///         let this = &mut *self;
+///         loop {
+///             let mut bytes = [0; 64];
+///             let mut buf = ReadBuf::new(&mut bytes);
+///             match poll_skip_bom(cx, Pin::new(&mut this.reader), &mut this.state, &mut buf) {
+///                 Poll::Ready(Ok(())) if buf.filled().is_empty() => return Poll::Ready(Ok(std::mem::take(&mut this.content))),
+///                 Poll::Ready(Ok(())) => this.content.extend_from_slice(buf.filled()),
+///                 Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+///                 Poll::Pending => return Poll::Pending,
+///             }
+///         }
+///     }
+/// }
+///
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// let reader = &b"\xEF\xBB\xBFcontent"[..];
+/// let content = ReadContent { reader, state: BomPollState::new(BomType::all()), content: Vec::new() }.await.unwrap();
+/// assert_eq!(b"content", content.as_slice());
+/// # });
+/// ```
+pub fn poll_skip_bom<R: AsyncRead + ?Sized>(cx: &mut Context<'_>, mut reader: Pin<&mut R>, state: &mut BomPollState<'_>, buf: &mut ReadBuf<'_>) -> Poll<Result<()>> {
+    match poll_resolve(cx, reader.as_mut(), state) {
+        Poll::Ready(Ok(())) => (),
+        polled => return polled,
+    }
+    match &mut state.state {
+        BomState::PostInitBuffer { bytes_after_bom, bom_type } => {
+            let bytes_read = bytes_after_bom.read(buf.initialize_unfilled())?;
+            buf.advance(bytes_read);
+            if bytes_after_bom.position() == bytes_after_bom.get_ref().byte_count() as u64 {
+                state.state = BomState::Final(bom_type.take());
+            }
+            Poll::Ready(Ok(()))
+        },
+        _ => reader.poll_read(cx, buf),
+    }
+}
+
+/// Poll `reader` until the BOM presence is determined.
+fn poll_resolve<R: AsyncRead + ?Sized>(cx: &mut Context<'_>, mut reader: Pin<&mut R>, state: &mut BomPollState<'_>) -> Poll<Result<()>> {
+    loop {
+        match &mut state.state {
+            BomState::Initial { start_bytes } => {
+                let mut bytes = [0; BomBytesPushBuffer::CAPACITY];
+                let read_len = state.probe_len.min(BomBytesPushBuffer::CAPACITY).saturating_sub(start_bytes.byte_count()).max(1);
+                let mut read_buf = ReadBuf::new(&mut bytes[..read_len]);
+                match reader.as_mut().poll_read(cx, &mut read_buf) {
+                    Poll::Pending => return Poll::Pending,
+                    Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                    Poll::Ready(Ok(())) => (),
+                }
+                let ended = read_buf.filled().is_empty();
+                start_bytes.push(read_buf.filled())?;
+                let start_bytes = *start_bytes;
+                state.state = match BomType::try_find_bytes_bom(start_bytes.bytes(), state.bom_types) {
+                    BomsBytesTest::Complete { bom_type, additional_bytes } => BomState::after_bom(bom_type, BomBytesPushBuffer::from_slice(additional_bytes)?),
+                    BomsBytesTest::Incomplete if ended => {
+                        let bom_type = BomType::find_bytes_bom_at_end(start_bytes.bytes(), state.bom_types);
+                        let bom_length = bom_type.map_or(0, |bom_type| bom_type.bom_length());
+                        BomState::after_bom(bom_type, BomBytesPushBuffer::from_slice(&start_bytes.bytes()[bom_length..])?)
+                    },
+                    BomsBytesTest::Incomplete => BomState::Initial { start_bytes },
+                };
+            },
+            _ => return Poll::Ready(Ok(())),
+        }
+    }
+}
+
+/// Skip the initial encoding BOM of an [`AsyncRead`], the asynchronous counterpart of
+/// [`SkipEncodingBom`](crate::SkipEncodingBom) built on [`poll_skip_bom`].
+///
+/// When the underlying reader implements [`AsyncBufRead`], so does this struct: once the start bytes buffered during
+/// the detection are returned, its buffer is passed through, which lets [`tokio::io::copy_buf`] read straight from it.
+/// Proxies can also call [`into_inner_async`](Self::into_inner_async) once the BOM is skipped to get rid of the wrapper.
+/// # Examples
+/// ```
+/// use skip_bom::{AsyncSkipEncodingBom, BomType};
+/// use tokio::io::BufReader;
+///
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// let mut reader = AsyncSkipEncodingBom::new(BomType::all(), BufReader::new(&b"\xEF\xBB\xBFcontent"[..]));
+/// let mut writer = Vec::new();
+/// let _ = tokio::io::copy_buf(&mut reader, &mut writer).await.unwrap();
+/// assert_eq!(b"content", writer.as_slice());
+/// assert_eq!(Some(Some(BomType::UTF8)), reader.bom_found());
+/// # });
+/// ```
+#[derive(Debug, Clone)]
+pub struct AsyncSkipEncodingBom<'a, R> {
+    state: BomPollState<'a>,
+    reader: R,
+}
+
+impl<'a, R> AsyncSkipEncodingBom<'a, R> {
+    /// Initialize an encoding BOM skip struct given any asynchronous reader.
+    ///
+    /// # Arguments
+    ///
+    /// * `bom_types` - a slice with the types of BOM to check for. To skip any of the supported BOMs, pass [`BomType::all`].
+    /// * `reader` - the underlying input stream reader.
+    pub fn new(bom_types: &'a [BomType], reader: R) -> Self {
+        Self { state: BomPollState::new(bom_types), reader }
+    }
+    /// Return the BOM found, or [`None`] for the outer option if the presence of a BOM was not determined yet: see
+    /// [`SkipEncodingBom::bom_found`](crate::SkipEncodingBom::bom_found).
+    pub fn bom_found(&self) -> Option<Option<BomType>> {
+        self.state.bom_found()
+    }
+    /// Unwraps this `AsyncSkipEncodingBom<R>`, returning the underlying reader. Start bytes buffered are lost.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+    /// Unwraps this `AsyncSkipEncodingBom<R>`, returning the start bytes it has buffered and not returned yet, followed
+    /// by the underlying reader: see [`BomPollState::take_leftover`].
+    ///
+    /// Writing the leftover bytes then copying the reader yields the same bytes as this struct would have returned,
+    /// without its overhead.
+    /// # Examples
+    /// ```
+    /// use skip_bom::{AsyncSkipEncodingBom, BomType};
+    /// use tokio::io::AsyncReadExt;
+    ///
+    /// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+    /// let mut reader = AsyncSkipEncodingBom::new(BomType::all(), &b"\xFF\xFEt\x00e\x00x\x00t\x00"[..]);
+    /// assert_eq!(Some(BomType::UTF16LE), reader.read_bom().await.unwrap());
+    /// let (mut content, mut reader) = reader.into_inner_async();
+    /// let _ = reader.read_to_end(&mut content).await.unwrap();
+    /// assert_eq!(b"t\x00e\x00x\x00t\x00", content.as_slice());
+    /// # });
+    /// ```
+    pub fn into_inner_async(mut self) -> (Vec<u8>, R) {
+        (self.state.take_leftover(), self.reader)
+    }
+    /// Get a shared reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.reader
+    }
+    /// Get a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.reader
+    }
+}
+
+impl<'a, R: AsyncRead + Unpin> AsyncSkipEncodingBom<'a, R> {
+    /// Poll the underlying reader until the BOM presence is determined and return the BOM found, skipping it.
+    pub fn poll_read_bom(&mut self, cx: &mut Context<'_>) -> Poll<Result<Option<BomType>>> {
+        poll_resolve(cx, Pin::new(&mut self.reader), &mut self.state).map_ok(|()| self.state.bom_found().flatten())
+    }
+    /// Determine the BOM presence if needed and return the BOM found, skipping it.
+    pub async fn read_bom(&mut self) -> Result<Option<BomType>> {
+        ReadBom(self).await
+    }
+}
+
+/// Future of [`AsyncSkipEncodingBom::read_bom`].
+struct ReadBom<'r, 'a, R>(&'r mut AsyncSkipEncodingBom<'a, R>);
+
+impl<R: AsyncRead + Unpin> Future for ReadBom<'_, '_, R> {
+    type Output = Result<Option<BomType>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.0.poll_read_bom(cx)
+    }
+}
+
+impl<R: AsyncRead + Unpin> AsyncRead for AsyncSkipEncodingBom<'_, R> {
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<Result<()>> {
+        let this = self.get_mut();
+        poll_skip_bom(cx, Pin::new(&mut this.reader), &mut this.state, buf)
+    }
+}
+
+impl<R: AsyncBufRead + Unpin> AsyncBufRead for AsyncSkipEncodingBom<'_, R> {
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<&[u8]>> {
+        let this = self.get_mut();
+        match poll_resolve(cx, Pin::new(&mut this.reader), &mut this.state) {
+            Poll::Ready(Ok(())) => (),
+            Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+            Poll::Pending => return Poll::Pending,
+        }
+        match &this.state.state {
+            BomState::PostInitBuffer { bytes_after_bom, .. } => {
+                let position = (bytes_after_bom.position() as usize).min(bytes_after_bom.get_ref().byte_count());
+                Poll::Ready(Ok(&bytes_after_bom.get_ref().bytes()[position..]))
+            },
+            _ => Pin::new(&mut this.reader).poll_fill_buf(cx),
+        }
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        let this = self.get_mut();
+        match &mut this.state.state {
+            BomState::PostInitBuffer { bytes_after_bom, bom_type } => {
+                let byte_count = bytes_after_bom.get_ref().byte_count() as u64;
+                let position = (bytes_after_bom.position() + amt as u64).min(byte_count);
+                bytes_after_bom.set_position(position);
+                if position == byte_count {
+                    this.state.state = BomState::Final(bom_type.take());
+                }
+            },
+            _ => Pin::new(&mut this.reader).consume(amt),
+        }
+    }
+}
@@ -0,0 +1,199 @@
+use super::{detect_sample, read_sample, BomType, DecodeReader, DetectOptions, EncodingReport, Result};
+
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+/// Line ending style of a text stream.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LineEnding {
+    /// No line ending was found.
+    None,
+    /// Unix line endings, `\n`.
+    Lf,
+    /// Windows line endings, `\r\n`.
+    CrLf,
+    /// Classic Mac OS line endings, `\r`.
+    Cr,
+    /// Several line ending styles are used.
+    Mixed,
+}
+
+impl LineEnding {
+    /// Return the line ending style of `text`.
+    pub fn of(text: &str) -> Self {
+        LineEndingCounts::of(text).style()
+    }
+}
+
+/// Number of line endings of each style in a text.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct LineEndingCounts {
+    /// Number of `\n` line endings.
+    pub lf: usize,
+    /// Number of `\r\n` line endings.
+    pub crlf: usize,
+    /// Number of `\r` line endings.
+    pub cr: usize,
+}
+
+impl LineEndingCounts {
+    /// Count the line endings of `text`.
+    pub fn of(text: &str) -> Self {
+        let bytes = text.as_bytes();
+        let mut counts = Self::default();
+        for (index, byte) in bytes.iter().enumerate() {
+            match (byte, bytes.get(index + 1)) {
+                (b'\r', Some(b'\n')) => counts.crlf += 1,
+                (b'\r', _) => counts.cr += 1,
+                (b'\n', _) if index > 0 && bytes[index - 1] == b'\r' => (),
+                (b'\n', _) => counts.lf += 1,
+                _ => (),
+            }
+        }
+        counts
+    }
+
+    /// Returns the total number of line endings.
+    pub fn total(&self) -> usize {
+        self.lf + self.crlf + self.cr
+    }
+
+    /// Return the line ending style: [`LineEnding::Mixed`] as soon as several styles are used.
+    pub fn style(&self) -> LineEnding {
+        match (self.lf > 0, self.crlf > 0, self.cr > 0) {
+            (false, false, false) => LineEnding::None,
+            (true, false, false) => LineEnding::Lf,
+            (false, true, false) => LineEnding::CrLf,
+            (false, false, true) => LineEnding::Cr,
+            _ => LineEnding::Mixed,
+        }
+    }
+
+    /// Return the most frequent line ending style, to configure a parser for a text with a few stray line endings.
+    /// [`LineEnding::Mixed`] is returned if several styles are the most frequent.
+    /// # Examples
+    /// ```
+    /// use skip_bom::{LineEnding, LineEndingCounts};
+    ///
+    /// let counts = LineEndingCounts::of("id;name\r\n1;a\r\n2;b\n");
+    /// assert_eq!(LineEnding::Mixed, counts.style());
+    /// assert_eq!(LineEnding::CrLf, counts.dominant());
+    /// ```
+    pub fn dominant(&self) -> LineEnding {
+        let max = self.lf.max(self.crlf).max(self.cr);
+        let styles = [(self.lf, LineEnding::Lf), (self.crlf, LineEnding::CrLf), (self.cr, LineEnding::Cr)];
+        let mut most_frequent = styles.iter().filter(|(count, _)| max > 0 && *count == max).map(|(_, style)| *style);
+        match (most_frequent.next(), most_frequent.next()) {
+            (Some(style), None) => style,
+            (Some(_), Some(_)) => LineEnding::Mixed,
+            (None, _) => LineEnding::None,
+        }
+    }
+}
+
+/// Result of [`preflight`].
+#[must_use]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PreflightReport {
+    /// Encoding detection report on the start of the stream.
+    pub encoding: EncodingReport,
+    /// Line ending style of the start of the stream.
+    pub line_ending: LineEnding,
+    /// Number of line endings of each style in the start of the stream.
+    pub line_ending_counts: LineEndingCounts,
+    /// Column delimiter guessed from the first rows of the stream: see [`guess_delimiter`](crate::guess_delimiter).
+    #[cfg(feature = "delimiter")]
+    pub delimiter: Option<char>,
+    /// First line of the stream, decoded to UTF-8 without the BOM or the line ending, or [`None`] if the start of the
+    /// stream could not be decoded or if the first line does not end within the sample.
+    pub header: Option<String>,
+}
+
+impl PreflightReport {
+    /// Return the BOM found at the start of the stream.
+    pub fn bom(&self) -> Option<BomType> {
+        self.encoding.bom
+    }
+    /// Return the most frequent line ending style of the start of the stream: see [`LineEndingCounts::dominant`].
+    pub fn dominant_line_ending(&self) -> LineEnding {
+        self.line_ending_counts.dominant()
+    }
+    /// Return the most likely encoding of the stream.
+    pub fn guessed_encoding(&self) -> Option<BomType> {
+        self.encoding.best().map(|candidate| candidate.encoding)
+    }
+}
+
+/// Characterize the start of a file before loading it, for instance into a database: look for a BOM, guess its
+/// encoding and find its line ending style and header line.
+///
+/// See [`preflight_reader`].
+/// # Examples
+/// ```no_run
+/// use skip_bom::{preflight, LineEnding};
+///
+/// let report = preflight("data.csv").unwrap();
+/// if report.line_ending == LineEnding::CrLf {
+///     println!("columns: {}", report.header.unwrap_or_default());
+/// }
+/// ```
+pub fn preflight<P: AsRef<Path>>(path: P) -> Result<PreflightReport> {
+    preflight_reader(File::open(path)?, &DetectOptions::default())
+}
+
+/// Characterize the start of a stream: see [`detect()`](crate::detect()), which reads the same bytes from `reader`.
+///
+/// The sample is decoded according to the most likely encoding, even when the stream does not start with a BOM, to
+/// find the line ending style and the first line.
+/// # Examples
+/// ```
+/// use skip_bom::{preflight_reader, BomType, DetectOptions, LineEnding};
+///
+/// let report = preflight_reader(&b"\xEF\xBB\xBFid;name\r\n1;a\r\n"[..], &DetectOptions::default()).unwrap();
+/// assert_eq!(Some(BomType::UTF8), report.bom());
+/// assert_eq!(LineEnding::CrLf, report.line_ending);
+/// assert_eq!(Some("id;name"), report.header.as_deref());
+/// ```
+pub fn preflight_reader<R: Read>(reader: R, options: &DetectOptions) -> Result<PreflightReport> {
+    let (sample, whole_stream) = read_sample(reader, options)?;
+    let sample = &sample[..sample.len().min(options.sample_len)];
+    let encoding = detect_sample(sample, whole_stream, options, |_, _| ())?;
+    let text = decode_sample(sample, &encoding);
+    let line_ending_counts = text.as_deref().map_or_else(LineEndingCounts::default, |text| {
+        // the sample may end between a CR and a LF
+        LineEndingCounts::of(if whole_stream { text } else { text.strip_suffix('\r').unwrap_or(text) })
+    });
+    let line_ending = line_ending_counts.style();
+    #[cfg(feature = "delimiter")]
+    let delimiter = text.as_deref().and_then(crate::guess_delimiter);
+    let header = text.and_then(|text| match text.find(['\n', '\r']) {
+        Some(end) => Some(text[..end].to_string()),
+        None if whole_stream => Some(text),
+        None => None,
+    });
+    Ok(PreflightReport {
+        encoding,
+        line_ending,
+        line_ending_counts,
+        #[cfg(feature = "delimiter")]
+        delimiter,
+        header,
+    })
+}
+
+/// Decode a sample to UTF-8 according to its most likely encoding, replacing invalid sequences.
+fn decode_sample(sample: &[u8], encoding: &EncodingReport) -> Option<String> {
+    let mut bytes = Vec::with_capacity(sample.len() + 4);
+    if let (None, Some(candidate)) = (encoding.bom, encoding.best()) {
+        // the BOM of the encoding guessed is added for the sample to be decoded
+        if matches!(candidate.encoding, BomType::UTF16LE | BomType::UTF16BE | BomType::UTF32LE | BomType::UTF32BE) {
+            bytes.extend_from_slice(candidate.encoding.bom_bytes());
+        }
+    }
+    bytes.extend_from_slice(sample);
+    let mut decoded = Vec::new();
+    let _ = DecodeReader::new(BomType::all(), bytes.as_slice()).read_to_end(&mut decoded).ok()?;
+    Some(String::from_utf8_lossy(&decoded).into_owned())
+}
@@ -0,0 +1,25 @@
+//! Extension traits and common types, to bring the main API of the crate in scope with a single import.
+//! # Examples
+//! ```
+//! use skip_bom::prelude::*;
+//! use std::io::Read;
+//!
+//! let mut reader = SkipEncodingBom::new(BomType::all(), &b"\xEF\xBB\xBFcontent"[..]);
+//! assert_eq!(Some(BomType::UTF8), reader.read_bom().unwrap());
+//! let report = detect(&b"\xFF\xFEH\x00i\x00"[..], &DetectOptions::default()).unwrap();
+//! assert_eq!(PolicyAction::Skip, ReadPolicy::default().action(report.bom));
+//! ```
+
+pub use super::{ChildExt, PathExt};
+
+#[cfg(feature = "unstable")]
+pub use super::Sniffer;
+
+pub use super::{BomStatus, BomType, BomTypeSet, DecodeReader, SkipEncodingBom, SkipEncodingBomBuf};
+
+pub use super::{BomError, BomErrorCode};
+
+pub use super::{detect, DetectOptions, EncodingReport, ManagedReader, PolicyAction, PolicyCondition, ReadPolicy};
+
+#[cfg(feature = "tokio")]
+pub use super::{poll_skip_bom, AsyncSkipEncodingBom};
@@ -0,0 +1,34 @@
+use super::{BomType, SkipEncodingBom};
+
+use std::io::{Chain, Cursor, Read};
+
+impl<'a, B: AsRef<[u8]>, R: Read> SkipEncodingBom<'a, Chain<Cursor<B>, R>> {
+    /// Initialize an encoding BOM skip struct given bytes already read from the start of a stream, for instance for
+    /// content sniffing, and the reader of the rest of the stream.
+    ///
+    /// The BOM is looked for in the prelude bytes first, then in the bytes read from `reader`: nothing is lost or read
+    /// again. The prelude bytes are not copied and may be of any length.
+    ///
+    /// # Arguments
+    ///
+    /// * `bom_types` - a slice with the types of BOM to check for. To skip any of the supported BOMs, pass [`BomType::all`].
+    /// * `prelude` - the bytes already read from the start of the stream.
+    /// * `reader` - the underlying input stream reader, positioned after the prelude bytes.
+    /// # Examples
+    /// ```
+    /// use skip_bom::{BomType, SkipEncodingBom};
+    /// use std::io::{Cursor, Read};
+    ///
+    /// let mut stream = Cursor::new(b"\xEF\xBB\xBFThis stream has a BOM.");
+    /// let mut prelude = [0u8; 2];
+    /// stream.read_exact(&mut prelude).unwrap();
+    /// let mut reader = SkipEncodingBom::with_prelude(BomType::all(), prelude, stream);
+    /// let mut string = String::new();
+    /// let _ = reader.read_to_string(&mut string).unwrap();
+    /// assert_eq!("This stream has a BOM.", &string);
+    /// assert_eq!(Some(Some(BomType::UTF8)), reader.bom_found());
+    /// ```
+    pub fn with_prelude(bom_types: &'a [BomType], prelude: B, reader: R) -> Self {
+        Self::new(bom_types, Cursor::new(prelude).chain(reader))
+    }
+}
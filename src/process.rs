@@ -0,0 +1,48 @@
+use super::{BomType, DecodeReader, SkipEncodingBom};
+
+use std::process::{Child, ChildStderr, ChildStdout};
+
+/// Extension trait taking the output pipes of a child process while skipping their initial encoding BOM.
+///
+/// Windows tools such as PowerShell commonly write UTF-16LE with a BOM: the `*_text_no_bom` methods decode such output
+/// to UTF-8, see [`DecodeReader`]. The pipes must have been configured with [`Stdio::piped`](std::process::Stdio::piped),
+/// otherwise `None` is returned.
+/// # Examples
+/// ```no_run
+/// use skip_bom::ChildExt;
+/// use std::io::Read;
+/// use std::process::{Command, Stdio};
+///
+/// let mut child = Command::new("powershell").args(&["-Command", "Get-Date"]).stdout(Stdio::piped()).spawn().unwrap();
+/// let mut output = String::new();
+/// let _ = child.take_stdout_text_no_bom().unwrap().read_to_string(&mut output).unwrap();
+/// let _ = child.wait().unwrap();
+/// ```
+pub trait ChildExt {
+    /// Take the standard output pipe of the child, skipping its BOM if present.
+    fn take_stdout_no_bom(&mut self) -> Option<SkipEncodingBom<'static, ChildStdout>>;
+    /// Take the standard error pipe of the child, skipping its BOM if present.
+    fn take_stderr_no_bom(&mut self) -> Option<SkipEncodingBom<'static, ChildStderr>>;
+    /// Take the standard output pipe of the child, skipping its BOM if present and decoding it to UTF-8 according to the BOM.
+    fn take_stdout_text_no_bom(&mut self) -> Option<DecodeReader<'static, ChildStdout>>;
+    /// Take the standard error pipe of the child, skipping its BOM if present and decoding it to UTF-8 according to the BOM.
+    fn take_stderr_text_no_bom(&mut self) -> Option<DecodeReader<'static, ChildStderr>>;
+}
+
+impl ChildExt for Child {
+    fn take_stdout_no_bom(&mut self) -> Option<SkipEncodingBom<'static, ChildStdout>> {
+        self.stdout.take().map(|stdout| SkipEncodingBom::new(BomType::all(), stdout))
+    }
+
+    fn take_stderr_no_bom(&mut self) -> Option<SkipEncodingBom<'static, ChildStderr>> {
+        self.stderr.take().map(|stderr| SkipEncodingBom::new(BomType::all(), stderr))
+    }
+
+    fn take_stdout_text_no_bom(&mut self) -> Option<DecodeReader<'static, ChildStdout>> {
+        self.stdout.take().map(|stdout| DecodeReader::new(BomType::all(), stdout))
+    }
+
+    fn take_stderr_text_no_bom(&mut self) -> Option<DecodeReader<'static, ChildStderr>> {
+        self.stderr.take().map(|stderr| DecodeReader::new(BomType::all(), stderr))
+    }
+}
@@ -0,0 +1,50 @@
+use super::{BomBytesPushBuffer, BomState, BomType, Result, TryReadBomResult};
+
+use std::io::{ErrorKind, Read};
+
+/// Read the BOM at the start of a reader without wrapping it, and return the BOM found along with the bytes read after it.
+///
+/// The reader is read until the presence of a BOM is determined or until it returns no more bytes. At most
+/// [`BomType::max_probe_len`] bytes are read: the bytes returned must be handled by the caller before the rest of
+/// the reader. If the reader ends before the BOM presence is determined, a complete BOM that could have been the start
+/// of a longer one is returned, such as UTF-16LE for `FF FE`, and otherwise [`None`] with all the bytes read.
+///
+/// Reads failing with [`ErrorKind::Interrupted`] are retried. If another error occurs once some bytes were read, these
+/// bytes are returned as if the reader had ended, and the error is left to the next read of the reader: it is only
+/// returned when no byte was read.
+/// # Examples
+/// ```
+/// use skip_bom::{read_bom_from, BomType};
+/// use std::io::Read;
+///
+/// let mut reader = &b"\xEF\xBB\xBFThis stream has a BOM."[..];
+/// let (bom_type, leftover) = read_bom_from(&mut reader, BomType::all()).unwrap();
+/// assert_eq!(Some(BomType::UTF8), bom_type);
+/// let mut string = String::from_utf8(leftover.bytes().to_vec()).unwrap();
+/// let _ = reader.read_to_string(&mut string).unwrap();
+/// assert_eq!("This stream has a BOM.", &string);
+/// ```
+#[must_use = "the bytes read after the BOM are returned and must be handled by the caller"]
+pub fn read_bom_from<R: Read + ?Sized>(reader: &mut R, bom_types: &[BomType]) -> Result<(Option<BomType>, BomBytesPushBuffer)> {
+    let mut start_bytes = BomBytesPushBuffer::default();
+    let probe_len = BomType::max_probe_len(bom_types);
+    loop {
+        match BomState::try_read_bom(&start_bytes, reader, bom_types, probe_len) {
+            Ok(TryReadBomResult::Complete { bom_type, bytes_after_bom }) => break Ok((bom_type, bytes_after_bom)),
+            // no new bytes were read: the reader ended
+            Ok(TryReadBomResult::Incomplete(new_start_bytes)) if new_start_bytes.byte_count() == start_bytes.byte_count() => break bom_at_end(new_start_bytes, bom_types),
+            Ok(TryReadBomResult::Incomplete(new_start_bytes)) => start_bytes = new_start_bytes,
+            Err(e) if e.kind() == ErrorKind::Interrupted => (),
+            // the bytes held would be lost with the error
+            Err(_) if start_bytes.byte_count() != 0 => break bom_at_end(start_bytes, bom_types),
+            Err(e) => break Err(e),
+        }
+    }
+}
+
+fn bom_at_end(start_bytes: BomBytesPushBuffer, bom_types: &[BomType]) -> Result<(Option<BomType>, BomBytesPushBuffer)> {
+    match BomType::find_bytes_bom_at_end(start_bytes.bytes(), bom_types) {
+        Some(bom_type) => Ok((Some(bom_type), BomBytesPushBuffer::from_slice(&start_bytes.bytes()[bom_type.bom_length()..])?)),
+        None => Ok((None, start_bytes)),
+    }
+}
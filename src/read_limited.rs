@@ -0,0 +1,38 @@
+use super::{BomError, BomErrorCode, BomType, DecodeReader, Result, SkipEncodingBom};
+
+use std::io::{Error, ErrorKind, Read};
+
+/// Read the whole stream to a string like [`Read::read_to_string`], skipping its BOM and decoding it to UTF-8 according
+/// to the BOM, unless the text exceeds `max_bytes` bytes of UTF-8.
+///
+/// At most `max_bytes + 1` bytes of text are held in memory, so a large upload or a decompression bomb cannot exhaust
+/// it. A text over the limit fails with an [`ErrorKind::InvalidData`] error of code [`BomErrorCode::LimitExceeded`],
+/// and content that is not valid UTF-8 fails with an [`ErrorKind::InvalidData`] error as well: see [`DecodeReader`].
+/// # Examples
+/// ```
+/// use skip_bom::{read_text_limited, BomErrorCode};
+///
+/// assert_eq!("Hi", read_text_limited(&b"\xFF\xFEH\x00i\x00"[..], 2).unwrap());
+/// let error = read_text_limited(&b"\xEF\xBB\xBFtoo long"[..], 2).unwrap_err();
+/// assert_eq!(Some(BomErrorCode::LimitExceeded), BomErrorCode::of(&error));
+/// ```
+pub fn read_text_limited<R: Read>(reader: R, max_bytes: usize) -> Result<String> {
+    let bytes = read_limited(&mut DecodeReader::new(BomType::all(), reader), max_bytes)?;
+    String::from_utf8(bytes).map_err(|e| Error::new(ErrorKind::InvalidData, e))
+}
+
+/// Read the whole stream to a vector of bytes, skipping its BOM without decoding the content, unless the content exceeds
+/// `max_bytes` bytes: see [`read_text_limited`].
+pub fn read_bytes_limited<R: Read>(reader: R, max_bytes: usize) -> Result<Vec<u8>> {
+    read_limited(&mut SkipEncodingBom::new(BomType::all(), reader), max_bytes)
+}
+
+fn read_limited<R: Read>(reader: &mut R, max_bytes: usize) -> Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    // read one byte more than the limit to tell a stream of exactly `max_bytes` bytes apart
+    let _ = reader.take((max_bytes as u64).saturating_add(1)).read_to_end(&mut bytes)?;
+    if bytes.len() > max_bytes {
+        return Err(BomError::new_io(ErrorKind::InvalidData, BomErrorCode::LimitExceeded, None, format!("the content exceeds the limit of {} bytes", max_bytes)));
+    }
+    Ok(bytes)
+}
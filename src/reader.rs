@@ -0,0 +1,23 @@
+//! Readers skipping the BOM of a stream, and the policies and helpers built on them.
+//! # Examples
+//! ```
+//! use skip_bom::reader::{ManagedReader, ReadPolicy, SkipEncodingBom};
+//! use skip_bom::BomType;
+//! use std::io::Read;
+//!
+//! let mut reader = SkipEncodingBom::new(BomType::all(), &b"\xEF\xBB\xBFcontent"[..]);
+//! assert_eq!(Some(BomType::UTF8), reader.read_bom().unwrap());
+//! let mut text = String::new();
+//! let _ = ManagedReader::new(&b"\xEF\xBB\xBFcontent"[..], &ReadPolicy::default()).unwrap().read_to_string(&mut text).unwrap();
+//! assert_eq!("content", text);
+//! ```
+
+pub use super::{BomHook, InnerGuard, ProgressiveRead, SkipEncodingBom, SkipEncodingBomBuf, TeeReader};
+
+pub use super::{read_bom_from, read_bytes_limited, FirstLineBom, NoBomSlice, ResolvedBom, ShebangBom};
+
+pub use super::{strip_bom_from_first_line, StripBomFromFirstLine, Utf8Chunk, Utf8Chunks};
+
+pub use super::{ManagedReader, MultipartSanitizer, PolicyAction, PolicyCondition, PolicyRule, ReadPolicy};
+
+pub use super::{ControlCharPolicy, SanitizeOptions, TextSanitizer};
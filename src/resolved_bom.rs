@@ -0,0 +1,89 @@
+use super::{BomBytesPushBuffer, BomState, BomType, Result, SkipEncodingBom};
+
+use std::io::{Cursor, Read};
+
+/// Guard over a [`SkipEncodingBom`] whose BOM presence is determined, returned by [`SkipEncodingBom::resolve`].
+///
+/// Its accessors cannot fail and its [`Read`] implementation reads the bytes buffered after the BOM then the underlying
+/// reader, without going through the BOM detection state. When the guard is dropped, the bytes it did not read are
+/// returned to the `SkipEncodingBom`. With the `zeroize` feature, the guard's copy of the buffered bytes is then zeroed.
+#[derive(Debug)]
+pub struct ResolvedBom<'r, R: Read + ?Sized> {
+    bom: Option<BomType>,
+    buffered: Cursor<BomBytesPushBuffer>,
+    reader: &'r mut R,
+    state: &'r mut BomState,
+}
+
+impl<'r, R: Read + ?Sized> ResolvedBom<'r, R> {
+    /// Return the BOM found, or [`None`] if the stream does not start with a BOM.
+    #[must_use]
+    pub fn bom(&self) -> Option<BomType> {
+        self.bom
+    }
+    /// Get a shared reference to the underlying reader.
+    pub fn reader(&self) -> &R {
+        self.reader
+    }
+    /// Get a mutable reference to the underlying reader.
+    ///
+    /// Reading from it directly skips the bytes still [buffered](ResolvedBom::buffered) after the BOM.
+    pub fn reader_mut(&mut self) -> &mut R {
+        self.reader
+    }
+    /// Return the bytes read after the BOM while determining its presence that were not read from the guard yet.
+    pub fn buffered(&self) -> &[u8] {
+        let bytes = self.buffered.get_ref().bytes();
+        &bytes[(self.buffered.position() as usize).min(bytes.len())..]
+    }
+}
+
+impl<'r, R: Read + ?Sized> Read for ResolvedBom<'r, R> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        match self.buffered.read(buf)? {
+            0 => self.reader.read(buf),
+            bytes_read => Ok(bytes_read),
+        }
+    }
+}
+
+impl<'r, R: Read + ?Sized> Drop for ResolvedBom<'r, R> {
+    fn drop(&mut self) {
+        *self.state = if self.buffered().is_empty() {
+            BomState::Final(self.bom)
+        } else {
+            BomState::PostInitBuffer { bytes_after_bom: self.buffered.clone(), bom_type: self.bom }
+        };
+        #[cfg(feature = "zeroize")]
+        zeroize::Zeroize::zeroize(self.buffered.get_mut());
+    }
+}
+
+impl<'a, R: Read + ?Sized> SkipEncodingBom<'a, R> {
+    /// Determine the BOM presence and return a [`ResolvedBom`] guard to read the rest of the stream, or [`None`] if the
+    /// reader ended before the BOM presence could be determined.
+    /// # Examples
+    /// ```
+    /// use skip_bom::{BomType, SkipEncodingBom};
+    /// use std::io::{Cursor, Read};
+    ///
+    /// let mut reader = SkipEncodingBom::new(BomType::all(), Cursor::new(b"\xEF\xBB\xBFThis stream has a BOM."));
+    /// let mut resolved = reader.resolve().unwrap().unwrap();
+    /// assert_eq!(Some(BomType::UTF8), resolved.bom());
+    /// let mut string = String::new();
+    /// let _ = resolved.read_to_string(&mut string).unwrap();
+    /// assert_eq!("This stream has a BOM.", &string);
+    /// ```
+    #[must_use = "the guard is returned to read the rest of the stream: use `read_bom` to only determine the BOM presence"]
+    pub fn resolve(&mut self) -> Result<Option<ResolvedBom<'_, R>>> {
+        let _ = self.read_bom()?;
+        let (reader, state) = self.reader_and_state_mut();
+        let (bom, buffered) = match state {
+            BomState::Initial { .. } => return Ok(None),
+            BomState::PostInitBuffer { bytes_after_bom, bom_type } => (*bom_type, bytes_after_bom.clone()),
+            BomState::Final(bom_type) => (*bom_type, Cursor::new(BomBytesPushBuffer::default())),
+        };
+        Ok(Some(ResolvedBom { bom, buffered, reader, state }))
+    }
+}
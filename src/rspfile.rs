@@ -0,0 +1,74 @@
+//! Parsing of response files, such as the `@file.rsp` arguments of the MSVC tools.
+//!
+//! Response files are often written in UTF-16LE with a BOM: they are decoded by [`DecodeReader`], then split into
+//! arguments following the rules of the Microsoft C runtime:
+//! * arguments are separated by whitespace, including line endings;
+//! * double quotes group whitespace into an argument and are removed, two double quotes within quotes giving one;
+//! * backslashes are literal unless followed by a double quote: `2n` backslashes then give `n` backslashes and the
+//!   quote delimits, `2n + 1` backslashes give `n` backslashes and a literal double quote.
+//! # Examples
+//! ```
+//! use skip_bom::rspfile::read_args;
+//!
+//! let mut rsp = vec![0xFF, 0xFE];
+//! rsp.extend("/Fo\"out dir\\\\\" main.c\r\n".encode_utf16().flat_map(u16::to_le_bytes));
+//! assert_eq!(vec!["/Foout dir\\", "main.c"], read_args(&rsp[..]).unwrap());
+//! ```
+
+use super::{BomType, DecodeReader, Result};
+
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+/// Split the content of a response file into arguments.
+pub fn split_args(text: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut chars = text.chars().peekable();
+    loop {
+        while chars.peek().is_some_and(|c| c.is_whitespace() || *c == '\u{FEFF}') {
+            chars.next();
+        }
+        if chars.peek().is_none() {
+            return args;
+        }
+        let mut arg = String::new();
+        let mut in_quotes = false;
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => {
+                    let mut backslashes = 1;
+                    while chars.next_if_eq(&'\\').is_some() {
+                        backslashes += 1;
+                    }
+                    if chars.peek() == Some(&'"') {
+                        arg.extend(std::iter::repeat_n('\\', backslashes / 2));
+                        if backslashes % 2 == 1 {
+                            arg.push('"');
+                            chars.next();
+                        }
+                    } else {
+                        arg.extend(std::iter::repeat_n('\\', backslashes));
+                    }
+                }
+                '"' if in_quotes && chars.next_if_eq(&'"').is_some() => arg.push('"'),
+                '"' => in_quotes = !in_quotes,
+                c if c.is_whitespace() && !in_quotes => break,
+                c => arg.push(c),
+            }
+        }
+        args.push(arg);
+    }
+}
+
+/// Read the arguments of a response file from a stream that may start with a BOM.
+pub fn read_args<R: Read>(reader: R) -> Result<Vec<String>> {
+    let mut text = String::new();
+    DecodeReader::new(BomType::all(), reader).read_to_string(&mut text)?;
+    Ok(split_args(&text))
+}
+
+/// Read the arguments of a response file.
+pub fn read_file<P: AsRef<Path>>(path: P) -> Result<Vec<String>> {
+    read_args(File::open(path)?)
+}
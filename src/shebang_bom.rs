@@ -0,0 +1,118 @@
+use super::{BomType, Result, SkipEncodingBom};
+
+use std::io::{Chain, Cursor, Read};
+
+/// Read from I/O and skip an encoding BOM, passing a shebang line through untouched and looking for the BOM right
+/// after it.
+///
+/// Some editors insert the BOM after the `#!` line of scripts, where interpreters choke on it. When the stream starts
+/// with `#!`, everything up to and including the first LF is returned as is and the BOM is looked for at the start of
+/// the second line. Otherwise, the BOM is looked for at the start of the stream like with [`SkipEncodingBom`].
+/// # Examples
+/// ```
+/// use skip_bom::{BomType, ShebangBom};
+/// use std::io::Read;
+///
+/// let mut reader = ShebangBom::new(BomType::all(), &b"#!/bin/sh\n\xEF\xBB\xBFecho hello\n"[..]);
+/// let mut script = String::new();
+/// let _ = reader.read_to_string(&mut script).unwrap();
+/// assert_eq!("#!/bin/sh\necho hello\n", script);
+/// assert_eq!((Some(true), Some(Some(BomType::UTF8))), (reader.shebang(), reader.bom_found()));
+/// ```
+#[derive(Debug)]
+pub struct ShebangBom<'a, R: Read> {
+    /// `true` if the stream starts with `#!`, [`None`] until the first bytes are read.
+    shebang: Option<bool>,
+    /// `true` while the shebang line is being passed through.
+    in_shebang_line: bool,
+    /// Bytes of the shebang line not returned yet.
+    pending: Cursor<Vec<u8>>,
+    /// Reader skipping the BOM, reading the bytes after the shebang line first.
+    inner: SkipEncodingBom<'a, Chain<Cursor<Vec<u8>>, R>>,
+}
+
+impl<'a, R: Read> ShebangBom<'a, R> {
+    /// Initialize a shebang-aware BOM skipping reader given any stream reader.
+    ///
+    /// # Arguments
+    ///
+    /// * `bom_types` - a slice with the types of BOM to check for. To skip any of the supported BOMs, pass [`BomType::all`].
+    /// * `reader` - the underlying input stream reader.
+    pub fn new(bom_types: &'a [BomType], reader: R) -> Self {
+        Self { shebang: None, in_shebang_line: false, pending: Cursor::new(Vec::new()), inner: SkipEncodingBom::with_prelude(bom_types, Vec::new(), reader) }
+    }
+
+    /// Returns `true` if the stream starts with a shebang line, or [`None`] before the first read.
+    #[must_use]
+    pub fn shebang(&self) -> Option<bool> {
+        self.shebang
+    }
+
+    /// Return the BOM skipped like [`SkipEncodingBom::bom_found`]: [`None`] for the outer option until the shebang line
+    /// is passed through and the BOM presence is determined.
+    #[must_use]
+    pub fn bom_found(&self) -> Option<Option<BomType>> {
+        self.inner.bom_found()
+    }
+
+    /// Get a shared reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        self.inner.get_ref().get_ref().1
+    }
+
+    /// Unwraps this `ShebangBom<R>`, returning the underlying reader.
+    ///
+    /// Bytes read from the underlying reader but not returned yet are lost.
+    pub fn into_inner(self) -> R {
+        self.inner.into_inner().into_inner().1
+    }
+
+    /// Get a mutable reference to the underlying reader, bypassing the bytes held for the BOM detection.
+    fn raw_reader(&mut self) -> &mut R {
+        self.inner.get_mut().get_mut().1
+    }
+
+    /// Hold `bytes` read after the shebang line, or at the start of a stream without one, for the BOM detection.
+    fn hold_for_detection(&mut self, bytes: Vec<u8>) {
+        *self.inner.get_mut().get_mut().0 = Cursor::new(bytes);
+    }
+}
+
+impl<'a, R: Read> Read for ShebangBom<'a, R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        if (self.pending.position() as usize) < self.pending.get_ref().len() {
+            return self.pending.read(buf);
+        }
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        if self.shebang.is_none() {
+            let mut start = Vec::with_capacity(2);
+            let _ = self.raw_reader().take(2).read_to_end(&mut start)?;
+            let shebang = start == b"#!";
+            self.shebang = Some(shebang);
+            if shebang {
+                self.in_shebang_line = true;
+                self.pending = Cursor::new(start);
+                return self.pending.read(buf);
+            }
+            self.hold_for_detection(start);
+        }
+        if !self.in_shebang_line {
+            return self.inner.read(buf);
+        }
+        let bytes_read = self.raw_reader().read(buf)?;
+        match buf[..bytes_read].iter().position(|byte| *byte == b'\n') {
+            Some(line_end) => {
+                self.in_shebang_line = false;
+                self.hold_for_detection(buf[line_end + 1..bytes_read].to_vec());
+                Ok(line_end + 1)
+            },
+            None => {
+                // the stream ended within the shebang line
+                self.in_shebang_line = bytes_read != 0;
+                Ok(bytes_read)
+            },
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use super::{BomType, ALL_SORTED_BY_LENGTH, MAX_BOM_LENGTH};
+
+/// Pack up to [`MAX_BOM_LENGTH`] bytes in a big-endian `u32`, returning the packed bytes and the mask of the bytes present.
+pub(crate) const fn pack_bytes(bytes: &[u8]) -> (u32, u32) {
+    let mut packed = 0;
+    let mut mask = 0;
+    let mut i = 0;
+    while i < bytes.len() && i < MAX_BOM_LENGTH as usize {
+        let shift = 8 * (MAX_BOM_LENGTH as usize - 1 - i);
+        packed |= (bytes[i] as u32) << shift;
+        mask |= 0xFF << shift;
+        i += 1;
+    }
+    (packed, mask)
+}
+
+/// BOM of a type packed for comparison with the start of a stream in a single operation.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct PackedSignature {
+    pub bom_type: BomType,
+    pub bytes: u32,
+    pub mask: u32,
+}
+
+impl PackedSignature {
+    const fn new(bom_type: BomType) -> Self {
+        let (bytes, mask) = pack_bytes(bom_type.bom_bytes());
+        Self { bom_type, bytes, mask }
+    }
+}
+
+/// Generate the signatures of all the BOM types, longest first.
+const fn sorted_signatures() -> [PackedSignature; 11] {
+    let mut signatures = [PackedSignature::new(ALL_SORTED_BY_LENGTH[0]); 11];
+    let mut i = 1;
+    while i < signatures.len() {
+        signatures[i] = PackedSignature::new(ALL_SORTED_BY_LENGTH[i]);
+        i += 1;
+    }
+    signatures
+}
+
+/// Signatures of all the BOM types in the order they are tested: a BOM that is the start of another, such as UTF-16LE
+/// for UTF-32LE, comes after it as the longest BOMs come first.
+pub(crate) const SIGNATURES: [PackedSignature; 11] = sorted_signatures();
+
+/// Mask with a bit for each signature in [`SIGNATURES`], the bit index being the signature index.
+pub(crate) type SignaturesMask = u16;
+
+/// Mask of all the signatures.
+pub(crate) const ALL_SIGNATURES: SignaturesMask = (1 << SIGNATURES.len()) - 1;
+
+/// Generate the table of the signatures starting with each possible first byte.
+const fn first_byte_signatures() -> [SignaturesMask; 256] {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < SIGNATURES.len() {
+        let first_byte = (SIGNATURES[i].bytes >> (8 * (MAX_BOM_LENGTH as usize - 1))) as usize;
+        table[first_byte] |= 1 << i;
+        i += 1;
+    }
+    table
+}
+
+/// Signatures starting with each first byte: at most two signatures remain to compare once the first byte is known.
+pub(crate) const FIRST_BYTE_SIGNATURES: [SignaturesMask; 256] = first_byte_signatures();
+
+/// Set of BOM types as a bit mask, to test the membership of a BOM type in constant time.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct BomTypeMask(u16);
+
+impl BomTypeMask {
+    pub fn new(bom_types: &[BomType]) -> Self {
+        BomTypeMask(bom_types.iter().fold(0, |mask, bom_type| mask | Self::bit(*bom_type)))
+    }
+
+    pub fn contains(&self, bom_type: BomType) -> bool {
+        self.0 & Self::bit(bom_type) != 0
+    }
+
+    const fn bit(bom_type: BomType) -> u16 {
+        1 << bom_type as u16
+    }
+}
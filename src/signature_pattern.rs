@@ -0,0 +1,79 @@
+use super::{BomBytesTest, BomType};
+
+/// Signature of a BOM type as a small pattern, for encodings whose BOM is not a fixed byte string.
+///
+/// The UTF-7 BOM is `2B 2F 76` followed by one of `38`, `39`, `2B` or `2F`, and the form `2B 2F 76 38` may be followed by
+/// `2D`. The signatures of the other BOM types are their BOM bytes.
+///
+/// BOMs are detected and skipped with their prefix alone, see [`BomType::bom_bytes`]: the byte after the UTF-7 prefix
+/// also encodes the first bits of the next character, except for the form ending with `38`. Test a detected UTF-7 BOM
+/// against the pattern to tell a well-formed signature from text starting with `+/v`.
+/// # Examples
+/// ```
+/// use skip_bom::{BomBytesTest, BomType};
+///
+/// let pattern = BomType::UTF7.signature_pattern();
+/// assert_eq!(Some(5), pattern.signature_len(b"+/v8-text"));
+/// assert_eq!(Some(4), pattern.signature_len(b"+/v9text"));
+/// assert_eq!(None, pattern.signature_len(b"+/var"));
+/// assert_eq!(BomBytesTest::Incomplete, pattern.test(b"+/v"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SignaturePattern {
+    /// Bytes every signature of the type starts with.
+    pub prefix: &'static [u8],
+    /// Alternatives for the byte following the prefix, or an empty slice if the signature is the prefix alone.
+    pub next_byte: &'static [u8],
+    /// Byte that may follow the signature when the byte after the prefix is the first alternative of `next_byte`.
+    pub optional_trailing: Option<u8>,
+}
+
+impl SignaturePattern {
+    /// Returns the number of bytes needed to confirm the signature.
+    pub const fn min_len(&self) -> usize {
+        self.prefix.len() + if self.next_byte.is_empty() { 0 } else { 1 }
+    }
+
+    /// Returns the length of the longest signature of the pattern.
+    pub const fn max_len(&self) -> usize {
+        self.min_len() + if self.optional_trailing.is_some() { 1 } else { 0 }
+    }
+
+    /// Test whether `tested_bytes` start with a signature of the pattern, like [`BomType::test_bytes`].
+    pub fn test(&self, tested_bytes: &[u8]) -> BomBytesTest {
+        let prefix_len = self.prefix.len().min(tested_bytes.len());
+        if tested_bytes[..prefix_len] != self.prefix[..prefix_len] {
+            BomBytesTest::NotBom
+        } else if tested_bytes.len() < self.min_len() {
+            BomBytesTest::Incomplete
+        } else if self.next_byte.is_empty() || self.next_byte.contains(&tested_bytes[self.prefix.len()]) {
+            BomBytesTest::StartsWithBom
+        } else {
+            BomBytesTest::NotBom
+        }
+    }
+
+    /// Returns the length of the signature `bytes` start with, including the optional trailing byte, or [`None`] if they
+    /// do not start with a complete signature of the pattern.
+    pub fn signature_len(&self, bytes: &[u8]) -> Option<usize> {
+        if self.test(bytes) != BomBytesTest::StartsWithBom {
+            return None;
+        }
+        let min_len = self.min_len();
+        let trailing = match (self.optional_trailing, self.next_byte.first()) {
+            (Some(trailing), Some(first)) => bytes[min_len - 1] == *first && bytes.get(min_len) == Some(&trailing),
+            _ => false,
+        };
+        Some(min_len + usize::from(trailing))
+    }
+}
+
+impl BomType {
+    /// Returns the signature pattern of the BOM type: see [`SignaturePattern`].
+    pub const fn signature_pattern(&self) -> SignaturePattern {
+        match self {
+            BomType::UTF7 => SignaturePattern { prefix: self.bom_bytes(), next_byte: &[0x38, 0x39, 0x2B, 0x2F], optional_trailing: Some(0x2D) },
+            _ => SignaturePattern { prefix: self.bom_bytes(), next_byte: &[], optional_trailing: None },
+        }
+    }
+}
@@ -1,15 +1,63 @@
 use super::*;
 
-use std::io::{Cursor, Read};
+use std::fmt::{self, Debug, Formatter};
+use std::io::{Chain, Cursor, Read, Seek, SeekFrom};
+use std::panic::RefUnwindSafe;
+use std::sync::Arc;
 
 /// Read from I/O and skip the initial encoding BOM if present.
-#[derive(Debug, Clone)]
-pub struct SkipEncodingBom<'a, R: Read> {
-    reader: R,
+///
+/// `SkipEncodingBom<R>` only holds plain data and a shared [`BomHook`] besides the reader: it is [`Send`], [`Sync`],
+/// [`Unpin`], [`UnwindSafe`](std::panic::UnwindSafe) and [`RefUnwindSafe`] whenever `R` is.
+///
+/// Cloning a `SkipEncodingBom<R>` clones the reader along with the detection state, including the start bytes buffered
+/// while the BOM presence is not determined yet. When cloning `R` also clones its position, as with [`Cursor`] or byte
+/// slices, each clone then completes the BOM detection and reads the rest of the stream independently.
+///
+/// Reading does not panic, whatever the input: while the BOM is being detected, an underlying reader reporting more
+/// bytes than the buffer it was given can hold makes the read fail with an [`ErrorKind::InvalidData`](std::io::ErrorKind::InvalidData) error.
+///
+/// Creating a `SkipEncodingBom<R>` and reading from it never allocate on the heap: the start bytes are buffered inline.
+/// Only the errors returned, besides those of the underlying reader, allocate their message, and setting a hook with
+/// [`SkipEncodingBom::with_bom_hook`] allocates it.
+///
+/// With the `zeroize` feature, the start bytes buffered while detecting the BOM are zeroed once they are returned or
+/// the struct is dropped. The copies made on the stack while reading are zeroed as far as possible.
+///
+/// The reader may be unsized: a boxed `SkipEncodingBom<R>` coerces to `Box<SkipEncodingBom<dyn Read>>`, and trait
+/// objects such as `&mut dyn Read` can be wrapped as they are, without boxing them again.
+/// # Examples
+/// ```
+/// use skip_bom::{BomType, SkipEncodingBom};
+/// use std::io::{Cursor, Read};
+///
+/// let mut readers: Vec<Box<SkipEncodingBom<dyn Read>>> = vec![
+///     Box::new(SkipEncodingBom::new(BomType::all(), Cursor::new(b"\xEF\xBB\xBFThis stream has a BOM."))),
+///     Box::new(SkipEncodingBom::new(BomType::all(), &b"This stream does not."[..])),
+/// ];
+/// for reader in readers.iter_mut() {
+///     let _ = reader.read_bom().unwrap();
+/// }
+/// assert_eq!(Some(Some(BomType::UTF8)), readers[0].bom_found());
+/// assert_eq!(Some(None), readers[1].bom_found());
+/// ```
+#[derive(Clone)]
+pub struct SkipEncodingBom<'a, R: Read + ?Sized> {
     state: BomState,
     bom_types: &'a [BomType],
+    bom_hook: Option<BomHook>,
+    probe_len: usize,
+    keep_bom: bool,
+    redact_debug: bool,
+    // last field, for the reader to be unsized
+    reader: R,
 }
 
+/// Function called when a BOM is found: returning an error makes the read that found the BOM fail with this error.
+///
+/// The hook is shared by the clones of a reader.
+pub type BomHook = Arc<dyn Fn(BomType) -> Result<()> + Send + Sync + RefUnwindSafe>;
+
 impl<'a, R: Read> SkipEncodingBom<'a, R> {
     /// Initialize an encoding BOM skip struct given any stream reader.
     /// 
@@ -22,25 +70,165 @@ impl<'a, R: Read> SkipEncodingBom<'a, R> {
             reader,
             state: BomState::default(),
             bom_types,
+            bom_hook: None,
+            probe_len: BomType::max_probe_len(bom_types),
+            keep_bom: false,
+            redact_debug: false,
+        }
+    }
+    /// Detect the BOM without skipping it: the BOM found is reported by [`SkipEncodingBom::bom_found`] and the hook is
+    /// called, but reads return the BOM bytes along with the content. To call before reading.
+    ///
+    /// This lets another layer that handles BOMs itself, such as `encoding_rs_io::DecodeReaderBytes`, own the BOM
+    /// while the BOM found is still known, instead of both layers stripping it.
+    /// # Examples
+    /// ```
+    /// use skip_bom::{BomType, SkipEncodingBom};
+    /// use std::io::Read;
+    ///
+    /// let mut reader = SkipEncodingBom::new(BomType::all(), &b"\xEF\xBB\xBFtext"[..]).with_bom_kept();
+    /// let mut buf = Vec::new();
+    /// let _ = reader.read_to_end(&mut buf).unwrap();
+    /// assert_eq!(b"\xEF\xBB\xBFtext", buf.as_slice());
+    /// assert_eq!(Some(Some(BomType::UTF8)), reader.bom_found());
+    /// ```
+    pub fn with_bom_kept(mut self) -> Self {
+        self.keep_bom = true;
+        self.state = self.state.with_bom_bytes();
+        self
+    }
+    /// Leave the start bytes buffered and the underlying reader out of the [`Debug`] output, as they may hold sensitive
+    /// content that would end up in logs: only the name of the detection state and the number of bytes buffered are
+    /// shown. Readers wrapping this struct, such as [`DecodeReader`], leave out their buffers as well.
+    /// # Examples
+    /// ```
+    /// use skip_bom::{BomType, SkipEncodingBom};
+    /// use std::io::{Cursor, Read};
+    ///
+    /// let mut reader = SkipEncodingBom::new(BomType::all(), Cursor::new(b"\xFF\xFEsecret")).with_redacted_debug();
+    /// let _ = reader.read(&mut [0; 1]).unwrap();
+    /// let debug = format!("{:?}", reader);
+    /// assert!(debug.contains("PostInitBuffer { buffered: 1, bom_type: Some(UTF16LE) }"), "{}", debug);
+    /// assert!(!debug.contains("115"), "{}", debug);
+    /// ```
+    pub fn with_redacted_debug(mut self) -> Self {
+        self.redact_debug = true;
+        self
+    }
+    /// Set a function to call when a BOM is found, for instance to warn about or reject BOM types that cannot be processed.
+    /// Closures capturing their environment can be passed, such as one counting the BOMs in an atomic counter.
+    ///
+    /// The hook is called once, by the read that determined the BOM presence. If it returns an error, this read fails
+    /// with the error; the BOM is still considered found and later reads return the content after the BOM.
+    /// # Examples
+    /// ```
+    /// use skip_bom::{BomType, SkipEncodingBom};
+    /// use std::io::{Cursor, Error, ErrorKind, Read};
+    ///
+    /// fn quarantine(bom_type: BomType) -> std::io::Result<()> {
+    ///     if bom_type.is_supported_by_std_string() {
+    ///         Ok(())
+    ///     } else {
+    ///         Err(Error::new(ErrorKind::InvalidData, format!("{:?} content cannot be read", bom_type)))
+    ///     }
+    /// }
+    ///
+    /// let mut reader = SkipEncodingBom::new(BomType::all(), Cursor::new(b"\xDD\x73\x66\x73EBCDIC")).with_bom_hook(quarantine);
+    /// assert_eq!(ErrorKind::InvalidData, reader.read_bom().unwrap_err().kind());
+    /// assert_eq!(Some(Some(BomType::UTFEBDIC)), reader.bom_found());
+    /// ```
+    pub fn with_bom_hook<F: Fn(BomType) -> Result<()> + Send + Sync + RefUnwindSafe + 'static>(mut self, bom_hook: F) -> Self {
+        self.bom_hook = Some(Arc::new(bom_hook));
+        self
+    }
+    /// Unwraps this `SkipEncodingBom<R>`, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Unwraps this `SkipEncodingBom<R>`, returning the bytes it has buffered chained with the underlying reader.
+    ///
+    /// The resulting reader yields the same bytes this struct would have returned. If the BOM presence is not
+    /// determined yet, the start bytes read so far are returned as-is since they cannot be confirmed to be a BOM.
+    /// # Examples
+    /// ```
+    /// use skip_bom::{BomType, SkipEncodingBom};
+    /// use std::io::{Cursor, Read};
+    ///
+    /// let mut reader = SkipEncodingBom::new(BomType::all(), Cursor::new(b"\xEF\xBB\xBFThis stream has a BOM."));
+    /// assert_eq!(Some(BomType::UTF8), reader.read_bom().unwrap());
+    /// let mut string = String::new();
+    /// let _ = reader.leftover_chain().read_to_string(&mut string).unwrap();
+    /// assert_eq!("This stream has a BOM.", &string);
+    /// ```
+    pub fn leftover_chain(self) -> Chain<Cursor<Vec<u8>>, R> {
+        let leftover = match &self.state {
+            BomState::Initial { start_bytes } => start_bytes.bytes(),
+            BomState::PostInitBuffer { bytes_after_bom, .. } => {
+                let position = (bytes_after_bom.position() as usize).min(bytes_after_bom.get_ref().byte_count());
+                &bytes_after_bom.get_ref().bytes()[position..]
+            },
+            BomState::Final(_) => &[],
+        };
+        Cursor::new(leftover.to_vec()).chain(self.reader)
+    }
+
+    /// Replace the underlying reader with the result of `f`, keeping the BOM detection state and the buffered start bytes.
+    ///
+    /// This can be used to wrap the underlying reader after construction, for instance to add progress reporting.
+    /// # Examples
+    /// ```
+    /// use skip_bom::{BomType, SkipEncodingBom};
+    /// use std::io::{BufReader, Cursor, Read};
+    ///
+    /// let mut reader = SkipEncodingBom::new(BomType::all(), Cursor::new(b"\xEF\xBB\xBFThis stream has a BOM."));
+    /// assert_eq!(Some(BomType::UTF8), reader.read_bom().unwrap());
+    /// let mut reader = reader.map_inner(BufReader::new);
+    /// let mut string = String::new();
+    /// let _ = reader.read_to_string(&mut string).unwrap();
+    /// assert_eq!("This stream has a BOM.", &string);
+    /// assert_eq!(Some(Some(BomType::UTF8)), reader.bom_found());
+    /// ```
+    pub fn map_inner<S: Read, F: FnOnce(R) -> S>(self, f: F) -> SkipEncodingBom<'a, S> {
+        SkipEncodingBom {
+            reader: f(self.reader),
+            state: self.state,
+            bom_types: self.bom_types,
+            bom_hook: self.bom_hook,
+            probe_len: self.probe_len,
+            keep_bom: self.keep_bom,
+            redact_debug: self.redact_debug,
         }
     }
+}
+
+impl<'a, R: Read + ?Sized> SkipEncodingBom<'a, R> {
     /// Read the BOM from a reader if it is present and return the BOM found as an [`Option`] with a [`BomType`] or [`None`] if it was not found.
     /// 
-    /// If the reader ends before a BOM if confirmed, [`None`] will be returned.  
+    /// If the reader ends before a BOM if confirmed, [`None`] will be returned. If it ends right after a BOM that could
+    /// also have been the start of a longer one, such as `FF FE` for UTF-16LE and UTF-32LE, the shorter BOM is found.
+    #[must_use = "the BOM found is returned: use `let _ =` to only skip it"]
     pub fn read_bom(&mut self) -> Result<Option<BomType>> {
         loop {
             match &self.state {
-                BomState::Initial { start_bytes } => match Self::state_after_initial(start_bytes, &mut self.reader, self.bom_types)? {
-                    NextStateResult::NewState(new_state) => self.state = new_state,
+                BomState::Initial { start_bytes } => match Self::state_after_initial(start_bytes, &mut self.reader, self.bom_types, self.probe_len)? {
+                    NextStateResult::NewState(new_state) => self.set_state(new_state)?,
                     NextStateResult::IncompleteRead(new_start_bytes) => {
                         self.state = BomState::Initial { start_bytes: new_start_bytes };
                         break Ok(None)
                     },
                 },
-                BomState::PostInitBuffer { bom_type, .. } | BomState::Final(bom_type) => break Ok(bom_type.clone()),
+                BomState::PostInitBuffer { bom_type, .. } | BomState::Final(bom_type) => break Ok(*bom_type),
             }
         }
     }
+    /// Return the maximum number of bytes read from the underlying reader to determine the presence of a BOM: see [`BomType::max_probe_len`].
+    ///
+    /// The BOM presence is determined as soon as possible: bytes past the longest enabled BOM are never read ahead.
+    #[must_use]
+    pub fn max_probe_len(&self) -> usize {
+        self.probe_len
+    }
     /// Return the BOM previously found as an inner [`Option`] with a [`BomType`] or [`None`] if it was not found, or [`None`] for the outer option if the presence of a BOM could not be determined yet.
     /// # Examples
     /// ```
@@ -81,12 +269,31 @@ impl<'a, R: Read> SkipEncodingBom<'a, R> {
     ///     assert_eq!(b"Th", buf.as_slice());
     /// }
     /// ```
+    #[must_use]
     pub fn bom_found(&self) -> Option<Option<BomType>> {
         self.state.bom_found()
     }
-    /// Unwraps this `SkipEncodingBom<R>`, returning the underlying reader.
-    pub fn into_inner(self) -> R {
-        self.reader
+    /// Return the presence of a BOM as determined so far: see [`BomStatus`].
+    pub fn status(&self) -> BomStatus {
+        self.bom_found().into()
+    }
+    /// Compare the BOM found with a charset declared out-of-band, for instance in an HTTP `Content-Type` header.
+    ///
+    /// [`CharsetReconciliation::Unknown`] is returned if no BOM was found or if its presence could not be determined yet.
+    /// # Examples
+    /// ```
+    /// use skip_bom::{BomType, CharsetReconciliation, SkipEncodingBom};
+    /// use std::io::{Cursor, Read};
+    ///
+    /// const BYTES: &[u8] = b"\xFF\xFEH\x00i\x00";
+    /// let mut reader = SkipEncodingBom::new(BomType::all(), Cursor::new(BYTES));
+    /// assert_eq!(CharsetReconciliation::Unknown, reader.reconcile_with_charset("text/plain; charset=utf-8"));
+    /// let _ = reader.read_bom().unwrap();
+    /// // The BOM wins over the declared charset.
+    /// assert_eq!(CharsetReconciliation::Conflict(BomType::UTF16LE), reader.reconcile_with_charset("text/plain; charset=utf-8"));
+    /// ```
+    pub fn reconcile_with_charset(&self, declared: &str) -> CharsetReconciliation {
+        CharsetReconciliation::new(self.bom_found().flatten(), declared)
     }
 
     /// Get a shared reference to the underlying reader.
@@ -95,36 +302,48 @@ impl<'a, R: Read> SkipEncodingBom<'a, R> {
     }
 
     /// Get a mutable reference to the underlying reader. 
+    ///
+    /// The detection state is kept whatever is done with the reader: bytes appended to it are read as the continuation
+    /// of the stream, but after moving it back to the start of the stream, [`SkipEncodingBom::restart_detection`] must be
+    /// called for the BOM to be looked for again. See [`SkipEncodingBom::inner_mut`] for access restarting the detection
+    /// when needed.
     pub fn get_mut(&mut self) -> &mut R {
         &mut self.reader
     }
 
-    fn state_after_initial(start_bytes: &BomBytesPushBuffer, reader: &mut R, bom_types: &[BomType]) -> Result<NextStateResult> {
-        use NextStateResult::*;
-        match BomState::try_read_bom(start_bytes, reader, bom_types)? {
-            // no new bytes were read
-            TryReadBomResult::Incomplete(new_start_bytes) if start_bytes.byte_count() == new_start_bytes.byte_count() => Ok(IncompleteRead(new_start_bytes)),
-            // new bytes were read
-            TryReadBomResult::Incomplete(new_start_bytes) => Ok(NewState(BomState::Initial { start_bytes: new_start_bytes })),
-            // the BOM presence and type was determined
-            TryReadBomResult::Complete { bom_type, bytes_after_bom } if bytes_after_bom.byte_count() == 0 => Ok(NewState(BomState::Final(bom_type))),
-            TryReadBomResult::Complete { bom_type, bytes_after_bom } => Ok(NewState(BomState::PostInitBuffer { bytes_after_bom: Cursor::new(bytes_after_bom), bom_type })),
-        }
+    /// Forget the BOM found and the start bytes buffered, so that the next read looks for a BOM again from the current
+    /// position of the underlying reader.
+    ///
+    /// This must be called after rewinding the underlying reader through [`SkipEncodingBom::get_mut`]: otherwise the
+    /// stale state is applied to the rewound stream, whose BOM is then returned as content.
+    /// # Examples
+    /// ```
+    /// use skip_bom::{BomType, SkipEncodingBom};
+    /// use std::io::{Cursor, Read, Seek, SeekFrom};
+    ///
+    /// let mut reader = SkipEncodingBom::new(BomType::all(), Cursor::new(b"\xEF\xBB\xBFtext"));
+    /// let mut text = String::new();
+    /// let _ = reader.read_to_string(&mut text).unwrap();
+    /// let _ = reader.get_mut().seek(SeekFrom::Start(0)).unwrap();
+    /// reader.restart_detection();
+    /// assert_eq!(None, reader.bom_found());
+    /// text.clear();
+    /// let _ = reader.read_to_string(&mut text).unwrap();
+    /// assert_eq!("text", text);
+    /// ```
+    pub fn restart_detection(&mut self) {
+        self.state = BomState::default();
     }
-}
 
-enum NextStateResult {
-    IncompleteRead(BomBytesPushBuffer),
-    NewState(BomState),
-}
-
-impl<'a, R: Read> Read for SkipEncodingBom<'a, R> {
-    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+    /// Read while the BOM presence is not determined yet or while bytes read after the BOM are still buffered.
+    #[cold]
+    #[inline(never)]
+    fn read_resolving(&mut self, buf: &mut [u8]) -> Result<usize> {
         loop {
             match &mut self.state {
                 // initial state
-                BomState::Initial { start_bytes } => match Self::state_after_initial(start_bytes, &mut self.reader, self.bom_types)? {
-                    NextStateResult::NewState(new_state) => self.state = new_state,
+                BomState::Initial { start_bytes } => match Self::state_after_initial(start_bytes, &mut self.reader, self.bom_types, self.probe_len)? {
+                    NextStateResult::NewState(new_state) => self.set_state(new_state)?,
                     NextStateResult::IncompleteRead(new_bytes) => {
                         self.state = BomState::Initial { start_bytes: new_bytes };
                         break Ok(0)
@@ -132,13 +351,13 @@ impl<'a, R: Read> Read for SkipEncodingBom<'a, R> {
                 },
                 BomState::PostInitBuffer { bytes_after_bom, bom_type } => {
                     let mut bytes_read = bytes_after_bom.read(buf)?;
-                    if bytes_after_bom.position() == bytes_after_bom.get_ref().byte_count() as _ {
+                    if bytes_after_bom.position() == bytes_after_bom.get_ref().byte_count() as u64 {
                         // if we are at the end of the post-init buffer, change state
                         self.state = BomState::Final(bom_type.take());
                         if bytes_read < buf.len() {
                             // if there is remaining space in the buffer
                             // then read from the underlying reader
-                            bytes_read += self.reader.read(&mut buf[bytes_read..])?;
+                            bytes_read += checked_read(&mut self.reader, &mut buf[bytes_read..])?;
                         }
                     }
                     break Ok(bytes_read)
@@ -148,4 +367,254 @@ impl<'a, R: Read> Read for SkipEncodingBom<'a, R> {
             }
         }
     }
+
+    /// Read from a source that may gain bytes later, such as a file being appended to, telling apart the start bytes
+    /// held back as a possible BOM from the absence of data.
+    ///
+    /// Reading such a source with [`Read::read`] returns 0 bytes both when no data is available yet and when the bytes
+    /// available are the start of a possible BOM, such as `EF BB`: [`ProgressiveRead::Pending`] is returned in the
+    /// latter case, to poll again once the source has grown. Unlike [`Read::read`], the underlying reader returning no
+    /// bytes is not taken as the end of the stream: `FF FE` is held back until the source grows, as it may be the
+    /// start of a UTF-32LE BOM. Once the source is known to have ended, call [`SkipEncodingBom::end_of_stream`] to
+    /// determine the BOM presence with the bytes held back.
+    /// # Examples
+    /// ```
+    /// use skip_bom::{BomType, ProgressiveRead, SkipEncodingBom};
+    /// use std::io::Cursor;
+    ///
+    /// let mut reader = SkipEncodingBom::new(BomType::all(), Cursor::new(Vec::new()));
+    /// let mut buf = [0; 64];
+    /// assert_eq!(ProgressiveRead::NoData, reader.poll_more(&mut buf).unwrap());
+    /// reader.get_mut().get_mut().extend_from_slice(b"\xEF\xBB");
+    /// assert_eq!(ProgressiveRead::Pending { held_bytes: 2 }, reader.poll_more(&mut buf).unwrap());
+    /// reader.get_mut().get_mut().extend_from_slice(b"\xBFlog line");
+    /// assert_eq!(ProgressiveRead::Read(8), reader.poll_more(&mut buf).unwrap());
+    /// assert_eq!(b"log line", &buf[..8]);
+    /// ```
+    pub fn poll_more(&mut self, buf: &mut [u8]) -> Result<ProgressiveRead> {
+        while let BomState::Initial { start_bytes } = &self.state {
+            match BomState::try_read_bom(start_bytes, &mut self.reader, self.bom_types, self.probe_len)? {
+                // no new bytes were read: the source may still grow
+                TryReadBomResult::Incomplete(new_start_bytes) if start_bytes.byte_count() == new_start_bytes.byte_count() => {
+                    return Ok(match new_start_bytes.byte_count() {
+                        0 => ProgressiveRead::NoData,
+                        held_bytes => ProgressiveRead::Pending { held_bytes },
+                    });
+                },
+                TryReadBomResult::Incomplete(new_start_bytes) => self.state = BomState::Initial { start_bytes: new_start_bytes },
+                TryReadBomResult::Complete { bom_type, bytes_after_bom } => self.set_state(BomState::after_bom(bom_type, bytes_after_bom))?,
+            }
+        }
+        Ok(match self.read(buf)? {
+            0 => ProgressiveRead::NoData,
+            bytes_read => ProgressiveRead::Read(bytes_read),
+        })
+    }
+
+    /// Declare that the underlying reader has ended, to determine the BOM presence with the bytes read so far.
+    ///
+    /// The reader is read until the BOM presence is determined or it returns no more bytes. The start bytes held back
+    /// as a possible BOM, if any, are then returned as content by the next reads, see [`SkipEncodingBom::poll_more`].
+    /// Returns the BOM found.
+    /// # Examples
+    /// ```
+    /// use skip_bom::{BomType, SkipEncodingBom};
+    /// use std::io::Read;
+    ///
+    /// let mut reader = SkipEncodingBom::new(BomType::all(), &b"\xEF\xBB"[..]);
+    /// assert_eq!(None, reader.end_of_stream().unwrap());
+    /// assert_eq!(Some(None), reader.bom_found());
+    /// let mut buf = Vec::new();
+    /// let _ = reader.read_to_end(&mut buf).unwrap();
+    /// assert_eq!(b"\xEF\xBB", buf.as_slice());
+    /// ```
+    pub fn end_of_stream(&mut self) -> Result<Option<BomType>> {
+        let bom_type = self.read_bom()?;
+        if let BomState::Initial { start_bytes } = &self.state {
+            self.state = match start_bytes.byte_count() {
+                0 => BomState::Final(None),
+                _ => BomState::PostInitBuffer { bytes_after_bom: Cursor::new(*start_bytes), bom_type: None },
+            };
+        }
+        Ok(bom_type)
+    }
+
+    /// Returns `true` if the [`Debug`] output leaves out the buffered content: see [`SkipEncodingBom::with_redacted_debug`].
+    pub(crate) fn redacts_debug(&self) -> bool {
+        self.redact_debug
+    }
+
+    /// Returns `true` while the BOM presence is not determined or start bytes are still buffered.
+    pub(crate) fn detection_in_progress(&self) -> bool {
+        !matches!(self.state, BomState::Final(_))
+    }
+
+    pub(crate) fn reader_and_state_mut(&mut self) -> (&mut R, &mut BomState) {
+        (&mut self.reader, &mut self.state)
+    }
+
+    fn set_state(&mut self, new_state: BomState) -> Result<()> {
+        self.state = if self.keep_bom { new_state.with_bom_bytes() } else { new_state };
+        match (self.state.bom_found(), &self.bom_hook) {
+            (Some(Some(bom_type)), Some(bom_hook)) => bom_hook(bom_type),
+            _ => Ok(()),
+        }
+    }
+
+    fn state_after_initial(start_bytes: &BomBytesPushBuffer, reader: &mut R, bom_types: &[BomType], probe_len: usize) -> Result<NextStateResult> {
+        use NextStateResult::*;
+        match BomState::try_read_bom(start_bytes, reader, bom_types, probe_len)? {
+            // no new bytes were read: the reader ended, so a complete BOM that could have been the start of a longer one is found
+            TryReadBomResult::Incomplete(new_start_bytes) if start_bytes.byte_count() == new_start_bytes.byte_count() => {
+                match BomType::find_bytes_bom_at_end(new_start_bytes.bytes(), bom_types) {
+                    Some(bom_type) if new_start_bytes.byte_count() == bom_type.bom_length() => Ok(NewState(BomState::Final(Some(bom_type)))),
+                    Some(bom_type) => {
+                        let bytes_after_bom = BomBytesPushBuffer::from_slice(&new_start_bytes.bytes()[bom_type.bom_length()..])?;
+                        Ok(NewState(BomState::PostInitBuffer { bytes_after_bom: Cursor::new(bytes_after_bom), bom_type: Some(bom_type) }))
+                    },
+                    None => Ok(IncompleteRead(new_start_bytes)),
+                }
+            },
+            // new bytes were read
+            TryReadBomResult::Incomplete(new_start_bytes) => Ok(NewState(BomState::Initial { start_bytes: new_start_bytes })),
+            // the BOM presence and type was determined
+            TryReadBomResult::Complete { bom_type, bytes_after_bom } => Ok(NewState(BomState::after_bom(bom_type, bytes_after_bom))),
+        }
+    }
+}
+
+impl<'a, R: Read + Seek + ?Sized> SkipEncodingBom<'a, R> {
+    /// Move the underlying reader back to the start of the stream and look for a BOM again: see
+    /// [`SkipEncodingBom::restart_detection`].
+    pub fn rewind(&mut self) -> Result<()> {
+        let _ = self.reader.seek(SeekFrom::Start(0))?;
+        self.restart_detection();
+        Ok(())
+    }
+}
+
+impl<'a, 'b> SkipEncodingBom<'a, &'b [u8]> {
+    /// Initialize an encoding BOM skip struct over bytes already in memory.
+    ///
+    /// As the whole input is known, the BOM presence is determined at once with a single comparison and reads copy
+    /// the bytes after the BOM directly from the slice. An input ending inside a possible BOM is returned as content. A
+    /// BOM hook set afterwards is not called.
+    /// # Examples
+    /// ```
+    /// use skip_bom::{BomType, SkipEncodingBom};
+    /// use std::io::Read;
+    ///
+    /// let mut reader = SkipEncodingBom::from_slice(BomType::all(), b"\xEF\xBB\xBFThis stream has a BOM.");
+    /// assert_eq!(Some(Some(BomType::UTF8)), reader.bom_found());
+    /// assert_eq!(b"This stream has a BOM.", reader.get_ref());
+    /// ```
+    pub fn from_slice(bom_types: &'a [BomType], bytes: &'b [u8]) -> Self {
+        let bom_type = find_whole_bytes_bom(bytes, bom_types);
+        let mut skip_encoding_bom = Self::new(bom_types, &bytes[bom_type.map_or(0, |bom_type| bom_type.bom_length())..]);
+        skip_encoding_bom.state = BomState::Final(bom_type);
+        skip_encoding_bom
+    }
+}
+
+impl<'a, T: AsRef<[u8]>> SkipEncodingBom<'a, Cursor<T>> {
+    /// Initialize an encoding BOM skip struct over a cursor on bytes already in memory, the BOM being looked for at the
+    /// cursor position.
+    ///
+    /// As for [`SkipEncodingBom::from_slice`], the BOM presence is determined at once and the cursor is moved past the
+    /// BOM: reads then go directly to the cursor.
+    /// # Examples
+    /// ```
+    /// use skip_bom::{BomType, SkipEncodingBom};
+    /// use std::io::{Cursor, Read};
+    ///
+    /// let reader = SkipEncodingBom::from_cursor(BomType::all(), Cursor::new(b"\xFF\xFEH\x00i\x00".to_vec()));
+    /// assert_eq!(Some(Some(BomType::UTF16LE)), reader.bom_found());
+    /// assert_eq!(2, reader.get_ref().position());
+    /// ```
+    pub fn from_cursor(bom_types: &'a [BomType], mut cursor: Cursor<T>) -> Self {
+        let bytes = cursor.get_ref().as_ref();
+        let position = (cursor.position() as usize).min(bytes.len());
+        let bom_type = find_whole_bytes_bom(&bytes[position..], bom_types);
+        cursor.set_position((position + bom_type.map_or(0, |bom_type| bom_type.bom_length())) as u64);
+        let mut skip_encoding_bom = Self::new(bom_types, cursor);
+        skip_encoding_bom.state = BomState::Final(bom_type);
+        skip_encoding_bom
+    }
+}
+
+/// Return the BOM `bytes` starts with, knowing that they are the whole input.
+fn find_whole_bytes_bom(bytes: &[u8], bom_types: &[BomType]) -> Option<BomType> {
+    match BomType::try_find_bytes_bom(bytes, bom_types) {
+        BomsBytesTest::Complete { bom_type, .. } => bom_type,
+        BomsBytesTest::Incomplete => BomType::find_bytes_bom_at_end(bytes, bom_types),
+    }
+}
+
+/// Result of [`SkipEncodingBom::poll_more`].
+#[must_use]
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ProgressiveRead {
+    /// This number of bytes was read.
+    Read(usize),
+    /// No bytes can be returned yet: the bytes available are held back as the start of a possible BOM, until the source
+    /// grows or is declared ended with [`SkipEncodingBom::end_of_stream`].
+    Pending {
+        /// Number of start bytes held back.
+        held_bytes: usize,
+    },
+    /// The underlying reader returned no bytes and none are held back: the source has ended or not grown yet.
+    NoData,
+}
+
+impl<'a, R: Read + Debug + ?Sized> Debug for SkipEncodingBom<'a, R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.redact_debug {
+            return f.debug_struct("SkipEncodingBom").field("state", &self.state.redacted()).field("bom_types", &self.bom_types).finish_non_exhaustive();
+        }
+        f.debug_struct("SkipEncodingBom")
+            .field("state", &self.state)
+            .field("bom_types", &self.bom_types)
+            .field("bom_hook", &self.bom_hook.is_some())
+            .field("probe_len", &self.probe_len)
+            .field("keep_bom", &self.keep_bom)
+            .field("reader", &&self.reader)
+            .finish()
+    }
+}
+
+enum NextStateResult {
+    IncompleteRead(BomBytesPushBuffer),
+    NewState(BomState),
+}
+
+impl<'a, R: Read + ?Sized> Read for SkipEncodingBom<'a, R> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        // once the BOM presence is determined and the buffered bytes are returned, read from the underlying reader directly
+        if let BomState::Final(_) = self.state {
+            return self.reader.read(buf);
+        }
+        self.read_resolving(buf)
+    }
+
+    fn read_exact(&mut self, mut buf: &mut [u8]) -> Result<()> {
+        while !buf.is_empty() {
+            if let BomState::Final(_) = self.state {
+                // the BOM presence is determined and no buffered byte is left
+                return self.reader.read_exact(buf);
+            }
+            match checked_read(self, buf) {
+                // the reader ended while start bytes of a possible BOM are held back: resolve them instead of failing
+                Ok(0) if matches!(self.state, BomState::Initial { .. }) => {
+                    let _ = self.end_of_stream()?;
+                },
+                Ok(0) => return Err(std::io::Error::new(std::io::ErrorKind::UnexpectedEof, "failed to fill whole buffer")),
+                Ok(bytes_read) => buf = &mut buf[bytes_read..],
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => (),
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(())
+    }
 }
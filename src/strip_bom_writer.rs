@@ -0,0 +1,138 @@
+use super::{BomBytesPushBuffer, BomStatus, BomType, BomsBytesTest, Result};
+
+use std::io::Write;
+
+/// What [`StripBomWriter::finish`] does with the bytes buffered when the stream ends while the BOM presence is not determined.
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FlushPolicy {
+    /// Write the buffered bytes, except for a BOM they are complete with such as `FF FE` for UTF-16LE.
+    #[default]
+    WriteBuffered,
+    /// Drop the buffered bytes.
+    Drop,
+}
+
+/// Write to I/O and skip the initial encoding BOM of the bytes written if present.
+///
+/// The first bytes written are buffered until the BOM presence is determined. Once all the bytes are written,
+/// [`StripBomWriter::finish`] must be called to handle the bytes still buffered if the stream was too short.
+/// # Examples
+/// ```
+/// use skip_bom::{BomStatus, BomType, StripBomWriter};
+/// use std::io::Write;
+///
+/// let mut writer = StripBomWriter::new(BomType::all(), Vec::new());
+/// writer.write_all(b"\xEF\xBB").unwrap();
+/// assert_eq!(BomStatus::Undetermined, writer.status());
+/// writer.write_all(b"\xBFThis stream has a BOM.").unwrap();
+/// assert_eq!(BomStatus::Bom(BomType::UTF8), writer.status());
+/// assert_eq!(b"This stream has a BOM.", writer.finish().unwrap().as_slice());
+/// ```
+#[derive(Debug, Clone)]
+pub struct StripBomWriter<'a, W: Write> {
+    writer: W,
+    bom_types: &'a [BomType],
+    probe_len: usize,
+    flush_policy: FlushPolicy,
+    /// Start bytes while the BOM presence is not determined, then bytes after the BOM not written to `writer` yet.
+    start_bytes: BomBytesPushBuffer,
+    pending_start: usize,
+    status: BomStatus,
+}
+
+impl<'a, W: Write> StripBomWriter<'a, W> {
+    /// Initialize a BOM stripping writer given any stream writer.
+    ///
+    /// # Arguments
+    ///
+    /// * `bom_types` - a slice with the types of BOM to check for. To skip any of the supported BOMs, pass [`BomType::all`].
+    /// * `writer` - the underlying output stream writer.
+    pub fn new(bom_types: &'a [BomType], writer: W) -> Self {
+        Self {
+            writer,
+            bom_types,
+            probe_len: BomType::max_probe_len(bom_types),
+            flush_policy: FlushPolicy::default(),
+            start_bytes: BomBytesPushBuffer::default(),
+            pending_start: 0,
+            status: BomStatus::Undetermined,
+        }
+    }
+    /// Set what [`StripBomWriter::finish`] does with the buffered bytes if the BOM presence is not determined.
+    pub fn with_flush_policy(mut self, flush_policy: FlushPolicy) -> Self {
+        self.flush_policy = flush_policy;
+        self
+    }
+    /// Return the BOM found: see [`SkipEncodingBom::bom_found`](crate::SkipEncodingBom::bom_found).
+    #[must_use]
+    pub fn bom_found(&self) -> Option<Option<BomType>> {
+        self.status.into()
+    }
+    /// Return the presence of a BOM as determined so far: see [`BomStatus`].
+    pub fn status(&self) -> BomStatus {
+        self.status
+    }
+    /// Get a shared reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.writer
+    }
+    /// Get a mutable reference to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.writer
+    }
+    /// Write the bytes still buffered according to the flush policy, flush the underlying writer and return it.
+    #[must_use = "the underlying writer is returned"]
+    pub fn finish(mut self) -> Result<W> {
+        if !self.status.is_determined() {
+            match (self.flush_policy, BomType::find_bytes_bom_at_end(self.start_bytes.bytes(), self.bom_types)) {
+                (FlushPolicy::WriteBuffered, Some(bom_type)) => self.set_status(BomStatus::Bom(bom_type), bom_type.bom_length()),
+                (FlushPolicy::WriteBuffered, None) => self.set_status(BomStatus::NoBom, 0),
+                (FlushPolicy::Drop, _) => self.set_status(BomStatus::NoBom, self.start_bytes.byte_count()),
+            }
+        }
+        self.flush()?;
+        Ok(self.writer)
+    }
+
+    fn set_status(&mut self, status: BomStatus, pending_start: usize) {
+        self.status = status;
+        self.pending_start = pending_start;
+    }
+
+    /// Write the bytes after the BOM buffered while determining its presence.
+    fn write_pending(&mut self) -> Result<()> {
+        let pending = &self.start_bytes.bytes()[self.pending_start.min(self.start_bytes.byte_count())..];
+        if !pending.is_empty() {
+            self.writer.write_all(pending)?;
+            self.pending_start = self.start_bytes.byte_count();
+        }
+        Ok(())
+    }
+}
+
+impl<'a, W: Write> Write for StripBomWriter<'a, W> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        if !self.status.is_determined() {
+            let count = buf.len().min(self.probe_len.saturating_sub(self.start_bytes.byte_count()));
+            self.start_bytes.push(&buf[..count])?;
+            if let BomsBytesTest::Complete { bom_type, additional_bytes } = BomType::try_find_bytes_bom(self.start_bytes.bytes(), self.bom_types) {
+                let status = bom_type.map_or(BomStatus::NoBom, BomStatus::Bom);
+                self.set_status(status, self.start_bytes.byte_count() - additional_bytes.len());
+            }
+            // the bytes after the BOM are written by the next write or flush
+            if count > 0 || buf.is_empty() {
+                return Ok(count);
+            }
+        }
+        self.write_pending()?;
+        self.writer.write(buf)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        if self.status.is_determined() {
+            self.write_pending()?;
+        }
+        self.writer.flush()
+    }
+}
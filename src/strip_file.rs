@@ -0,0 +1,182 @@
+use super::{check_cancelled, copy_chunks, BomType, CopyOptions, NoBomSlice, Result, Throttle};
+
+use std::ffi::OsString;
+use std::fs::{self, File};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::num::NonZeroU64;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
+
+/// Options of [`strip_bom_file`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct StripFileOptions {
+    /// Only report what would change, without writing anything.
+    pub dry_run: bool,
+    /// Copy the original file to `<file>.bak` before modifying it. No backup is written if the file is left unchanged.
+    pub backup: bool,
+    /// Rewrite the file in place rather than replacing it, so that everything but its content is kept: ownership,
+    /// extended attributes, ACLs, alternate data streams on Windows and hard links.
+    ///
+    /// Unlike the replacement, the rewrite is not atomic: an interrupted rewrite leaves a partially shifted file,
+    /// which [`StripFileOptions::backup`] allows to recover.
+    pub preserve_metadata: bool,
+    /// Maximum average throughput of the rewrite in bytes per second: see [`CopyOptions::max_bytes_per_second`].
+    pub max_bytes_per_second: Option<NonZeroU64>,
+}
+
+/// Changes made to a file by [`strip_bom_file`], or that would be made in a dry run.
+#[must_use]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct StripFileReport {
+    /// The BOM found at the start of the file.
+    pub bom: Option<BomType>,
+    /// Size of the file before stripping.
+    pub original_len: u64,
+    /// `true` if the file was rewritten: `false` if it has no BOM or in a dry run.
+    pub modified: bool,
+    /// Path of the backup of the original file, if one was written.
+    pub backup: Option<PathBuf>,
+}
+
+impl StripFileReport {
+    /// Returns the offsets of the bytes removed, or an empty range if the file has no BOM.
+    pub fn removed_range(&self) -> Range<u64> {
+        0..self.removed_bytes().len() as u64
+    }
+
+    /// Returns the bytes removed: the BOM found, if any.
+    pub fn removed_bytes(&self) -> &'static [u8] {
+        self.bom.map_or(&[], |bom_type| bom_type.bom_bytes())
+    }
+
+    /// Returns the size of the file once stripped.
+    pub fn new_len(&self) -> u64 {
+        self.original_len - self.removed_range().end
+    }
+}
+
+/// Remove the initial encoding BOM of a file in place, if present, and report the changes.
+///
+/// By default, the content after the BOM is copied to a temporary file in the same directory, which then replaces the
+/// original file with the same permissions. With [`StripFileOptions::preserve_metadata`], the content is shifted
+/// within the file instead. The file is not written to if it has no BOM or if [`StripFileOptions::dry_run`] is set.
+/// # Examples
+/// ```no_run
+/// use skip_bom::{strip_bom_file, BomType, StripFileOptions};
+///
+/// let options = StripFileOptions { dry_run: true, ..Default::default() };
+/// let report = strip_bom_file("data.csv", BomType::all(), &options).unwrap();
+/// if let Some(bom_type) = report.bom {
+///     println!("would remove the {:?} BOM: bytes {:?}, {} bytes left", bom_type, report.removed_range(), report.new_len());
+/// }
+/// ```
+pub fn strip_bom_file<P: AsRef<Path>>(path: P, bom_types: &[BomType], options: &StripFileOptions) -> Result<StripFileReport> {
+    strip_bom_file_with_progress(path, bom_types, options, None, |_, _| {})
+}
+
+/// Remove the initial encoding BOM of a file in place like [`strip_bom_file`], reporting the progress and checking for
+/// cancellation, for instance to display the rewrite of a large file in a GUI.
+///
+/// `progress` is called with the number of bytes of content written so far and the total, [`StripFileReport::new_len`],
+/// after each chunk written. Once `cancel` is set, the rewrite stops with an [`ErrorKind::Other`](io::ErrorKind::Other)
+/// error of code [`BomErrorCode::Cancelled`] and the original file is left unchanged. With
+/// [`StripFileOptions::preserve_metadata`], the cancellation is only checked before the rewrite starts, as stopping it
+/// would leave a partially shifted file.
+/// # Examples
+/// ```no_run
+/// use skip_bom::{strip_bom_file_with_progress, BomType, StripFileOptions};
+/// use std::sync::atomic::AtomicBool;
+///
+/// let cancel = AtomicBool::new(false);
+/// let _ = strip_bom_file_with_progress("huge.csv", BomType::all(), &StripFileOptions::default(), Some(&cancel), |done, total| {
+///     eprint!("\r{}/{} bytes", done, total);
+/// })
+/// .unwrap();
+/// ```
+pub fn strip_bom_file_with_progress<P, F>(path: P, bom_types: &[BomType], options: &StripFileOptions, cancel: Option<&AtomicBool>, mut progress: F) -> Result<StripFileReport>
+where
+    P: AsRef<Path>,
+    F: FnMut(u64, u64),
+{
+    let path = path.as_ref();
+    let mut file = File::open(path)?;
+    let metadata = file.metadata()?;
+    let mut start = Vec::with_capacity(BomType::max_probe_len(bom_types));
+    let _ = (&mut file).take(BomType::max_probe_len(bom_types) as u64).read_to_end(&mut start)?;
+    // the start is the whole file when it is shorter than the probe
+    let mut report = StripFileReport { bom: NoBomSlice::new(bom_types, &start).bom(), original_len: metadata.len(), modified: false, backup: None };
+    if report.bom.is_none() || options.dry_run {
+        return Ok(report);
+    }
+    check_cancelled(cancel)?;
+    if options.backup {
+        let backup = with_suffix(path, ".bak");
+        let _ = fs::copy(path, &backup)?;
+        report.backup = Some(backup);
+    }
+    let bom_len = report.removed_range().end;
+    let new_len = report.new_len();
+    let mut progress = |done| progress(done, new_len);
+    let copy_options = CopyOptions { cancel, max_bytes_per_second: options.max_bytes_per_second, ..Default::default() };
+    if options.preserve_metadata {
+        shift_in_place(path, bom_len, new_len, &copy_options, &mut progress)?;
+    } else {
+        let _ = file.seek(SeekFrom::Start(bom_len))?;
+        replace_file(path, &mut file, &metadata, &copy_options, &mut progress)?;
+    }
+    report.modified = true;
+    Ok(report)
+}
+
+/// Replace the file at `path` with the rest of `content` through a temporary file with the same permissions.
+fn replace_file(path: &Path, content: &mut File, metadata: &fs::Metadata, copy_options: &CopyOptions, progress: &mut dyn FnMut(u64)) -> Result<()> {
+    let temp_path = with_suffix(path, ".skip_bom.tmp");
+    let result = (|| {
+        let mut temp = File::create(&temp_path)?;
+        let _ = copy_chunks(content, &mut temp, copy_options, progress)?;
+        temp.sync_all()?;
+        fs::set_permissions(&temp_path, metadata.permissions())?;
+        fs::rename(&temp_path, path)
+    })();
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    result
+}
+
+/// Move the content of the file at `path` after its first `offset` bytes to its start, then truncate it to `new_len`.
+///
+/// The cancellation of `copy_options` is not checked, as stopping would leave a partially shifted file.
+fn shift_in_place(path: &Path, offset: u64, new_len: u64, copy_options: &CopyOptions, progress: &mut dyn FnMut(u64)) -> Result<()> {
+    let mut file = fs::OpenOptions::new().read(true).write(true).open(path)?;
+    let throttle = Throttle::new(copy_options.max_bytes_per_second);
+    let mut buf = vec![0; throttle.chunk_len()];
+    let mut written = 0;
+    loop {
+        throttle.wait(written);
+        let _ = file.seek(SeekFrom::Start(written + offset))?;
+        let len = match file.read(&mut buf) {
+            Ok(len) => len,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        if len == 0 {
+            break;
+        }
+        let _ = file.seek(SeekFrom::Start(written))?;
+        file.write_all(&buf[..len])?;
+        written += len as u64;
+        progress(written);
+    }
+    file.set_len(new_len)?;
+    file.sync_all()
+}
+
+
+/// Append `suffix` to the file name of `path`.
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut path = OsString::from(path);
+    path.push(suffix);
+    path.into()
+}
@@ -0,0 +1,79 @@
+use super::{checked_read, BomType, Result, SkipEncodingBom};
+
+use std::io::{Read, Write};
+
+/// Reader copying all the bytes read from an underlying reader to a writer.
+///
+/// Used inside a [`SkipEncodingBom`], the writer receives the raw stream including the BOM bytes, for instance to
+/// compute a checksum of the original file while its content is parsed.
+#[derive(Debug, Clone)]
+pub struct TeeReader<R: Read, W: Write> {
+    reader: R,
+    writer: W,
+}
+
+impl<R: Read, W: Write> TeeReader<R, W> {
+    /// Initialize a tee reader that writes every byte read from `reader` to `writer`.
+    pub fn new(reader: R, writer: W) -> Self {
+        Self { reader, writer }
+    }
+
+    /// Unwraps this `TeeReader<R, W>`, returning the underlying reader and writer.
+    pub fn into_inner(self) -> (R, W) {
+        (self.reader, self.writer)
+    }
+
+    /// Get a shared reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.reader
+    }
+
+    /// Get a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.reader
+    }
+
+    /// Get a shared reference to the writer.
+    pub fn writer(&self) -> &W {
+        &self.writer
+    }
+
+    /// Get a mutable reference to the writer.
+    pub fn writer_mut(&mut self) -> &mut W {
+        &mut self.writer
+    }
+}
+
+impl<R: Read, W: Write> Read for TeeReader<R, W> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let bytes_read = checked_read(&mut self.reader, buf)?;
+        self.writer.write_all(&buf[..bytes_read])?;
+        Ok(bytes_read)
+    }
+}
+
+impl<'a, R: Read, W: Write> SkipEncodingBom<'a, TeeReader<R, W>> {
+    /// Initialize an encoding BOM skip struct that also writes all the bytes consumed from `reader`, including the BOM, to `writer`.
+    ///
+    /// # Arguments
+    ///
+    /// * `bom_types` - a slice with the types of BOM to check for. To skip any of the supported BOMs, pass [`BomType::all`].
+    /// * `reader` - the underlying input stream reader.
+    /// * `writer` - the writer receiving a copy of the raw input stream.
+    ///
+    /// # Examples
+    /// ```
+    /// use skip_bom::{BomType, SkipEncodingBom};
+    /// use std::io::Read;
+    ///
+    /// let mut reader = SkipEncodingBom::new_tee(BomType::all(), &b"\xEF\xBB\xBFThis stream has a BOM."[..], Vec::new());
+    /// let mut string = String::new();
+    /// let _ = reader.read_to_string(&mut string).unwrap();
+    /// assert_eq!("This stream has a BOM.", &string);
+    /// let (_, raw) = reader.into_inner().into_inner();
+    /// assert_eq!(b"\xEF\xBB\xBFThis stream has a BOM.", raw.as_slice());
+    /// ```
+    pub fn new_tee(bom_types: &'a [BomType], reader: R, writer: W) -> Self {
+        Self::new(bom_types, TeeReader::new(reader, writer))
+    }
+}
@@ -0,0 +1,227 @@
+use super::{BomError, BomErrorCode, BomStatus, BomType, Result, SkipEncodingBom};
+
+use std::io::{ErrorKind, Read};
+
+/// DOS end-of-file marker, `^Z`.
+const EOF_MARKER: u8 = 0x1A;
+
+/// Handling of the C0 control characters other than tab, CR and LF by a [`TextSanitizer`].
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ControlCharPolicy {
+    /// Control characters are not checked.
+    #[default]
+    Allow,
+    /// Control characters are counted: see [`TextSanitizer::control_char_count`].
+    Count,
+    /// A control character fails the read with an [`ErrorKind::InvalidData`] error.
+    Reject,
+}
+
+/// Returns `true` for the C0 control characters other than tab, CR and LF, which do not occur in text files.
+fn is_unexpected_control_char(byte: u8) -> bool {
+    byte < 0x20 && !matches!(byte, b'\t' | b'\r' | b'\n')
+}
+
+/// Rules applied by a [`TextSanitizer`] besides skipping the BOM.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SanitizeOptions {
+    /// Replace CRLF line endings with LF. Lone CR bytes are kept.
+    pub normalize_newlines: bool,
+    /// Remove a `^Z` byte at the very end of the stream. `^Z` bytes elsewhere are kept.
+    pub strip_eof_marker: bool,
+    /// Handling of the C0 control characters other than tab, CR and LF.
+    pub control_chars: ControlCharPolicy,
+    /// Number of bytes at the start of the content in which control characters are checked.
+    pub control_chars_window: usize,
+}
+
+impl Default for SanitizeOptions {
+    fn default() -> Self {
+        Self { normalize_newlines: true, strip_eof_marker: true, control_chars: ControlCharPolicy::default(), control_chars_window: 4096 }
+    }
+}
+
+/// Read text from I/O, skip the initial encoding BOM if present and remove the other marks left by Windows tools:
+/// CRLF line endings are normalized to LF and a trailing `^Z` is removed, each according to [`SanitizeOptions`].
+///
+/// The content is processed as bytes, which is correct for UTF-8 and ASCII-compatible encodings.
+/// # Examples
+/// ```
+/// use skip_bom::{BomType, TextSanitizer};
+/// use std::io::Read;
+///
+/// let mut reader = TextSanitizer::new(BomType::all(), &b"\xEF\xBB\xBFfirst line\r\nsecond line\r\n\x1A"[..]);
+/// let mut string = String::new();
+/// let _ = reader.read_to_string(&mut string).unwrap();
+/// assert_eq!("first line\nsecond line\n", &string);
+/// ```
+#[derive(Debug, Clone)]
+pub struct TextSanitizer<'a, R: Read> {
+    inner: SkipEncodingBom<'a, R>,
+    options: SanitizeOptions,
+    /// Byte read but not returned yet, as the next byte is needed to process it.
+    lookahead: Option<u8>,
+    /// Number of bytes returned so far.
+    position: usize,
+    control_char_count: usize,
+}
+
+impl<'a, R: Read> TextSanitizer<'a, R> {
+    /// Initialize a text sanitizer with the default rules given any stream reader.
+    ///
+    /// # Arguments
+    ///
+    /// * `bom_types` - a slice with the types of BOM to check for. To skip any of the supported BOMs, pass [`BomType::all`].
+    /// * `reader` - the underlying input stream reader.
+    pub fn new(bom_types: &'a [BomType], reader: R) -> Self {
+        Self::from_skip_encoding_bom(SkipEncodingBom::new(bom_types, reader))
+    }
+    /// Initialize a text sanitizer with the default rules from an encoding BOM skip struct.
+    pub fn from_skip_encoding_bom(inner: SkipEncodingBom<'a, R>) -> Self {
+        Self { inner, options: SanitizeOptions::default(), lookahead: None, position: 0, control_char_count: 0 }
+    }
+    /// Set the rules applied besides skipping the BOM.
+    pub fn with_options(mut self, options: SanitizeOptions) -> Self {
+        self.options = options;
+        self
+    }
+    /// Return the BOM found: see [`SkipEncodingBom::bom_found`].
+    #[must_use]
+    pub fn bom_found(&self) -> Option<Option<BomType>> {
+        self.inner.bom_found()
+    }
+    /// Return the presence of a BOM as determined so far: see [`SkipEncodingBom::status`].
+    pub fn status(&self) -> BomStatus {
+        self.inner.status()
+    }
+    /// Return the number of C0 control characters other than tab, CR and LF found so far in the checked window, if
+    /// [`SanitizeOptions::control_chars`] is [`ControlCharPolicy::Count`].
+    ///
+    /// A non-zero count is a strong sign that the content is not text.
+    /// # Examples
+    /// ```
+    /// use skip_bom::{BomType, ControlCharPolicy, SanitizeOptions, TextSanitizer};
+    /// use std::io::Read;
+    ///
+    /// let options = SanitizeOptions { control_chars: ControlCharPolicy::Count, ..Default::default() };
+    /// let mut reader = TextSanitizer::new(BomType::all(), &b"\x89PNG\r\n\x1A\n\x00\x00"[..]).with_options(options);
+    /// let _ = reader.read_to_end(&mut Vec::new()).unwrap();
+    /// assert_eq!(3, reader.control_char_count());
+    /// ```
+    pub fn control_char_count(&self) -> usize {
+        self.control_char_count
+    }
+    /// Unwraps this `TextSanitizer<R>`, returning the encoding BOM skip struct. A byte held to process the next one is lost.
+    pub fn into_inner(self) -> SkipEncodingBom<'a, R> {
+        self.inner
+    }
+    /// Get a shared reference to the encoding BOM skip struct.
+    pub fn get_ref(&self) -> &SkipEncodingBom<'a, R> {
+        &self.inner
+    }
+    /// Get a mutable reference to the encoding BOM skip struct.
+    pub fn get_mut(&mut self) -> &mut SkipEncodingBom<'a, R> {
+        &mut self.inner
+    }
+
+    /// Returns `true` if the byte must be held until the next byte or the end of the stream is known.
+    fn must_hold(&self, byte: u8) -> bool {
+        (byte == b'\r' && self.options.normalize_newlines) || (byte == EOF_MARKER && self.options.strip_eof_marker)
+    }
+
+    /// Check the control characters of bytes about to be returned, according to the options.
+    fn check_control_chars(&mut self, bytes: &[u8]) -> Result<()> {
+        let window_len = self.options.control_chars_window.saturating_sub(self.position).min(bytes.len());
+        self.position = self.position.saturating_add(bytes.len());
+        if self.options.control_chars == ControlCharPolicy::Allow {
+            return Ok(());
+        }
+        let count = bytes[..window_len].iter().filter(|byte| is_unexpected_control_char(**byte)).count();
+        self.control_char_count += count;
+        if count > 0 && self.options.control_chars == ControlCharPolicy::Reject {
+            return Err(BomError::new_io(ErrorKind::InvalidData, BomErrorCode::ControlCharacters, None, "the stream contains control characters and may not be text"));
+        }
+        Ok(())
+    }
+
+    /// Returns `true` if `byte` is kept given the byte that follows it.
+    fn keeps(&self, byte: u8, next: u8) -> bool {
+        !(byte == b'\r' && next == b'\n' && self.options.normalize_newlines)
+    }
+}
+
+impl<'a, R: Read> Read for TextSanitizer<'a, R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        loop {
+            let lookahead = self.lookahead.take();
+            let offset = lookahead.map_or(0, |_| 1);
+            if let (Some(byte), 1) = (lookahead, buf.len()) {
+                // no room for the next byte in the buffer
+                let mut next = [0u8];
+                let bytes_read = match self.inner.read(&mut next) {
+                    Ok(bytes_read) => bytes_read,
+                    Err(e) => {
+                        self.lookahead = lookahead;
+                        return Err(e);
+                    },
+                };
+                if bytes_read == 0 {
+                    if byte == EOF_MARKER && self.options.strip_eof_marker {
+                        return Ok(0);
+                    }
+                    buf[0] = byte;
+                    return self.check_control_chars(&buf[..1]).map(|_| 1);
+                }
+                self.lookahead = Some(next[0]);
+                if self.keeps(byte, next[0]) {
+                    buf[0] = byte;
+                    return self.check_control_chars(&buf[..1]).map(|_| 1);
+                }
+                continue;
+            }
+            if let Some(byte) = lookahead {
+                buf[0] = byte;
+            }
+            let bytes_read = match self.inner.read(&mut buf[offset..]) {
+                Ok(bytes_read) => bytes_read.min(buf.len() - offset),
+                Err(e) => {
+                    self.lookahead = lookahead;
+                    return Err(e);
+                },
+            };
+            let length = offset + bytes_read;
+            if bytes_read == 0 {
+                // end of the stream
+                return match lookahead {
+                    Some(EOF_MARKER) if self.options.strip_eof_marker => Ok(0),
+                    Some(_) => self.check_control_chars(&buf[..1]).map(|_| 1),
+                    None => Ok(0),
+                };
+            }
+            let mut written = 0;
+            for i in 0..length {
+                let byte = buf[i];
+                let next = if i + 1 < length { Some(buf[i + 1]) } else { None };
+                let keep = match next {
+                    Some(next) => self.keeps(byte, next),
+                    None if self.must_hold(byte) => {
+                        self.lookahead = Some(byte);
+                        false
+                    },
+                    None => true,
+                };
+                if keep {
+                    buf[written] = byte;
+                    written += 1;
+                }
+            }
+            if written > 0 {
+                return self.check_control_chars(&buf[..written]).map(|_| written);
+            }
+        }
+    }
+}
@@ -0,0 +1,101 @@
+//! Typestate API: a reader whose BOM detection state is part of its type.
+//!
+//! [`SkipEncodingBom<R, Undetected>`](SkipEncodingBom) can only be turned into a
+//! [`SkipEncodingBom<R, Detected>`](SkipEncodingBom) by [`SkipEncodingBom::detect`]: querying the BOM found or
+//! reading the content before the detection does not compile.
+//! # Examples
+//! ```
+//! use skip_bom::typestate::SkipEncodingBom;
+//! use skip_bom::BomType;
+//! use std::io::Read;
+//!
+//! let reader = SkipEncodingBom::new(BomType::all(), &b"\xEF\xBB\xBFThis stream has a BOM."[..]);
+//! let mut reader = reader.detect().unwrap();
+//! assert_eq!(Some(BomType::UTF8), reader.bom_found());
+//! let mut string = String::new();
+//! let _ = reader.read_to_string(&mut string).unwrap();
+//! assert_eq!("This stream has a BOM.", &string);
+//! ```
+//!
+//! ```compile_fail
+//! use skip_bom::typestate::SkipEncodingBom;
+//! use skip_bom::BomType;
+//!
+//! let reader = SkipEncodingBom::new(BomType::all(), &b"\xEF\xBB\xBFThis stream has a BOM."[..]);
+//! // the BOM was not detected yet
+//! let _ = reader.bom_found();
+//! ```
+
+use super::{BomType, Result};
+
+use std::io::{ErrorKind, Read};
+
+/// State of a reader whose BOM presence is not determined yet.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Undetected;
+
+/// State of a reader whose BOM presence is determined, with the BOM found if there was one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Detected(Option<BomType>);
+
+/// Read from I/O and skip the initial encoding BOM if present, with the detection state `S` being either [`Undetected`] or [`Detected`].
+#[derive(Debug, Clone)]
+pub struct SkipEncodingBom<'a, R: Read, S = Undetected> {
+    inner: crate::SkipEncodingBom<'a, R>,
+    state: S,
+}
+
+impl<'a, R: Read> SkipEncodingBom<'a, R, Undetected> {
+    /// Initialize an encoding BOM skip struct given any stream reader: see [`crate::SkipEncodingBom::new`].
+    pub fn new(bom_types: &'a [BomType], reader: R) -> Self {
+        Self { inner: crate::SkipEncodingBom::new(bom_types, reader), state: Undetected }
+    }
+    /// Read the start of the stream until the BOM presence is determined.
+    ///
+    /// If the reader ends before the BOM presence can be determined, the stream is considered not to start with a BOM and
+    /// the bytes read are content, see [`crate::SkipEncodingBom::end_of_stream`]. Reads interrupted with [`ErrorKind::Interrupted`] are retried; on any other error the reader is lost.
+    #[must_use = "the detected reader is returned and this one is consumed"]
+    pub fn detect(mut self) -> Result<SkipEncodingBom<'a, R, Detected>> {
+        let bom_type = loop {
+            match self.inner.end_of_stream() {
+                Err(e) if e.kind() == ErrorKind::Interrupted => (),
+                result => break result?,
+            }
+        };
+        Ok(SkipEncodingBom { inner: self.inner, state: Detected(bom_type) })
+    }
+}
+
+impl<'a, R: Read> SkipEncodingBom<'a, R, Detected> {
+    /// Return the BOM found, or [`None`] if the stream does not start with a BOM.
+    #[must_use]
+    pub fn bom_found(&self) -> Option<BomType> {
+        self.state.0
+    }
+    /// Unwraps this struct, returning the untyped encoding BOM skip struct with the same detection state.
+    pub fn into_untyped(self) -> crate::SkipEncodingBom<'a, R> {
+        self.inner
+    }
+}
+
+impl<'a, R: Read, S> SkipEncodingBom<'a, R, S> {
+    /// Unwraps this struct, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner.into_inner()
+    }
+    /// Get a shared reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        self.inner.get_ref()
+    }
+    /// Get a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        self.inner.get_mut()
+    }
+}
+
+impl<'a, R: Read> Read for SkipEncodingBom<'a, R, Detected> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        self.inner.read(buf)
+    }
+}
@@ -0,0 +1,142 @@
+use super::{BomType, Result, SkipEncodingBom};
+
+use std::io::{ErrorKind, Read};
+
+const READ_CHUNK_LENGTH: usize = 8 * 1024;
+
+/// Chunk of a stream yielded by [`Utf8Chunks`]: valid UTF-8 text followed by a run of bytes that are not valid UTF-8.
+///
+/// Either part may be empty, but not both.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Utf8Chunk {
+    valid: String,
+    invalid: Vec<u8>,
+}
+
+impl Utf8Chunk {
+    /// Returns the valid UTF-8 text of the chunk.
+    pub fn valid(&self) -> &str {
+        &self.valid
+    }
+
+    /// Returns the bytes following the valid text that are not valid UTF-8: an invalid sequence of at most 3 bytes, or
+    /// an incomplete sequence at the end of the stream.
+    pub fn invalid(&self) -> &[u8] {
+        &self.invalid
+    }
+
+    /// Unwraps this chunk, returning its valid text and invalid bytes.
+    pub fn into_parts(self) -> (String, Vec<u8>) {
+        (self.valid, self.invalid)
+    }
+}
+
+/// Iterator over the content of a stream after its BOM as chunks of valid UTF-8 text and invalid bytes, like
+/// [`Utf8Chunks`](https://doc.rust-lang.org/std/str/struct.Utf8Chunks.html) on a byte slice but reading the stream
+/// incrementally.
+///
+/// The valid text is split into chunks of the size of the reads at most, and only invalid sequences are held until
+/// the following bytes are read, so that the whole stream is never buffered. The BOM is skipped but the content is not
+/// decoded: a UTF-16 stream is yielded mostly as invalid bytes.
+/// # Examples
+/// ```
+/// use skip_bom::{BomType, Utf8Chunks};
+///
+/// let mut lossy = String::new();
+/// for chunk in Utf8Chunks::new(BomType::all(), &b"\xEF\xBB\xBFcaf\xC3\xA9 \xFF\xFEbar"[..]) {
+///     let chunk = chunk.unwrap();
+///     lossy.push_str(chunk.valid());
+///     if !chunk.invalid().is_empty() {
+///         lossy.push('\u{FFFD}');
+///     }
+/// }
+/// assert_eq!("café \u{FFFD}\u{FFFD}bar", lossy);
+/// ```
+#[derive(Debug)]
+pub struct Utf8Chunks<'a, R: Read> {
+    reader: SkipEncodingBom<'a, R>,
+    /// Bytes read and not yielded yet, from `start`.
+    buffer: Vec<u8>,
+    start: usize,
+    ended: bool,
+}
+
+impl<'a, R: Read> Utf8Chunks<'a, R> {
+    /// Initialize the iterator given any stream reader and the types of BOM to skip.
+    pub fn new(bom_types: &'a [BomType], reader: R) -> Self {
+        Self::from_skip_encoding_bom(SkipEncodingBom::new(bom_types, reader))
+    }
+
+    /// Initialize the iterator over an encoding BOM skip struct.
+    pub fn from_skip_encoding_bom(reader: SkipEncodingBom<'a, R>) -> Self {
+        Self { reader, buffer: Vec::new(), start: 0, ended: false }
+    }
+
+    /// Return the BOM found, or [`None`] for the outer option if the presence of a BOM was not determined yet: see
+    /// [`SkipEncodingBom::bom_found`].
+    pub fn bom_found(&self) -> Option<Option<BomType>> {
+        self.reader.bom_found()
+    }
+
+    /// Unwraps this iterator, returning the encoding BOM skip struct. The bytes read and not yielded yet are lost.
+    pub fn into_inner(self) -> SkipEncodingBom<'a, R> {
+        self.reader
+    }
+
+    /// Split the next chunk off the pending bytes, if they hold one.
+    fn take_chunk(&mut self) -> Option<Utf8Chunk> {
+        let pending = &self.buffer[self.start..];
+        let (valid_len, invalid_len) = match std::str::from_utf8(pending) {
+            Ok(_) => (pending.len(), 0),
+            Err(e) => match e.error_len() {
+                Some(error_len) => (e.valid_up_to(), error_len),
+                // the stream ends with an incomplete sequence
+                None if self.ended => (e.valid_up_to(), pending.len() - e.valid_up_to()),
+                // the sequence may be completed by the next bytes
+                None => (e.valid_up_to(), 0),
+            },
+        };
+        if valid_len + invalid_len == 0 {
+            return None;
+        }
+        let valid = std::str::from_utf8(&pending[..valid_len]).unwrap_or_default().to_string();
+        let invalid = pending[valid_len..valid_len + invalid_len].to_vec();
+        self.start += valid_len + invalid_len;
+        Some(Utf8Chunk { valid, invalid })
+    }
+
+    /// Read more bytes after the pending ones.
+    fn fill(&mut self) -> Result<()> {
+        let _ = self.buffer.drain(..self.start);
+        self.start = 0;
+        let pending_len = self.buffer.len();
+        self.buffer.resize(pending_len + READ_CHUNK_LENGTH, 0);
+        let result = loop {
+            match self.reader.read(&mut self.buffer[pending_len..]) {
+                Err(e) if e.kind() == ErrorKind::Interrupted => (),
+                result => break result,
+            }
+        };
+        self.buffer.truncate(pending_len + *result.as_ref().unwrap_or(&0));
+        self.ended = result? == 0;
+        Ok(())
+    }
+}
+
+impl<'a, R: Read> Iterator for Utf8Chunks<'a, R> {
+    type Item = Result<Utf8Chunk>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(chunk) = self.take_chunk() {
+                return Some(Ok(chunk));
+            }
+            if self.ended {
+                return None;
+            }
+            if let Err(e) = self.fill() {
+                return Some(Err(e));
+            }
+        }
+    }
+}
@@ -0,0 +1,46 @@
+//! Sanitization of WebSocket text frames, for servers receiving messages from clients that prefix them with a BOM.
+//!
+//! The functions work on the payload of each message, whatever the WebSocket library: no reader is built per message.
+//! # Examples
+//! ```
+//! use skip_bom::websocket::TextFrameSanitizer;
+//!
+//! let sanitizer = TextFrameSanitizer { reject_utf16: true };
+//! assert_eq!(b"{\"hello\": 1}", sanitizer.sanitize(b"\xEF\xBB\xBF{\"hello\": 1}").unwrap());
+//! assert!(sanitizer.sanitize(b"\xFF\xFE{\x00}\x00").is_err());
+//! assert_eq!("hello", sanitizer.sanitize_str("\u{FEFF}hello"));
+//! ```
+
+use super::{BomError, BomErrorCode, BomType, BomTypeSet, NoBomSlice, Result};
+
+use std::io::ErrorKind;
+
+/// Sanitizer of the payloads of WebSocket text frames.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct TextFrameSanitizer {
+    /// Reject the payloads starting with a UTF-16 or UTF-32 BOM: text frames must be UTF-8, such payloads were sent
+    /// with the wrong encoding. Otherwise they are returned unchanged, to fail the UTF-8 validation of the caller.
+    pub reject_utf16: bool,
+}
+
+impl TextFrameSanitizer {
+    /// Returns the payload of a text frame without its UTF-8 BOM, before its UTF-8 validation.
+    ///
+    /// With [`TextFrameSanitizer::reject_utf16`], a payload starting with a UTF-16 or UTF-32 BOM fails with an
+    /// [`ErrorKind::InvalidData`] error of code [`BomErrorCode::BomForbidden`].
+    pub fn sanitize<'t>(&self, payload: &'t [u8]) -> Result<&'t [u8]> {
+        let slice = NoBomSlice::new(&BomTypeSet::WEB, payload);
+        match slice.bom() {
+            Some(BomType::UTF8) => Ok(slice.content()),
+            Some(bom_type) if self.reject_utf16 => {
+                Err(BomError::new_io(ErrorKind::InvalidData, BomErrorCode::BomForbidden, Some(bom_type), format!("the text frame starts with a {:?} BOM instead of being UTF-8", bom_type)))
+            },
+            _ => Ok(payload),
+        }
+    }
+
+    /// Returns the text of a text frame already validated as UTF-8 without its leading U+FEFF.
+    pub fn sanitize_str<'t>(&self, text: &'t str) -> &'t str {
+        text.strip_prefix('\u{FEFF}').unwrap_or(text)
+    }
+}
@@ -0,0 +1,36 @@
+use super::{BomTypeSet, DecodeReader};
+
+use std::io::{Read, Stdin};
+
+/// Wrap a redirected console pipe, such as the output of `cmd /u` or of a PowerShell redirection, to skip its BOM and
+/// decode UTF-16LE content to UTF-8: see [`DecodeReader`].
+///
+/// Only the BOMs written by Windows tools are looked for: see [`BomTypeSet::WINDOWS_TEXT`]. Content without a BOM is
+/// passed through as-is.
+/// # Examples
+/// ```no_run
+/// use skip_bom::console_pipe_text_no_bom;
+/// use std::io::Read;
+/// use std::process::{Command, Stdio};
+///
+/// let mut child = Command::new("cmd").args(&["/u", "/c", "dir"]).stdout(Stdio::piped()).spawn().unwrap();
+/// let mut output = String::new();
+/// let _ = console_pipe_text_no_bom(child.stdout.take().unwrap()).read_to_string(&mut output).unwrap();
+/// ```
+pub fn console_pipe_text_no_bom<R: Read>(pipe: R) -> DecodeReader<'static, R> {
+    DecodeReader::new(BomTypeSet::WINDOWS_TEXT.bom_types(), pipe)
+}
+
+/// Read the standard input, skipping its BOM and decoding UTF-16LE content to UTF-8 when it is redirected from a
+/// UTF-16LE file or pipe: see [`console_pipe_text_no_bom`].
+/// # Examples
+/// ```no_run
+/// use skip_bom::stdin_text_no_bom;
+/// use std::io::Read;
+///
+/// let mut input = String::new();
+/// let _ = stdin_text_no_bom().read_to_string(&mut input).unwrap();
+/// ```
+pub fn stdin_text_no_bom() -> DecodeReader<'static, Stdin> {
+    console_pipe_text_no_bom(std::io::stdin())
+}
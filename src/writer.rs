@@ -0,0 +1,13 @@
+//! Writers and copies removing the BOM of the bytes written.
+//! # Examples
+//! ```
+//! use skip_bom::writer::StripBomWriter;
+//! use skip_bom::BomType;
+//! use std::io::Write;
+//!
+//! let mut writer = StripBomWriter::new(BomType::all(), Vec::new());
+//! writer.write_all(b"\xEF\xBB\xBFcontent").unwrap();
+//! assert_eq!(b"content", writer.finish().unwrap().as_slice());
+//! ```
+
+pub use super::{copy_without_bom, CopyOptions, FlushPolicy, StripBomWriter};
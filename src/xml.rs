@@ -0,0 +1,49 @@
+use super::{checked_read, BomType, BomsBytesTest, BuiltinSniffer, DecodeReader, Result, SkipEncodingBom};
+
+use std::io::{BufReader, Chain, Cursor, ErrorKind, Read};
+
+/// XML reader returned by [`xml_reader_no_bom`].
+pub type XmlReaderNoBom<R> = quick_xml::Reader<BufReader<DecodeReader<'static, Chain<Cursor<Vec<u8>>, R>>>>;
+
+/// Build a [`quick_xml::Reader`] over a stream, skipping its BOM and transcoding UTF-16 and UTF-32 content to UTF-8.
+///
+/// When the stream does not start with a BOM, its encoding is guessed from the XML declaration with
+/// [`BuiltinSniffer::XmlDeclaration`]. The first bytes of the stream are read to do so.
+/// # Examples
+/// ```
+/// use quick_xml::events::Event;
+/// use skip_bom::xml_reader_no_bom;
+///
+/// // UTF-16LE without a BOM
+/// let bytes: Vec<u8> = "<?xml version=\"1.0\"?><root/>".encode_utf16().flat_map(u16::to_le_bytes).collect();
+/// let mut reader = xml_reader_no_bom(bytes.as_slice()).unwrap();
+/// let mut buf = Vec::new();
+/// assert!(matches!(reader.read_event_into(&mut buf).unwrap(), Event::Decl(_)));
+/// match reader.read_event_into(&mut buf).unwrap() {
+///     Event::Empty(element) => assert_eq!(b"root", element.name().as_ref()),
+///     event => panic!("unexpected event {:?}", event),
+/// }
+/// ```
+pub fn xml_reader_no_bom<R: Read>(mut reader: R) -> Result<XmlReaderNoBom<R>> {
+    let mut start = [0u8; 4];
+    let mut start_len = 0;
+    while start_len < start.len() {
+        match checked_read(&mut reader, &mut start[start_len..]) {
+            Ok(0) => break,
+            Ok(bytes_read) => start_len += bytes_read,
+            Err(e) if e.kind() == ErrorKind::Interrupted => (),
+            Err(e) => return Err(e),
+        }
+    }
+    let start = &start[..start_len];
+    let mut prelude = Vec::with_capacity(8);
+    if let BomsBytesTest::Complete { bom_type: None, .. } = BomType::try_find_bytes_bom(start, BomType::all()) {
+        // without a BOM, the BOM of the encoding guessed from the XML declaration is added for the content to be decoded
+        if let Some((encoding, _)) = BuiltinSniffer::XmlDeclaration.sniff(start) {
+            prelude.extend_from_slice(encoding.bom_bytes());
+        }
+    }
+    prelude.extend_from_slice(start);
+    let decode_reader = DecodeReader::from_skip_encoding_bom(SkipEncodingBom::with_prelude(BomType::all(), prelude, reader));
+    Ok(quick_xml::Reader::from_reader(BufReader::new(decode_reader)))
+}
@@ -0,0 +1,57 @@
+use super::{BomType, Result, SkipEncodingBom};
+
+use std::io::{Read, Seek};
+
+use zip::read::{ZipArchive, ZipFile};
+
+/// BOMs found in the file entries of a ZIP archive, as `(name, BOM)` pairs in archive order.
+///
+/// Entries sharing the same name each keep their own pair.
+pub type ZipEntriesBoms = Vec<(String, Option<BomType>)>;
+
+/// Call `f` with each file entry of a ZIP archive wrapped in an encoding BOM skip struct, and return the BOMs found.
+///
+/// Directory entries are skipped. If `f` does not read an entry far enough, its BOM is read after `f` returns.
+/// # Examples
+/// ```
+/// use skip_bom::{for_each_zip_entry, BomType};
+/// use std::io::{Cursor, Read, Write};
+///
+/// // Create an archive with a single CSV file.
+/// let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+/// writer.start_file("data.csv", zip::write::SimpleFileOptions::default()).unwrap();
+/// writer.write_all(b"\xEF\xBB\xBFa,b\n1,2\n").unwrap();
+/// let mut archive = zip::ZipArchive::new(writer.finish().unwrap()).unwrap();
+///
+/// let mut contents = Vec::new();
+/// let boms = for_each_zip_entry(&mut archive, BomType::all(), |name, reader| {
+///     let mut content = String::new();
+///     let _ = reader.read_to_string(&mut content)?;
+///     contents.push((name.to_owned(), content));
+///     Ok(())
+/// }).unwrap();
+/// assert_eq!(vec![("data.csv".to_owned(), Some(BomType::UTF8))], boms);
+/// assert_eq!(vec![("data.csv".to_owned(), "a,b\n1,2\n".to_owned())], contents);
+/// ```
+pub fn for_each_zip_entry<R, F>(archive: &mut ZipArchive<R>, bom_types: &[BomType], mut f: F) -> Result<ZipEntriesBoms>
+where
+    R: Read + Seek,
+    F: FnMut(&str, &mut SkipEncodingBom<ZipFile>) -> Result<()>,
+{
+    let mut boms = ZipEntriesBoms::new();
+    for index in 0..archive.len() {
+        let entry = archive.by_index(index)?;
+        if entry.is_dir() {
+            continue;
+        }
+        let name = entry.name().to_owned();
+        let mut reader = SkipEncodingBom::new(bom_types, entry);
+        f(&name, &mut reader)?;
+        let bom_type = match reader.bom_found() {
+            Some(bom_type) => bom_type,
+            None => reader.read_bom()?,
+        };
+        boms.push((name, bom_type));
+    }
+    Ok(boms)
+}
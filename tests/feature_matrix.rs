@@ -0,0 +1,181 @@
+//! Each optional subsystem is an independent feature: its API is present exactly when its feature is enabled, whatever
+//! the other features.
+
+use skip_bom::BomType;
+use std::io::Read;
+
+#[test]
+fn test_no_default_features() {
+    let manifest = std::fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.toml")).unwrap();
+    assert!(!manifest.lines().any(|line| line.trim_start().starts_with("default")));
+}
+
+#[test]
+fn test_core_api() {
+    // available without any feature
+    let mut text = String::new();
+    let _ = skip_bom::DecodeReader::new(BomType::all(), &b"\xFF\xFEH\x00i\x00"[..]).read_to_string(&mut text).unwrap();
+    assert_eq!("Hi", text);
+    assert!(skip_bom::detect(&b"\xEF\xBB\xBF"[..], &skip_bom::DetectOptions::default()).is_ok());
+    let _ = <std::path::Path as skip_bom::PathExt>::read_text_no_bom;
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_feature_serde() {
+    fn assert_serde<T: serde::Serialize + serde::de::DeserializeOwned>() {}
+    assert_serde::<BomType>();
+    assert_serde::<skip_bom::ReadPolicy>();
+}
+
+#[cfg(feature = "schemars")]
+#[test]
+fn test_feature_schemars() {
+    fn assert_json_schema<T: schemars::JsonSchema>() {}
+    assert_json_schema::<BomType>();
+}
+
+#[cfg(all(feature = "serde", feature = "serde_json"))]
+#[test]
+fn test_feature_serde_json() {
+    let value: Vec<u8> = skip_bom::from_json_reader(&b"\xEF\xBB\xBF[1]"[..]).unwrap();
+    assert_eq!(vec![1], value);
+}
+
+#[cfg(all(feature = "serde", feature = "serde_yaml"))]
+#[test]
+fn test_feature_serde_yaml() {
+    let value: Vec<u8> = skip_bom::from_yaml_reader(&b"\xEF\xBB\xBF[1]"[..]).unwrap();
+    assert_eq!(vec![1], value);
+}
+
+#[cfg(all(feature = "serde", feature = "toml"))]
+#[test]
+fn test_feature_toml() {
+    let value: std::collections::BTreeMap<String, u8> = skip_bom::from_toml_reader(&b"\xEF\xBB\xBFa = 1"[..]).unwrap();
+    assert_eq!(Some(&1), value.get("a"));
+}
+
+#[cfg(feature = "zip")]
+#[test]
+fn test_feature_zip() {
+    let mut archive = zip::ZipArchive::new(std::io::Cursor::new(b"PK\x05\x06\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0".to_vec())).unwrap();
+    assert!(skip_bom::for_each_zip_entry(&mut archive, BomType::all(), |_, _| Ok(())).unwrap().is_empty());
+}
+
+#[cfg(feature = "encoding_rs")]
+#[test]
+fn test_feature_encoding_rs() {
+    let _: Option<&'static encoding_rs::Encoding> = Some(encoding_rs::UTF_8);
+    assert!(skip_bom::detect(&b"\xFF\xFE"[..], &skip_bom::DetectOptions::default()).is_ok());
+}
+
+#[cfg(all(feature = "encoding_rs", feature = "encoding_rs_io"))]
+#[test]
+fn test_feature_encoding_rs_io() {
+    assert_eq!(Some(encoding_rs::UTF_16LE), skip_bom::encoding_rs_encoding(BomType::UTF16LE));
+}
+
+#[cfg(feature = "flate2")]
+#[test]
+fn test_feature_flate2() {
+    let _ = skip_bom::SkipEncodingBom::new_gz(BomType::all(), &b""[..]);
+}
+
+#[cfg(feature = "zstd")]
+#[test]
+fn test_feature_zstd() {
+    assert!(skip_bom::SkipEncodingBom::new_zstd(BomType::all(), &b""[..]).is_ok());
+}
+
+#[cfg(feature = "magic")]
+#[test]
+fn test_feature_magic() {
+    assert!(skip_bom::magic::find_signature(b"text", BomType::all()).is_none());
+}
+
+#[cfg(feature = "rspfile")]
+#[test]
+fn test_feature_rspfile() {
+    assert_eq!(vec!["-v".to_string()], skip_bom::rspfile::read_args(&b"\xEF\xBB\xBF-v"[..]).unwrap());
+}
+
+#[cfg(feature = "quick-xml")]
+#[test]
+fn test_feature_quick_xml() {
+    assert!(skip_bom::xml_reader_no_bom(&b"\xEF\xBB\xBF<a/>"[..]).is_ok());
+}
+
+#[cfg(feature = "follow")]
+#[test]
+fn test_feature_follow() {
+    let _ = skip_bom::follow_no_bom("tests/missing.txt");
+}
+
+#[cfg(feature = "websocket")]
+#[test]
+fn test_feature_websocket() {
+    assert_eq!("text", skip_bom::websocket::TextFrameSanitizer::default().sanitize_str("\u{FEFF}text"));
+}
+
+#[cfg(feature = "bytes")]
+#[test]
+fn test_feature_bytes() {
+    let (bom, payload) = skip_bom::clean_message(bytes::Bytes::from_static(b"\xEF\xBB\xBFtext"));
+    assert_eq!((Some(BomType::UTF8), &b"text"[..]), (bom, &payload[..]));
+}
+
+#[cfg(feature = "unstable")]
+#[test]
+fn test_feature_unstable() {
+    let sniffers: [&dyn skip_bom::Sniffer; 1] = [&|_: &[u8]| Some((BomType::UTF16LE, 100))];
+    let report = skip_bom::detect_with_sniffers(&b"\x00\x01"[..], &skip_bom::DetectOptions::default(), &sniffers).unwrap();
+    assert_eq!(skip_bom::CandidateSource::Custom(0), report.best().unwrap().source);
+}
+
+#[cfg(feature = "delimiter")]
+#[test]
+fn test_feature_delimiter() {
+    assert_eq!(Some(';'), skip_bom::guess_delimiter("a;b\nc;d\n"));
+}
+
+#[cfg(feature = "zeroize")]
+#[test]
+fn test_feature_zeroize() {
+    fn assert_zeroize<T: zeroize::Zeroize>() {}
+    assert_zeroize::<skip_bom::BomBytesPushBuffer>();
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn test_feature_tokio() {
+    let mut reader = skip_bom::AsyncSkipEncodingBom::new(BomType::all(), &b"\xEF\xBB\xBFtext"[..]);
+    assert_eq!(Some(BomType::UTF8), reader.read_bom().await.unwrap());
+}
+
+#[cfg(feature = "futures-core")]
+#[test]
+fn test_feature_futures_core() {
+    fn assert_stream<S: futures_core::Stream>(_: &S) {}
+    assert_stream(&skip_bom::SkipBomChunks::new(BomType::all(), futures_core_empty()));
+}
+
+/// Stream without chunks.
+#[cfg(feature = "futures-core")]
+fn futures_core_empty() -> impl futures_core::Stream<Item = Result<Vec<u8>, ()>> + Unpin {
+    struct Empty;
+    impl futures_core::Stream for Empty {
+        type Item = Result<Vec<u8>, ()>;
+        fn poll_next(self: std::pin::Pin<&mut Self>, _: &mut std::task::Context<'_>) -> std::task::Poll<Option<Self::Item>> {
+            std::task::Poll::Ready(None)
+        }
+    }
+    Empty
+}
+
+#[cfg(feature = "axum")]
+#[test]
+fn test_feature_axum() {
+    fn assert_from_request<T: axum::extract::FromRequest<()>>() {}
+    assert_from_request::<skip_bom::BomCleanText>();
+}
@@ -0,0 +1,27 @@
+#![allow(dead_code)]
+
+use std::path::PathBuf;
+
+/// Temporary file removed along with its backup when dropped.
+pub struct TempFile(pub PathBuf);
+
+impl TempFile {
+    pub fn new(name: &str, content: &[u8]) -> Self {
+        let path = std::env::temp_dir().join(format!("skip_bom_{}_{}", std::process::id(), name));
+        std::fs::write(&path, content).unwrap();
+        TempFile(path)
+    }
+
+    pub fn backup_path(&self) -> PathBuf {
+        let mut path = self.0.clone().into_os_string();
+        path.push(".bak");
+        path.into()
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+        let _ = std::fs::remove_file(self.backup_path());
+    }
+}
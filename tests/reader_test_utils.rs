@@ -0,0 +1,30 @@
+#![allow(dead_code)]
+
+use std::io::Read;
+
+/// Reader returning a single byte for each read.
+pub struct ByteByByteReader<'a>(pub &'a [u8]);
+
+impl Read for ByteByByteReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let count = buf.len().min(self.0.len()).min(1);
+        buf[..count].copy_from_slice(&self.0[..count]);
+        self.0 = &self.0[count..];
+        Ok(count)
+    }
+}
+
+/// Reader returning at most `chunk_len` bytes per read.
+pub struct ChunkedReader<'b> {
+    pub bytes: &'b [u8],
+    pub chunk_len: usize,
+}
+
+impl Read for ChunkedReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let len = self.chunk_len.min(buf.len()).min(self.bytes.len());
+        buf[..len].copy_from_slice(&self.bytes[..len]);
+        self.bytes = &self.bytes[len..];
+        Ok(len)
+    }
+}
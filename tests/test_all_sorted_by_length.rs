@@ -0,0 +1,44 @@
+use skip_bom::*;
+
+#[test]
+fn test_all_sorted_by_length_order() {
+    let sorted = BomType::all_sorted_by_length();
+    assert!(sorted.windows(2).all(|pair| pair[0].bom_length() > pair[1].bom_length() || (pair[0].bom_length() == pair[1].bom_length() && pair[0] < pair[1])));
+}
+
+#[test]
+fn test_all_sorted_by_length_contains_all() {
+    let mut sorted = BomType::all_sorted_by_length().to_vec();
+    let mut all = BomType::all().to_vec();
+    sorted.sort();
+    all.sort();
+    assert_eq!(all, sorted);
+}
+
+macro_rules! test_longest_first {
+    ($test_fn_name:ident, $bytes:expr, $expected:expr) => {
+        #[test]
+        fn $test_fn_name() {
+            // the BOM found does not depend on the order of the BOM types tested
+            let mut bom_types = BomType::all().to_vec();
+            for _ in 0..2 {
+                assert_eq!($expected, BomType::try_find_bytes_bom($bytes, &bom_types));
+                bom_types.reverse();
+            }
+        }
+    };
+}
+
+test_longest_first!(test_longest_first_utf32le, b"\xFF\xFE\x00\x00a\x00\x00\x00", BomsBytesTest::Complete { bom_type: Some(BomType::UTF32LE), additional_bytes: b"a\x00\x00\x00" });
+test_longest_first!(test_longest_first_utf16le, b"\xFF\xFEa\x00", BomsBytesTest::Complete { bom_type: Some(BomType::UTF16LE), additional_bytes: b"a\x00" });
+test_longest_first!(test_longest_first_utf16le_null, b"\xFF\xFE\x00a", BomsBytesTest::Complete { bom_type: Some(BomType::UTF16LE), additional_bytes: b"\x00a" });
+test_longest_first!(test_longest_first_incomplete, b"\xFF\xFE\x00", BomsBytesTest::Incomplete);
+test_longest_first!(test_longest_first_utf32be, b"\x00\x00\xFE\xFF", BomsBytesTest::Complete { bom_type: Some(BomType::UTF32BE), additional_bytes: b"" });
+
+#[test]
+fn test_find_bytes_bom_at_end_longest_first() {
+    let bom_types = [BomType::UTF16LE, BomType::UTF32LE];
+    assert_eq!(Some(BomType::UTF32LE), BomType::find_bytes_bom_at_end(b"\xFF\xFE\x00\x00", &bom_types));
+    assert_eq!(Some(BomType::UTF16LE), BomType::find_bytes_bom_at_end(b"\xFF\xFE\x00", &bom_types));
+    assert_eq!(Some(BomType::UTF16LE), BomType::find_bytes_bom_at_end(b"\xFF\xFE", &bom_types));
+}
@@ -0,0 +1,86 @@
+use skip_bom::*;
+use std::io::{Cursor, Read};
+use std::panic::{RefUnwindSafe, UnwindSafe};
+
+fn assert_send<T: Send>() {}
+fn assert_sync<T: Sync>() {}
+fn assert_unpin<T: Unpin>() {}
+fn assert_unwind_safe<T: UnwindSafe + RefUnwindSafe>() {}
+
+macro_rules! test_auto_traits {
+    ($test_fn_name:ident, $type:ty) => {
+        #[test]
+        fn $test_fn_name() {
+            assert_send::<$type>();
+            assert_sync::<$type>();
+            assert_unpin::<$type>();
+            assert_unwind_safe::<$type>();
+        }
+    };
+    // guards borrowing a reader mutably are not unwind safe
+    ($test_fn_name:ident, $type:ty, borrowing) => {
+        #[test]
+        fn $test_fn_name() {
+            assert_send::<$type>();
+            assert_sync::<$type>();
+            assert_unpin::<$type>();
+        }
+    };
+}
+
+test_auto_traits!(test_auto_traits_skip_encoding_bom_cursor, SkipEncodingBom<'static, Cursor<Vec<u8>>>);
+test_auto_traits!(test_auto_traits_skip_encoding_bom_slice, SkipEncodingBom<'static, &'static [u8]>);
+test_auto_traits!(test_auto_traits_skip_encoding_bom_file, SkipEncodingBom<'static, std::fs::File>);
+test_auto_traits!(test_auto_traits_bom_type, BomType);
+test_auto_traits!(test_auto_traits_encoding_report, EncodingReport);
+test_auto_traits!(test_auto_traits_detect_options, DetectOptions<'static>);
+test_auto_traits!(test_auto_traits_charset_reconciliation, CharsetReconciliation);
+test_auto_traits!(test_auto_traits_decode_reader, DecodeReader<'static, &'static [u8]>);
+test_auto_traits!(test_auto_traits_shebang_bom, ShebangBom<'static, &'static [u8]>);
+test_auto_traits!(test_auto_traits_managed_reader, ManagedReader<&'static [u8]>);
+test_auto_traits!(test_auto_traits_utf8_chunks, Utf8Chunks<'static, &'static [u8]>);
+test_auto_traits!(test_auto_traits_resolved_bom, ResolvedBom<'static, &'static [u8]>, borrowing);
+test_auto_traits!(test_auto_traits_typestate_undetected, typestate::SkipEncodingBom<'static, &'static [u8], typestate::Undetected>);
+test_auto_traits!(test_auto_traits_typestate_detected, typestate::SkipEncodingBom<'static, &'static [u8], typestate::Detected>);
+test_auto_traits!(test_auto_traits_skip_encoding_bom_buf, SkipEncodingBomBuf<'static, &'static [u8]>);
+test_auto_traits!(test_auto_traits_first_line_bom, FirstLineBom<'static, &'static [u8]>);
+test_auto_traits!(test_auto_traits_text_sanitizer, TextSanitizer<'static, &'static [u8]>);
+test_auto_traits!(test_auto_traits_tee_reader, TeeReader<&'static [u8], Vec<u8>>);
+test_auto_traits!(test_auto_traits_strip_bom_writer, StripBomWriter<'static, Vec<u8>>);
+test_auto_traits!(test_auto_traits_inner_guard, InnerGuard<'static, 'static, &'static [u8]>, borrowing);
+test_auto_traits!(test_auto_traits_no_bom_slice, NoBomSlice<'static>);
+test_auto_traits!(test_auto_traits_strip_bom_from_first_line, StripBomFromFirstLine<std::vec::IntoIter<String>>);
+test_auto_traits!(test_auto_traits_path_lines, PathLines<&'static [u8]>);
+test_auto_traits!(test_auto_traits_multipart_sanitizer, MultipartSanitizer<'static>);
+#[cfg(feature = "tokio")]
+test_auto_traits!(test_auto_traits_async_skip_encoding_bom, AsyncSkipEncodingBom<'static, &'static [u8]>);
+#[cfg(feature = "tokio")]
+test_auto_traits!(test_auto_traits_bom_poll_state, BomPollState<'static>);
+#[cfg(feature = "futures-core")]
+test_auto_traits!(test_auto_traits_skip_bom_chunks, SkipBomChunks<'static, std::iter::Empty<Vec<u8>>>);
+#[cfg(feature = "follow")]
+test_auto_traits!(test_auto_traits_follow_no_bom, FollowNoBom);
+
+#[test]
+fn test_read_across_threads() {
+    let mut reader = SkipEncodingBom::new(BomType::all(), Cursor::new(b"\xEF\xBB".to_vec()));
+    assert_eq!(None, reader.read_bom().unwrap());
+    let handle = std::thread::spawn(move || {
+        reader.get_mut().get_mut().extend_from_slice(b"\xBFThis stream has a BOM.");
+        let mut string = String::new();
+        let _ = reader.read_to_string(&mut string).unwrap();
+        (reader.bom_found(), string)
+    });
+    assert_eq!((Some(Some(BomType::UTF8)), "This stream has a BOM.".to_owned()), handle.join().unwrap());
+}
+
+#[test]
+fn test_read_across_catch_unwind() {
+    let mut reader = SkipEncodingBom::new(BomType::all(), Cursor::new(b"\xEF\xBB\xBFThis stream has a BOM.".to_vec()));
+    let result = std::panic::catch_unwind(move || {
+        let mut buf = [0u8; 4];
+        reader.read_exact(&mut buf).unwrap();
+        buf
+    });
+    assert_eq!(b"This", &result.unwrap());
+}
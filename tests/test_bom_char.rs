@@ -0,0 +1,50 @@
+use skip_bom::*;
+
+fn utf16_bytes(text: &str, little_endian: bool) -> Vec<u8> {
+    text.encode_utf16().flat_map(|unit| if little_endian { unit.to_le_bytes() } else { unit.to_be_bytes() }).collect()
+}
+
+#[test]
+fn test_utf8_bom_bytes_encode_bom_char() {
+    let mut buf = [0u8; 4];
+    assert_eq!(BomType::UTF8.bom_bytes(), BOM_CHAR.encode_utf8(&mut buf).as_bytes());
+}
+
+#[test]
+fn test_utf16_bom_bytes_encode_bom_char() {
+    assert_eq!(BomType::UTF16LE.bom_bytes(), utf16_bytes("\u{FEFF}", true).as_slice());
+    assert_eq!(BomType::UTF16BE.bom_bytes(), utf16_bytes("\u{FEFF}", false).as_slice());
+}
+
+#[test]
+fn test_bom_char() {
+    for bom_type in BomType::all() {
+        assert_eq!('\u{FEFF}', bom_type.bom_char());
+    }
+}
+
+#[test]
+fn test_encodes_feff() {
+    for bom_type in BomType::all() {
+        assert_eq!(*bom_type != BomType::UTF7, bom_type.encodes_feff(), "{:?}", bom_type);
+    }
+}
+
+#[test]
+fn test_strip_bom_char() {
+    assert!(starts_with_bom_char("\u{FEFF}text"));
+    assert!(!starts_with_bom_char("text"));
+    assert_eq!("text", strip_bom_char("\u{FEFF}text"));
+    assert_eq!("\u{FEFF}text", strip_bom_char("\u{FEFF}\u{FEFF}text"));
+    assert_eq!("", strip_bom_char("\u{FEFF}"));
+    assert_eq!("", strip_bom_char(""));
+}
+
+#[test]
+fn test_remove_bom_char() {
+    let mut text = "\u{FEFF}text".to_owned();
+    assert!(remove_bom_char(&mut text));
+    assert_eq!("text", &text);
+    assert!(!remove_bom_char(&mut text));
+    assert_eq!("text", &text);
+}
@@ -0,0 +1,82 @@
+use skip_bom::*;
+use std::io::{Cursor, Error, ErrorKind, Read};
+
+fn reject_unsupported(bom_type: BomType) -> std::io::Result<()> {
+    if bom_type.is_supported_by_std_string() {
+        Ok(())
+    } else {
+        Err(Error::new(ErrorKind::InvalidData, "unsupported"))
+    }
+}
+
+fn panic_hook(_: BomType) -> std::io::Result<()> {
+    panic!("the BOM hook should not be called")
+}
+
+#[test]
+fn test_bom_hook_accepts() {
+    let mut reader = SkipEncodingBom::new(BomType::all(), Cursor::new(b"\xEF\xBB\xBFThis stream has a BOM.")).with_bom_hook(reject_unsupported);
+    let mut string = String::new();
+    let _ = reader.read_to_string(&mut string).unwrap();
+    assert_eq!("This stream has a BOM.", &string);
+}
+
+#[test]
+fn test_bom_hook_rejects_on_read() {
+    let mut reader = SkipEncodingBom::new(BomType::all(), Cursor::new(b"\x0E\xFE\xFFSCSU")).with_bom_hook(reject_unsupported);
+    let mut buf = [0u8; 16];
+    assert_eq!(ErrorKind::InvalidData, reader.read(&mut buf).unwrap_err().kind());
+    assert_eq!(Some(Some(BomType::SCSU)), reader.bom_found());
+    // the hook is only called once
+    assert_eq!(4, reader.read(&mut buf).unwrap());
+    assert_eq!(b"SCSU", &buf[..4]);
+}
+
+#[test]
+fn test_bom_hook_rejects_on_read_bom() {
+    let mut reader = SkipEncodingBom::new(BomType::all(), Cursor::new(b"\xDD\x73\x66\x73")).with_bom_hook(reject_unsupported);
+    assert_eq!(ErrorKind::InvalidData, reader.read_bom().unwrap_err().kind());
+    assert_eq!(Some(BomType::UTFEBDIC), reader.read_bom().unwrap());
+}
+
+#[test]
+fn test_bom_hook_not_called_without_bom() {
+    let mut reader = SkipEncodingBom::new(BomType::all(), Cursor::new(b"This stream has no BOM.")).with_bom_hook(panic_hook);
+    let mut string = String::new();
+    let _ = reader.read_to_string(&mut string).unwrap();
+    assert_eq!("This stream has no BOM.", &string);
+}
+
+#[test]
+fn test_bom_hook_closure() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    let bom_count = Arc::new(AtomicUsize::new(0));
+    let hook_count = Arc::clone(&bom_count);
+    let reader = SkipEncodingBom::new(BomType::all(), Cursor::new(b"\xEF\xBB\xBFThis stream has a BOM.")).with_bom_hook(move |_| {
+        let _ = hook_count.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    });
+    // the clones share the hook, and can be read on other threads
+    let clone = reader.clone();
+    let handle = std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        { clone }.read_to_end(&mut buf).unwrap()
+    });
+    let mut string = String::new();
+    let _ = { reader }.read_to_string(&mut string).unwrap();
+    assert_eq!("This stream has a BOM.", &string);
+    assert_eq!(string.len(), handle.join().unwrap());
+    assert_eq!(2, bom_count.load(Ordering::Relaxed));
+}
+
+#[test]
+fn test_is_supported_by_std_string() {
+    use BomType::*;
+
+    for bom_type in BomType::all() {
+        let expected = matches!(bom_type, UTF8 | UTF16LE | UTF16BE | UTF32LE | UTF32BE);
+        assert_eq!(expected, bom_type.is_supported_by_std_string(), "{:?}", bom_type);
+    }
+}
@@ -0,0 +1,70 @@
+use skip_bom::*;
+use std::io::{Cursor, Read};
+
+macro_rules! test_bom_kept {
+    ($name:ident, $bytes:expr, $bom:expr) => {
+        #[test]
+        fn $name() {
+            let bytes: &[u8] = $bytes;
+            // read in small chunks, including one smaller than the BOM
+            for buf_len in 1..=bytes.len().max(1) {
+                let mut reader = SkipEncodingBom::new(BomType::all(), Cursor::new(bytes)).with_bom_kept();
+                let mut content = Vec::new();
+                let mut buf = vec![0; buf_len];
+                loop {
+                    match reader.read(&mut buf).unwrap() {
+                        0 => break,
+                        bytes_read => content.extend_from_slice(&buf[..bytes_read]),
+                    }
+                }
+                assert_eq!(bytes, content.as_slice());
+                assert_eq!(Some($bom), reader.bom_found());
+            }
+        }
+    };
+}
+
+test_bom_kept!(test_bom_kept_utf8, b"\xEF\xBB\xBFtext", Some(BomType::UTF8));
+test_bom_kept!(test_bom_kept_utf16le, b"\xFF\xFEt\x00", Some(BomType::UTF16LE));
+test_bom_kept!(test_bom_kept_utf32le, b"\xFF\xFE\x00\x00t\x00\x00\x00", Some(BomType::UTF32LE));
+test_bom_kept!(test_bom_kept_bom_only, b"\xEF\xBB\xBF", Some(BomType::UTF8));
+test_bom_kept!(test_bom_kept_bom_at_end, b"\xFF\xFE", Some(BomType::UTF16LE));
+test_bom_kept!(test_bom_kept_no_bom, b"text", None);
+
+#[test]
+fn test_bom_kept_read_bom() {
+    let mut reader = SkipEncodingBom::new(BomType::all(), &b"\xEF\xBB\xBFtext"[..]).with_bom_kept();
+    assert_eq!(Some(BomType::UTF8), reader.read_bom().unwrap());
+    let mut text = String::new();
+    let _ = reader.read_to_string(&mut text).unwrap();
+    assert_eq!("\u{FEFF}text", text);
+}
+
+#[test]
+fn test_bom_kept_from_slice() {
+    let mut reader = SkipEncodingBom::from_slice(BomType::all(), b"\xEF\xBB\xBFtext").with_bom_kept();
+    let mut text = String::new();
+    let _ = reader.read_to_string(&mut text).unwrap();
+    assert_eq!("\u{FEFF}text", text);
+}
+
+#[test]
+fn test_bom_kept_rewind() {
+    let mut reader = SkipEncodingBom::new(BomType::all(), Cursor::new(b"\xEF\xBB\xBFtext")).with_bom_kept();
+    let mut buf = Vec::new();
+    let _ = reader.read_to_end(&mut buf).unwrap();
+    reader.rewind().unwrap();
+    buf.clear();
+    let _ = reader.read_to_end(&mut buf).unwrap();
+    assert_eq!(b"\xEF\xBB\xBFtext", buf.as_slice());
+}
+
+#[test]
+fn test_bom_kept_hook() {
+    fn reject(bom_type: BomType) -> Result<()> {
+        reject_external_decoder(bom_type)
+    }
+    let mut reader = SkipEncodingBom::new(BomType::all(), &b"\x2B\x2F\x76\x38text"[..]).with_bom_kept().with_bom_hook(reject);
+    assert!(reader.read_bom().is_err());
+    assert_eq!(Some(Some(BomType::UTF7)), reader.bom_found());
+}
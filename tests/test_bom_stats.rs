@@ -0,0 +1,76 @@
+use skip_bom::*;
+use std::io::Read;
+
+#[test]
+fn test_bom_stats_empty() {
+    let stats = BomStats::default();
+    assert_eq!(0, stats.total());
+    assert_eq!(0.0, stats.percent_no_bom());
+    assert_eq!(0, stats.counts().count());
+}
+
+#[test]
+fn test_bom_stats_counts() {
+    let stats: BomStats = vec![
+        BomStatus::Bom(BomType::UTF16LE),
+        BomStatus::Bom(BomType::UTF8),
+        BomStatus::Bom(BomType::UTF8),
+        BomStatus::NoBom,
+        BomStatus::Undetermined,
+    ]
+    .into_iter()
+    .collect();
+    assert_eq!(5, stats.total());
+    assert_eq!(3, stats.with_bom());
+    assert_eq!(1, stats.no_bom());
+    assert_eq!(1, stats.undetermined());
+    assert_eq!(2, stats.count(BomType::UTF8));
+    assert_eq!(0, stats.count(BomType::UTF32LE));
+    assert_eq!(vec![(BomType::UTF8, 2), (BomType::UTF16LE, 1)], stats.counts().collect::<Vec<_>>());
+    assert_eq!(20.0, stats.percent_no_bom());
+}
+
+#[test]
+fn test_bom_stats_readers() {
+    let streams: [&[u8]; 4] = [b"\xEF\xBB\xBFa", b"a", b"\xFE\xFF\x00a", b"\xEF\xBB"];
+    let mut stats = BomStats::default();
+    for bytes in streams.iter() {
+        let mut reader = SkipEncodingBom::new(BomType::all(), *bytes);
+        let _ = reader.read_to_end(&mut Vec::new()).unwrap();
+        stats.add(reader.status());
+    }
+    assert_eq!(1, stats.count(BomType::UTF8));
+    assert_eq!(1, stats.count(BomType::UTF16BE));
+    assert_eq!(1, stats.no_bom());
+    // truncated within a possible BOM
+    assert_eq!(1, stats.undetermined());
+}
+
+#[test]
+fn test_bom_stats_reports() {
+    let mut stats = BomStats::default();
+    for bytes in [&b"\xFF\xFEa\x00"[..], b"a"].iter() {
+        stats.add_report(&detect(*bytes, &DetectOptions::default()).unwrap());
+    }
+    assert_eq!(1, stats.count(BomType::UTF16LE));
+    assert_eq!(1, stats.no_bom());
+}
+
+#[test]
+fn test_bom_stats_merge() {
+    let mut stats: BomStats = vec![BomStatus::Bom(BomType::UTF8), BomStatus::NoBom].into_iter().collect();
+    let other: BomStats = vec![BomStatus::Bom(BomType::UTF8), BomStatus::Undetermined].into_iter().collect();
+    stats.merge(&other);
+    assert_eq!(2, stats.count(BomType::UTF8));
+    assert_eq!(1, stats.no_bom());
+    assert_eq!(1, stats.undetermined());
+    assert_eq!(4, stats.total());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_bom_stats_to_toml() {
+    let stats: BomStats = vec![BomStatus::Bom(BomType::UTF8), BomStatus::NoBom].into_iter().collect();
+    let serialized = toml::to_string(&stats).unwrap();
+    assert_eq!(stats, toml::from_str(&serialized).unwrap());
+}
@@ -0,0 +1,45 @@
+use skip_bom::*;
+use std::io::{Cursor, Read};
+
+fn test_status(bytes: &[u8], expected: BomStatus) {
+    let mut reader = SkipEncodingBom::new(BomType::all(), Cursor::new(bytes));
+    assert_eq!(BomStatus::Undetermined, reader.status());
+    let mut buf = Vec::new();
+    let _ = reader.read_to_end(&mut buf).unwrap();
+    assert_eq!(expected, reader.status());
+    assert_eq!(reader.bom_found(), Option::<Option<BomType>>::from(reader.status()));
+    assert_eq!(expected.is_determined(), reader.bom_found().is_some());
+    assert_eq!(expected.bom_type(), reader.bom_found().flatten());
+}
+
+macro_rules! test_status {
+    ($test_fn_name:ident, $bytes:expr, $expected:expr) => {
+        #[test]
+        fn $test_fn_name() {
+            test_status($bytes, $expected);
+        }
+    };
+}
+
+test_status!(test_status_utf8, b"\xEF\xBB\xBFabc", BomStatus::Bom(BomType::UTF8));
+test_status!(test_status_utf16le_only, b"\xFF\xFE", BomStatus::Bom(BomType::UTF16LE));
+test_status!(test_status_no_bom, b"abc", BomStatus::NoBom);
+test_status!(test_status_incomplete, b"\xEF\xBB", BomStatus::Undetermined);
+test_status!(test_status_empty, b"", BomStatus::Undetermined);
+
+#[test]
+fn test_status_conversions() {
+    assert_eq!(BomStatus::Undetermined, BomStatus::from(None));
+    assert_eq!(BomStatus::NoBom, BomStatus::from(Some(None)));
+    assert_eq!(BomStatus::Bom(BomType::SCSU), BomStatus::from(Some(Some(BomType::SCSU))));
+    assert_eq!(BomStatus::Undetermined, BomStatus::default());
+}
+
+#[test]
+fn test_status_decode_reader() {
+    let mut reader = DecodeReader::new(BomType::all(), &b"\xFE\xFF\x00a"[..]);
+    assert_eq!(BomStatus::Undetermined, reader.status());
+    let mut string = String::new();
+    let _ = reader.read_to_string(&mut string).unwrap();
+    assert_eq!(BomStatus::Bom(BomType::UTF16BE), reader.status());
+}
@@ -0,0 +1,62 @@
+use skip_bom::*;
+use std::convert::TryFrom;
+use std::io::ErrorKind;
+
+macro_rules! test_try_from_bytes {
+    ($test_fn_name:ident, $bytes:expr, $expected:expr) => {
+        #[test]
+        fn $test_fn_name() {
+            assert_eq!($expected, BomType::try_from(&$bytes[..]).ok());
+        }
+    };
+}
+
+test_try_from_bytes!(test_try_from_utf8, b"\xEF\xBB\xBF", Some(BomType::UTF8));
+test_try_from_bytes!(test_try_from_utf16le, b"\xFF\xFE", Some(BomType::UTF16LE));
+test_try_from_bytes!(test_try_from_utf32le, b"\xFF\xFE\x00\x00", Some(BomType::UTF32LE));
+test_try_from_bytes!(test_try_from_gb18030, b"\x84\x31\x95\x33", Some(BomType::GB1803));
+test_try_from_bytes!(test_try_from_content_after_bom, b"\xEF\xBB\xBFa", None);
+test_try_from_bytes!(test_try_from_incomplete, b"\xEF\xBB", None);
+test_try_from_bytes!(test_try_from_empty, b"", None);
+
+#[test]
+fn test_try_from_all() {
+    for bom_type in BomType::all() {
+        assert_eq!(*bom_type, BomType::try_from(bom_type.bom_bytes()).unwrap());
+    }
+}
+
+#[test]
+fn test_try_from_error() {
+    let error = BomType::try_from(&b"text"[..]).unwrap_err();
+    assert_eq!(ErrorKind::InvalidData, error.kind());
+    assert_eq!(Some(BomErrorCode::NotBom), BomErrorCode::of(&error));
+}
+
+macro_rules! test_strip_prefix {
+    ($test_fn_name:ident, $bom_type:expr, $bytes:expr, $expected:expr) => {
+        #[test]
+        fn $test_fn_name() {
+            assert_eq!($expected.map(|bytes: &[u8]| bytes), $bom_type.strip_prefix($bytes));
+        }
+    };
+}
+
+test_strip_prefix!(test_strip_prefix_utf8, BomType::UTF8, b"\xEF\xBB\xBFtext", Some(&b"text"[..]));
+test_strip_prefix!(test_strip_prefix_bom_only, BomType::UTF8, b"\xEF\xBB\xBF", Some(&b""[..]));
+test_strip_prefix!(test_strip_prefix_other_bom, BomType::UTF16BE, b"\xFF\xFEt\x00", None);
+test_strip_prefix!(test_strip_prefix_no_bom, BomType::UTF8, b"text", None);
+test_strip_prefix!(test_strip_prefix_incomplete, BomType::UTF8, b"\xEF\xBB", None);
+test_strip_prefix!(test_strip_prefix_longer_bom, BomType::UTF16LE, b"\xFF\xFE\x00\x00", Some(&b"\x00\x00"[..]));
+
+#[test]
+fn test_signatures_table() {
+    let bom_types: Vec<BomType> = BomType::SIGNATURES.iter().map(|(bom_type, _)| *bom_type).collect();
+    let mut all = BomType::all().to_vec();
+    all.sort();
+    assert_eq!(all, bom_types);
+    for (bom_type, bytes) in BomType::SIGNATURES {
+        assert_eq!(bom_type.bom_bytes(), *bytes);
+        assert_eq!(*bom_type, BomType::try_from(*bytes).unwrap());
+    }
+}
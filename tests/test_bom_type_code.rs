@@ -0,0 +1,37 @@
+use skip_bom::*;
+
+macro_rules! test_code {
+    ($test_fn_name:ident, $bom_type:expr, $expected_code:expr) => {
+        #[test]
+        fn $test_fn_name() {
+            assert_eq!($expected_code, $bom_type.as_u8());
+            assert_eq!(Some($bom_type), BomType::from_u8($expected_code));
+        }
+    };
+}
+
+// the codes are stable: they must never change
+test_code!(test_code_utf8, BomType::UTF8, 0);
+test_code!(test_code_utf16le, BomType::UTF16LE, 1);
+test_code!(test_code_utf16be, BomType::UTF16BE, 2);
+test_code!(test_code_utf32le, BomType::UTF32LE, 3);
+test_code!(test_code_utf32be, BomType::UTF32BE, 4);
+test_code!(test_code_utf7, BomType::UTF7, 5);
+test_code!(test_code_utf1, BomType::UTF1, 6);
+test_code!(test_code_utfebdic, BomType::UTFEBDIC, 7);
+test_code!(test_code_scsu, BomType::SCSU, 8);
+test_code!(test_code_bocu1, BomType::BOCU1, 9);
+test_code!(test_code_gb18030, BomType::GB1803, 10);
+
+#[test]
+fn test_code_round_trip() {
+    for bom_type in BomType::all() {
+        assert_eq!(Some(*bom_type), BomType::from_u8(bom_type.as_u8()));
+    }
+}
+
+#[test]
+fn test_code_unknown() {
+    assert_eq!(None, BomType::from_u8(11));
+    assert_eq!(None, BomType::from_u8(u8::MAX));
+}
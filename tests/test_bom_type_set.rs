@@ -0,0 +1,44 @@
+use skip_bom::*;
+use std::io::Read;
+
+fn test_bom_type_set(set: BomTypeSet, bom_type: BomType, found: bool) {
+    let mut bytes = bom_type.bom_bytes().to_vec();
+    bytes.extend(b"abcd");
+    let mut reader = SkipEncodingBom::new(&set, bytes.as_slice());
+    let mut buf = Vec::new();
+    let _ = reader.read_to_end(&mut buf).unwrap();
+    assert_eq!(found, set.contains(&bom_type));
+    if found {
+        assert_eq!(Some(Some(bom_type)), reader.bom_found());
+        assert_eq!(b"abcd", buf.as_slice());
+    } else {
+        assert_ne!(Some(Some(bom_type)), reader.bom_found());
+    }
+}
+
+macro_rules! test_bom_type_set {
+    ($test_fn_name:ident, $set:expr, $bom_type:expr, $found:expr) => {
+        #[test]
+        fn $test_fn_name() {
+            test_bom_type_set($set, $bom_type, $found);
+        }
+    };
+}
+
+test_bom_type_set!(test_windows_text_utf8, BomTypeSet::WINDOWS_TEXT, BomType::UTF8, true);
+test_bom_type_set!(test_windows_text_utf16le, BomTypeSet::WINDOWS_TEXT, BomType::UTF16LE, true);
+test_bom_type_set!(test_windows_text_utf16be, BomTypeSet::WINDOWS_TEXT, BomType::UTF16BE, false);
+test_bom_type_set!(test_windows_text_utf32le, BomTypeSet::WINDOWS_TEXT, BomType::UTF32LE, false);
+test_bom_type_set!(test_web_utf16be, BomTypeSet::WEB, BomType::UTF16BE, true);
+test_bom_type_set!(test_web_utf32le, BomTypeSet::WEB, BomType::UTF32LE, true);
+test_bom_type_set!(test_web_utf32be, BomTypeSet::WEB, BomType::UTF32BE, true);
+test_bom_type_set!(test_web_gb18030, BomTypeSet::WEB, BomType::GB1803, false);
+test_bom_type_set!(test_strict_utf8_utf8, BomTypeSet::STRICT_UTF8, BomType::UTF8, true);
+test_bom_type_set!(test_strict_utf8_utf16le, BomTypeSet::STRICT_UTF8, BomType::UTF16LE, false);
+test_bom_type_set!(test_all_scsu, BomTypeSet::ALL, BomType::SCSU, true);
+
+#[test]
+fn test_all_matches_bom_type_all() {
+    assert_eq!(BomType::all(), BomTypeSet::ALL.bom_types());
+    assert_eq!(BomType::all(), &*BomTypeSet::ALL);
+}
@@ -1,3 +1,5 @@
+#![allow(clippy::redundant_static_lifetimes)]
+
 use skip_bom::*;
 use std::io::{Cursor, Read};
 
@@ -75,3 +77,24 @@ test_read_bom_types_parameter!(test_read_utf32be_before_utf16le, BomType::UTF32B
 test_read_bom_types_parameter!(test_read_utf32be_after_utf16le, BomType::UTF32BE, &[BomType::UTF16LE, BomType::UTF32BE], true);
 test_read_bom_types_parameter!(test_read_utf16le_before_utf32be, BomType::UTF16LE, &[BomType::UTF16LE, BomType::UTF32BE], true);
 test_read_bom_types_parameter!(test_read_utf16le_after_utf32be, BomType::UTF16LE, &[BomType::UTF32BE, BomType::UTF16LE], true);
+
+// test that UTF-16 Little Endian and UTF-32 Little Endian are not confused
+test_read_bom_types_parameter!(test_read_utf32le_before_utf16le, BomType::UTF32LE, &[BomType::UTF32LE, BomType::UTF16LE], true);
+test_read_bom_types_parameter!(test_read_utf32le_after_utf16le, BomType::UTF32LE, &[BomType::UTF16LE, BomType::UTF32LE], true);
+test_read_bom_types_parameter!(test_read_utf16le_before_utf32le, BomType::UTF16LE, &[BomType::UTF16LE, BomType::UTF32LE], true);
+test_read_bom_types_parameter!(test_read_utf16le_after_utf32le, BomType::UTF16LE, &[BomType::UTF32LE, BomType::UTF16LE], true);
+
+#[test]
+fn test_utf32_bom_bytes() {
+    assert_eq!(&[0xFF, 0xFE, 0x00, 0x00], BomType::UTF32LE.bom_bytes());
+    assert_eq!(&[0x00, 0x00, 0xFE, 0xFF], BomType::UTF32BE.bom_bytes());
+}
+
+#[test]
+fn test_find_utf16le_bom_in_utf32le_bom() {
+    let bom_types = &[BomType::UTF16LE, BomType::UTF32LE];
+    assert_eq!(BomsBytesTest::Incomplete, BomType::try_find_bytes_bom(b"\xFF\xFE", bom_types));
+    assert_eq!(BomsBytesTest::Incomplete, BomType::try_find_bytes_bom(b"\xFF\xFE\x00", bom_types));
+    assert_eq!(BomsBytesTest::Complete { bom_type: Some(BomType::UTF16LE), additional_bytes: b"a\x00" }, BomType::try_find_bytes_bom(b"\xFF\xFEa\x00", bom_types));
+    assert_eq!(Some(BomType::UTF16LE), BomType::find_bytes_bom_at_end(b"\xFF\xFE\x00", bom_types));
+}
@@ -0,0 +1,66 @@
+use skip_bom::*;
+use std::io::{BufRead, BufReader, Read};
+
+fn read_buf(bytes: &[u8], capacity: usize) -> (Vec<u8>, Option<Option<BomType>>) {
+    let mut reader = SkipEncodingBomBuf::new(BomType::all(), BufReader::with_capacity(capacity, bytes));
+    let mut content = Vec::new();
+    let _ = reader.read_to_end(&mut content).unwrap();
+    (content, reader.bom_found())
+}
+
+macro_rules! test_buf_read {
+    ($test_fn_name:ident, $bytes:expr, $expected_content:expr, $expected_bom:expr) => {
+        #[test]
+        fn $test_fn_name() {
+            for capacity in 1..=9 {
+                assert_eq!(($expected_content.to_vec(), Some($expected_bom)), read_buf($bytes, capacity), "capacity {}", capacity);
+            }
+        }
+    };
+}
+
+test_buf_read!(test_buf_read_utf8, b"\xEF\xBB\xBFtext", b"text", Some(BomType::UTF8));
+test_buf_read!(test_buf_read_utf32le, b"\xFF\xFE\x00\x00t\x00\x00\x00", b"t\x00\x00\x00", Some(BomType::UTF32LE));
+test_buf_read!(test_buf_read_utf16le, b"\xFF\xFEt\x00", b"t\x00", Some(BomType::UTF16LE));
+test_buf_read!(test_buf_read_utf16le_at_end, b"\xFF\xFE", b"", Some(BomType::UTF16LE));
+test_buf_read!(test_buf_read_utf16le_null_at_end, b"\xFF\xFE\x00", b"\x00", Some(BomType::UTF16LE));
+test_buf_read!(test_buf_read_no_bom, b"text", b"text", None);
+test_buf_read!(test_buf_read_partial_bom_at_end, b"\xEF\xBB", b"\xEF\xBB", None);
+test_buf_read!(test_buf_read_empty, b"", b"", None);
+
+#[test]
+fn test_buf_read_zero_copy() {
+    let bytes: &[u8] = b"\xEF\xBB\xBFfirst line\nsecond line\n";
+    let mut reader = SkipEncodingBomBuf::new(BomType::all(), bytes);
+    assert_eq!(Some(BomType::UTF8), reader.read_bom().unwrap());
+    // the content is read straight from the underlying buffer
+    assert_eq!(bytes[3..].as_ptr(), reader.fill_buf().unwrap().as_ptr());
+}
+
+#[test]
+fn test_buf_read_already_buffered() {
+    let mut buf_reader = BufReader::new(&b"\xFE\xFF\x00a\x00b"[..]);
+    let _ = buf_reader.fill_buf().unwrap();
+    let mut reader = SkipEncodingBomBuf::new(BomType::all(), buf_reader);
+    assert_eq!(BomStatus::Undetermined, reader.status());
+    assert_eq!(b"\x00a\x00b", reader.fill_buf().unwrap());
+    assert_eq!(BomStatus::Bom(BomType::UTF16BE), reader.status());
+}
+
+#[test]
+fn test_buf_read_lines() {
+    let reader = SkipEncodingBomBuf::new(BomType::all(), BufReader::with_capacity(2, &b"\xEF\xBB\xBFa\nb\n"[..]));
+    let lines: Vec<String> = reader.lines().map(Result::unwrap).collect();
+    assert_eq!(vec!["a".to_string(), "b".to_string()], lines);
+}
+
+#[test]
+fn test_buf_read_consume_pending() {
+    let mut reader = SkipEncodingBomBuf::new(BomType::all(), BufReader::with_capacity(1, &b"\xFF\xFEab"[..]));
+    // the BOM presence is determined by the third byte, only this byte is buffered
+    assert_eq!(b"a", reader.fill_buf().unwrap());
+    reader.consume(1);
+    assert_eq!(b"b", reader.fill_buf().unwrap());
+    reader.consume(1);
+    assert_eq!(b"", reader.fill_buf().unwrap());
+}
@@ -0,0 +1,61 @@
+use skip_bom::*;
+use std::io::ErrorKind;
+
+#[test]
+fn test_push_buffer_capacity() {
+    let buffer = BomBytesPushBuffer::default();
+    assert_eq!(BomBytesPushBuffer::CAPACITY, buffer.capacity());
+    assert!(BomType::all().iter().all(|bom_type| bom_type.bom_length() <= buffer.capacity()));
+    assert_eq!(buffer.capacity(), buffer.available_bytes());
+    assert!(buffer.is_empty());
+    assert!(!buffer.is_full());
+}
+
+#[test]
+fn test_push_buffer_push() {
+    let mut buffer = BomBytesPushBuffer::default();
+    buffer.push(b"\xFF").unwrap();
+    buffer.push(b"").unwrap();
+    buffer.push(b"\xFE\x00").unwrap();
+    assert_eq!(b"\xFF\xFE\x00", buffer.bytes());
+    assert_eq!(3, buffer.byte_count());
+    assert_eq!(BomBytesPushBuffer::CAPACITY - 3, buffer.available_bytes());
+    assert!(!buffer.is_empty());
+}
+
+#[test]
+fn test_push_buffer_push_overflow() {
+    let mut buffer = BomBytesPushBuffer::from_slice(b"\x00\x00").unwrap();
+    let overflow = vec![0xFF; buffer.available_bytes() + 1];
+    assert_eq!(ErrorKind::InvalidInput, buffer.push(&overflow).unwrap_err().kind());
+    // the buffer is unchanged
+    assert_eq!(b"\x00\x00", buffer.bytes());
+    buffer.push(&overflow[1..]).unwrap();
+    assert!(buffer.is_full());
+    assert_eq!(ErrorKind::InvalidInput, buffer.push(b"\x00").unwrap_err().kind());
+}
+
+#[test]
+fn test_push_buffer_push_truncated() {
+    let mut buffer = BomBytesPushBuffer::default();
+    let bytes = vec![0xAB; BomBytesPushBuffer::CAPACITY + 2];
+    assert_eq!(BomBytesPushBuffer::CAPACITY, buffer.push_truncated(&bytes));
+    assert!(buffer.is_full());
+    assert_eq!(0, buffer.push_truncated(&bytes));
+    assert_eq!(&bytes[..BomBytesPushBuffer::CAPACITY], buffer.bytes());
+}
+
+#[test]
+fn test_push_buffer_from_slice_overflow() {
+    let bytes = vec![0; BomBytesPushBuffer::CAPACITY + 1];
+    assert_eq!(ErrorKind::InvalidInput, BomBytesPushBuffer::from_slice(&bytes).unwrap_err().kind());
+    assert!(BomBytesPushBuffer::from_slice(&bytes[1..]).unwrap().is_full());
+}
+
+#[test]
+fn test_push_buffer_from_array() {
+    let array = BomBytesArray::default();
+    assert_eq!(2, BomBytesPushBuffer::from_array(array, 2).unwrap().byte_count());
+    assert!(BomBytesPushBuffer::from_array(array, array.len()).unwrap().is_full());
+    assert_eq!(ErrorKind::InvalidInput, BomBytesPushBuffer::from_array(array, array.len() + 1).unwrap_err().kind());
+}
@@ -0,0 +1,30 @@
+#![cfg(feature = "bytes")]
+
+use bytes::Bytes;
+use skip_bom::*;
+
+macro_rules! test_clean_message {
+    ($test_fn_name:ident, $payload:expr, $expected_bom:expr, $expected:expr) => {
+        #[test]
+        fn $test_fn_name() {
+            let (bom, payload) = clean_message(Bytes::from_static($payload));
+            assert_eq!($expected_bom, bom);
+            assert_eq!(&$expected[..], payload);
+        }
+    };
+}
+
+test_clean_message!(test_clean_message_utf8_bom, b"\xEF\xBB\xBF{}", Some(BomType::UTF8), b"{}");
+test_clean_message!(test_clean_message_utf32le_bom, b"\xFF\xFE\x00\x00{\x00\x00\x00", Some(BomType::UTF32LE), b"{\x00\x00\x00");
+test_clean_message!(test_clean_message_utf16le_bom_only, b"\xFF\xFE", Some(BomType::UTF16LE), b"");
+test_clean_message!(test_clean_message_no_bom, b"{}", None, b"{}");
+test_clean_message!(test_clean_message_incomplete_bom, b"\xEF\xBB", None, b"\xEF\xBB");
+test_clean_message!(test_clean_message_empty, b"", None, b"");
+
+#[test]
+fn test_clean_message_no_copy() {
+    let payload = Bytes::from(b"\xEF\xBB\xBFpayload".to_vec());
+    let start = payload.as_ptr();
+    let (_, cleaned) = clean_message(payload);
+    assert_eq!(start.wrapping_add(3), cleaned.as_ptr());
+}
@@ -0,0 +1,32 @@
+use skip_bom::*;
+use std::io::{Cursor, Read};
+
+fn test_reconcile(bytes: &[u8], declared: &str, expected: CharsetReconciliation) {
+    let mut reader = SkipEncodingBom::new(BomType::all(), Cursor::new(bytes));
+    let mut buf = Default::default();
+    let _ = reader.read_to_end(&mut buf).unwrap();
+    assert_eq!(expected, reader.reconcile_with_charset(declared));
+}
+
+macro_rules! test_reconcile {
+    ($test_fn_name:ident, $bytes:expr, $declared:expr, $expected:expr) => {
+        #[test]
+        fn $test_fn_name() {
+            test_reconcile($bytes, $declared, $expected);
+        }
+    };
+}
+
+test_reconcile!(test_utf8_label, b"\xEF\xBB\xBFabc", "utf-8", CharsetReconciliation::Agree(BomType::UTF8));
+test_reconcile!(test_utf8_label_case, b"\xEF\xBB\xBFabc", "UTF8", CharsetReconciliation::Agree(BomType::UTF8));
+test_reconcile!(test_utf8_content_type, b"\xEF\xBB\xBFabc", "text/csv; charset=utf-8", CharsetReconciliation::Agree(BomType::UTF8));
+test_reconcile!(test_utf8_content_type_quoted, b"\xEF\xBB\xBFabc", "text/csv; header=present; charset=\"UTF-8\"", CharsetReconciliation::Agree(BomType::UTF8));
+test_reconcile!(test_utf16le_generic_label, b"\xFF\xFEa\x00", "utf-16", CharsetReconciliation::Agree(BomType::UTF16LE));
+test_reconcile!(test_utf16be_generic_label, b"\xFE\xFF\x00a", "utf-16", CharsetReconciliation::Agree(BomType::UTF16BE));
+test_reconcile!(test_utf16be_declared_le, b"\xFE\xFF\x00a", "utf-16le", CharsetReconciliation::Conflict(BomType::UTF16BE));
+test_reconcile!(test_utf8_declared_latin1, b"\xEF\xBB\xBFabc", "text/html; charset=ISO-8859-1", CharsetReconciliation::Conflict(BomType::UTF8));
+test_reconcile!(test_gb18030_label, b"\x84\x31\x95\x33abc", "GB18030", CharsetReconciliation::Agree(BomType::GB1803));
+test_reconcile!(test_unknown_label, b"\xEF\xBB\xBFabc", "x-unknown", CharsetReconciliation::Unknown);
+test_reconcile!(test_no_charset_parameter, b"\xEF\xBB\xBFabc", "text/plain", CharsetReconciliation::Unknown);
+test_reconcile!(test_no_bom, b"abc", "utf-8", CharsetReconciliation::Unknown);
+test_reconcile!(test_undetermined_bom, b"\xEF\xBB", "utf-8", CharsetReconciliation::Unknown);
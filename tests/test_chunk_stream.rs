@@ -0,0 +1,80 @@
+#![cfg(feature = "futures-core")]
+
+use futures_core::Stream;
+use skip_bom::{BomType, SkipBomChunks};
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// Stream of chunks returning [`Poll::Pending`] before each item.
+struct PendingChunks {
+    items: Vec<Result<Vec<u8>, &'static str>>,
+    ready: bool,
+}
+
+impl Stream for PendingChunks {
+    type Item = Result<Vec<u8>, &'static str>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if !self.ready {
+            self.ready = true;
+            cx.waker().wake_by_ref();
+            return Poll::Pending;
+        }
+        self.ready = false;
+        Poll::Ready(if self.items.is_empty() { None } else { Some(self.items.remove(0)) })
+    }
+}
+
+/// Future of the next item of a stream.
+struct Next<'s, S>(&'s mut S);
+
+impl<S: Stream + Unpin> Future for Next<'_, S> {
+    type Output = Option<S::Item>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut *self.0).poll_next(cx)
+    }
+}
+
+async fn collect(chunks: &[&[u8]]) -> (Vec<Vec<u8>>, Option<Option<BomType>>) {
+    let items = chunks.iter().map(|chunk| Ok(chunk.to_vec())).collect();
+    let mut stream = SkipBomChunks::new(BomType::all(), PendingChunks { items, ready: false });
+    let mut collected = Vec::new();
+    while let Some(chunk) = Next(&mut stream).await {
+        collected.push(chunk.unwrap());
+    }
+    (collected, stream.bom_found())
+}
+
+macro_rules! test_chunk_stream {
+    ($name:ident, [$($chunk:expr),*], [$($content:expr),*], $bom:expr) => {
+        #[tokio::test]
+        async fn $name() {
+            let (collected, bom_found) = collect(&[$(&$chunk[..]),*]).await;
+            let expected: Vec<Vec<u8>> = vec![$($content.to_vec()),*];
+            assert_eq!(expected, collected);
+            assert_eq!(Some($bom), bom_found);
+        }
+    };
+}
+
+test_chunk_stream!(test_chunk_stream_utf8, [b"\xEF\xBB\xBFfirst", b" second"], [b"first", b" second"], Some(BomType::UTF8));
+test_chunk_stream!(test_chunk_stream_split_bom, [b"\xEF", b"", b"\xBB", b"\xBFfirst", b" second"], [b"first", b" second"], Some(BomType::UTF8));
+test_chunk_stream!(test_chunk_stream_bom_chunk, [b"\xFF\xFE\x00\x00", b"t\x00\x00\x00"], [b"t\x00\x00\x00"], Some(BomType::UTF32LE));
+test_chunk_stream!(test_chunk_stream_utf16le_split, [b"\xFF\xFE", b"\x00t"], [b"\x00t"], Some(BomType::UTF16LE));
+test_chunk_stream!(test_chunk_stream_utf16le_at_end, [b"\xFF\xFE"], [], Some(BomType::UTF16LE));
+test_chunk_stream!(test_chunk_stream_no_bom, [b"first", b"", b" second"], [b"first", b"", b" second"], None);
+test_chunk_stream!(test_chunk_stream_incomplete, [b"\xEF", b"\xBB"], [b"\xEF\xBB"], None);
+test_chunk_stream!(test_chunk_stream_empty, [], [], None);
+
+#[tokio::test]
+async fn test_chunk_stream_error() {
+    let items = vec![Ok(b"\xEF\xBB".to_vec()), Err("interrupted"), Ok(b"\xBFcontent".to_vec())];
+    let mut stream = SkipBomChunks::new(BomType::all(), PendingChunks { items, ready: false });
+    assert_eq!(Some(Err("interrupted")), Next(&mut stream).await);
+    assert_eq!(None, stream.bom_found());
+    assert_eq!(Some(Ok(b"content".to_vec())), Next(&mut stream).await);
+    assert_eq!(Some(Some(BomType::UTF8)), stream.bom_found());
+    assert_eq!(None, Next(&mut stream).await);
+}
@@ -0,0 +1,57 @@
+use skip_bom::*;
+use std::io::{Cursor, Read};
+
+fn read_to_end<R: Read>(reader: &mut SkipEncodingBom<R>) -> Vec<u8> {
+    let mut buf = Vec::new();
+    let _ = reader.read_to_end(&mut buf).unwrap();
+    buf
+}
+
+#[test]
+fn test_clone_initial_state() {
+    let reader = SkipEncodingBom::new(BomType::all(), Cursor::new(b"\xEF\xBB\xBFThis stream has a BOM.".as_slice()));
+    let mut clone = reader.clone();
+    let mut reader = reader;
+    assert_eq!(b"This stream has a BOM.", read_to_end(&mut reader).as_slice());
+    assert_eq!(b"This stream has a BOM.", read_to_end(&mut clone).as_slice());
+    assert_eq!(Some(Some(BomType::UTF8)), clone.bom_found());
+}
+
+#[test]
+fn test_clone_incomplete_bom() {
+    let mut reader = SkipEncodingBom::new(BomType::all(), Cursor::new(b"\xEF\xBB".to_vec()));
+    assert_eq!(0, read_to_end(&mut reader).len());
+    assert_eq!(None, reader.bom_found());
+    let mut clone = reader.clone();
+    // each clone receives different bytes and completes detection independently
+    reader.get_mut().get_mut().extend_from_slice(b"\xBFThis stream has a BOM.");
+    clone.get_mut().get_mut().extend_from_slice(b"This stream has no BOM.");
+    assert_eq!(b"This stream has a BOM.", read_to_end(&mut reader).as_slice());
+    assert_eq!(Some(Some(BomType::UTF8)), reader.bom_found());
+    assert_eq!(b"\xEF\xBBThis stream has no BOM.", read_to_end(&mut clone).as_slice());
+    assert_eq!(Some(None), clone.bom_found());
+}
+
+#[test]
+fn test_clone_post_init_buffer() {
+    let mut reader = SkipEncodingBom::new(BomType::all(), Cursor::new(b"\xEF\xBBThis stream has no BOM.".as_slice()));
+    let mut small_buf = [0u8; 1];
+    assert_eq!(1, reader.read(&mut small_buf).unwrap());
+    assert_eq!(b"\xEF", &small_buf);
+    let mut clone = reader.clone();
+    assert_eq!(b"\xBBThis stream has no BOM.", read_to_end(&mut reader).as_slice());
+    assert_eq!(b"\xBBThis stream has no BOM.", read_to_end(&mut clone).as_slice());
+    assert_eq!(reader.bom_found(), clone.bom_found());
+}
+
+#[test]
+fn test_clone_final_state() {
+    let mut reader = SkipEncodingBom::new(BomType::all(), Cursor::new(b"\xFE\xFFThis stream has a BOM.".as_slice()));
+    assert_eq!(Some(BomType::UTF16BE), reader.read_bom().unwrap());
+    let mut small_buf = [0u8; 4];
+    reader.read_exact(&mut small_buf).unwrap();
+    let mut clone = reader.clone();
+    assert_eq!(b" stream has a BOM.", read_to_end(&mut clone).as_slice());
+    assert_eq!(b" stream has a BOM.", read_to_end(&mut reader).as_slice());
+    assert_eq!(Some(Some(BomType::UTF16BE)), clone.bom_found());
+}
@@ -0,0 +1,58 @@
+#![cfg(any(feature = "flate2", feature = "zstd"))]
+
+use skip_bom::*;
+use std::io::Read;
+
+#[cfg(feature = "flate2")]
+fn gzip(bytes: &[u8]) -> Vec<u8> {
+    use std::io::Write;
+
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(bytes).unwrap();
+    encoder.finish().unwrap()
+}
+
+#[cfg(feature = "flate2")]
+#[test]
+fn test_gz_bom() {
+    let compressed = gzip(b"\xEF\xBB\xBFThis stream has a BOM.");
+    let mut reader = SkipEncodingBom::new_gz(BomType::all(), compressed.as_slice());
+    let mut string = Default::default();
+    let _ = reader.read_to_string(&mut string).unwrap();
+    assert_eq!(Some(Some(BomType::UTF8)), reader.bom_found());
+    assert_eq!("This stream has a BOM.", &string);
+}
+
+#[cfg(feature = "flate2")]
+#[test]
+fn test_gz_multiple_members() {
+    let mut compressed = gzip(b"\xEF\xBB\xBFFirst member, ");
+    compressed.extend(gzip(b"second member."));
+    let mut reader = SkipEncodingBom::new_gz(BomType::all(), compressed.as_slice());
+    let mut string = Default::default();
+    let _ = reader.read_to_string(&mut string).unwrap();
+    assert_eq!(Some(Some(BomType::UTF8)), reader.bom_found());
+    assert_eq!("First member, second member.", &string);
+}
+
+#[cfg(feature = "zstd")]
+#[test]
+fn test_zstd_bom() {
+    let compressed = zstd::stream::encode_all(&b"\xFF\xFEThis stream has a BOM."[..], 0).unwrap();
+    let mut reader = SkipEncodingBom::new_zstd(BomType::all(), compressed.as_slice()).unwrap();
+    let mut buf = Vec::new();
+    let _ = reader.read_to_end(&mut buf).unwrap();
+    assert_eq!(Some(Some(BomType::UTF16LE)), reader.bom_found());
+    assert_eq!(b"This stream has a BOM.", buf.as_slice());
+}
+
+#[cfg(feature = "zstd")]
+#[test]
+fn test_zstd_no_bom() {
+    let compressed = zstd::stream::encode_all(&b"This stream has no BOM."[..], 0).unwrap();
+    let mut reader = SkipEncodingBom::new_zstd(BomType::all(), compressed.as_slice()).unwrap();
+    let mut buf = Vec::new();
+    let _ = reader.read_to_end(&mut buf).unwrap();
+    assert_eq!(Some(None), reader.bom_found());
+    assert_eq!(b"This stream has no BOM.", buf.as_slice());
+}
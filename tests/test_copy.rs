@@ -0,0 +1,97 @@
+use skip_bom::*;
+use std::io::{ErrorKind, Read, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+fn test_copy(bytes: &[u8], expected: &[u8], expected_bom: Option<BomType>) {
+    let mut output = Vec::new();
+    let copied = copy_without_bom(BomType::all(), bytes, &mut output, &CopyOptions::default()).unwrap();
+    assert_eq!((expected.len() as u64, expected_bom), copied);
+    assert_eq!(expected, output.as_slice());
+}
+
+macro_rules! test_copy {
+    ($test_fn_name:ident, $bytes:expr, $expected:expr, $expected_bom:expr) => {
+        #[test]
+        fn $test_fn_name() {
+            test_copy($bytes, $expected, $expected_bom);
+        }
+    };
+}
+
+test_copy!(test_copy_utf8_bom, b"\xEF\xBB\xBFtext", b"text", Some(BomType::UTF8));
+test_copy!(test_copy_utf16le_bom, b"\xFF\xFEt\x00", b"t\x00", Some(BomType::UTF16LE));
+test_copy!(test_copy_no_bom, b"text", b"text", None);
+test_copy!(test_copy_bom_only, b"\xEF\xBB\xBF", b"", Some(BomType::UTF8));
+test_copy!(test_copy_empty, b"", b"", None);
+
+#[test]
+fn test_copy_large() {
+    let content: Vec<u8> = (0..200_000).map(|i| (i % 251) as u8).collect();
+    let mut bytes = BomType::UTF8.bom_bytes().to_vec();
+    bytes.extend(&content);
+    test_copy(&bytes, &content, Some(BomType::UTF8));
+}
+
+#[test]
+fn test_copy_cancelled_before() {
+    let cancel = AtomicBool::new(true);
+    let mut output = Vec::new();
+    let error = copy_without_bom(BomType::all(), &b"\xEF\xBB\xBFtext"[..], &mut output, &CopyOptions { cancel: Some(&cancel), ..Default::default() }).unwrap_err();
+    assert_eq!(ErrorKind::Other, error.kind());
+    assert_eq!(Some(BomErrorCode::Cancelled), BomErrorCode::of(&error));
+    assert!(output.is_empty());
+}
+
+/// Writer setting a cancellation flag once written to.
+struct CancellingWriter<'a>(Vec<u8>, &'a AtomicBool);
+
+impl Write for CancellingWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.1.store(true, Ordering::Relaxed);
+        self.0.write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn test_copy_cancelled_between_chunks() {
+    let cancel = AtomicBool::new(false);
+    let mut writer = CancellingWriter(Vec::new(), &cancel);
+    let input = std::io::repeat(b'a').take(1 << 20);
+    let error = copy_without_bom(BomType::all(), input, &mut writer, &CopyOptions { cancel: Some(&cancel), ..Default::default() }).unwrap_err();
+    assert_eq!(Some(BomErrorCode::Cancelled), BomErrorCode::of(&error));
+    assert!(!writer.0.is_empty());
+    assert!(writer.0.len() < 1 << 20);
+}
+
+#[test]
+fn test_copy_unsized_writer() {
+    let mut output = Vec::new();
+    let writer: &mut dyn Write = &mut output;
+    let _ = copy_without_bom(BomType::all(), &b"\xEF\xBB\xBFtext"[..], writer, &CopyOptions::default()).unwrap();
+    assert_eq!(b"text", output.as_slice());
+}
+
+#[test]
+fn test_copy_max_bytes_per_second() {
+    let options = CopyOptions { max_bytes_per_second: std::num::NonZeroU64::new(100_000), ..Default::default() };
+    let start = std::time::Instant::now();
+    let copied = copy_without_bom(BomType::all(), std::io::repeat(b'a').take(30_000), &mut std::io::sink(), &options).unwrap();
+    assert_eq!((30_000, None), copied);
+    // the first chunk is not delayed
+    assert!(start.elapsed() >= std::time::Duration::from_millis(200), "{:?}", start.elapsed());
+}
+
+#[test]
+fn test_copy_max_bytes_per_second_small() {
+    // a limit below the chunk length splits the chunks
+    let options = CopyOptions { max_bytes_per_second: std::num::NonZeroU64::new(1_000), ..Default::default() };
+    let start = std::time::Instant::now();
+    let mut output = Vec::new();
+    let _ = copy_without_bom(BomType::all(), &[b'a'; 1_500][..], &mut output, &options).unwrap();
+    assert_eq!(1_500, output.len());
+    assert!(start.elapsed() >= std::time::Duration::from_millis(400), "{:?}", start.elapsed());
+}
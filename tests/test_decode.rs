@@ -0,0 +1,134 @@
+use skip_bom::*;
+use std::io::{ErrorKind, Read};
+
+mod reader_test_utils;
+use reader_test_utils::*;
+
+fn encode_utf16(text: &str, big_endian: bool) -> Vec<u8> {
+    text.encode_utf16().flat_map(|unit| if big_endian { unit.to_be_bytes() } else { unit.to_le_bytes() }).collect()
+}
+
+fn encode_utf32(text: &str, big_endian: bool) -> Vec<u8> {
+    text.chars().flat_map(|c| if big_endian { (c as u32).to_be_bytes() } else { (c as u32).to_le_bytes() }).collect()
+}
+
+fn with_bom(bom_type: BomType, content: &[u8]) -> Vec<u8> {
+    let mut bytes = bom_type.bom_bytes().to_vec();
+    bytes.extend(content);
+    bytes
+}
+
+fn decode(bytes: &[u8]) -> String {
+    let mut string = String::new();
+    let _ = DecodeReader::new(BomType::all(), bytes).read_to_string(&mut string).unwrap();
+    let mut byte_by_byte_string = String::new();
+    let _ = DecodeReader::new(BomType::all(), ByteByByteReader(bytes)).read_to_string(&mut byte_by_byte_string).unwrap();
+    assert_eq!(string, byte_by_byte_string);
+    string
+}
+
+const TEXT: &str = "Plain ASCII, accents: éàü, CJK: 漢字, emoji: 🦀🎉.";
+
+#[test]
+fn test_decode_no_bom() {
+    assert_eq!(TEXT, decode(TEXT.as_bytes()));
+}
+
+#[test]
+fn test_decode_utf8() {
+    assert_eq!(TEXT, decode(&with_bom(BomType::UTF8, TEXT.as_bytes())));
+}
+
+#[test]
+fn test_decode_utf16le() {
+    assert_eq!(TEXT, decode(&with_bom(BomType::UTF16LE, &encode_utf16(TEXT, false))));
+}
+
+#[test]
+fn test_decode_utf16be() {
+    assert_eq!(TEXT, decode(&with_bom(BomType::UTF16BE, &encode_utf16(TEXT, true))));
+}
+
+#[test]
+fn test_decode_utf32le() {
+    assert_eq!(TEXT, decode(&with_bom(BomType::UTF32LE, &encode_utf32(TEXT, false))));
+}
+
+#[test]
+fn test_decode_utf32be() {
+    assert_eq!(TEXT, decode(&with_bom(BomType::UTF32BE, &encode_utf32(TEXT, true))));
+}
+
+#[test]
+fn test_decode_long_utf16() {
+    let text = TEXT.repeat(100);
+    assert_eq!(text, decode(&with_bom(BomType::UTF16LE, &encode_utf16(&text, false))));
+}
+
+#[test]
+fn test_decode_utf16_invalid() {
+    // unpaired low surrogate, unpaired high surrogate, truncated unit
+    assert_eq!("a\u{FFFD}b\u{FFFD}c\u{FFFD}", decode(b"\xFF\xFEa\x00\x00\xDCb\x00\x00\xD8c\x00\x00"));
+    // high surrogate at the end of the stream
+    assert_eq!("a\u{FFFD}", decode(b"\xFF\xFEa\x00\x00\xD8"));
+}
+
+#[test]
+fn test_decode_utf32_invalid() {
+    assert_eq!("a\u{FFFD}\u{FFFD}", decode(&with_bom(BomType::UTF32BE, b"\x00\x00\x00a\x00\x11\x00\x00\x00\x00")));
+}
+
+#[test]
+fn test_decode_unsupported() {
+    let mut reader = DecodeReader::new(BomType::all(), &b"\x0E\xFE\xFFSCSU"[..]);
+    let mut buf = Vec::new();
+    assert_eq!(ErrorKind::InvalidData, reader.read_to_end(&mut buf).unwrap_err().kind());
+    assert_eq!(Some(Some(BomType::SCSU)), reader.bom_found());
+}
+
+#[cfg(not(feature = "encoding_rs"))]
+#[test]
+fn test_decode_gb18030_unsupported() {
+    let mut reader = DecodeReader::new(BomType::all(), &b"\x84\x31\x95\x33abc"[..]);
+    let mut buf = Vec::new();
+    assert_eq!(ErrorKind::InvalidData, reader.read_to_end(&mut buf).unwrap_err().kind());
+}
+
+#[cfg(feature = "encoding_rs")]
+#[test]
+fn test_decode_gb18030() {
+    let (content, _, _) = encoding_rs::GB18030.encode(TEXT);
+    assert_eq!(TEXT, decode(&with_bom(BomType::GB1803, &content)));
+}
+
+fn test_read_after_end(bytes: &[u8]) {
+    let mut reader = DecodeReader::new(BomType::all(), bytes);
+    let mut string = String::new();
+    let _ = reader.read_to_string(&mut string).unwrap();
+    assert_eq!(TEXT, string);
+    // reading again after the end of the stream
+    assert_eq!(0, reader.read(&mut [0u8; 16]).unwrap());
+    assert_eq!(0, reader.read(&mut [0u8; 16]).unwrap());
+}
+
+#[test]
+fn test_decode_utf16le_read_after_end() {
+    test_read_after_end(&with_bom(BomType::UTF16LE, &encode_utf16(TEXT, false)));
+}
+
+#[cfg(feature = "encoding_rs")]
+#[test]
+fn test_decode_gb18030_read_after_end() {
+    test_read_after_end(&with_bom(BomType::GB1803, &encoding_rs::GB18030.encode(TEXT).0));
+}
+
+#[test]
+fn test_decode_progressive() {
+    let mut reader = DecodeReader::new(BomType::all(), std::io::Cursor::new(b"\xFF".to_vec()));
+    let mut buf = [0u8; 16];
+    assert_eq!(0, reader.read(&mut buf).unwrap());
+    assert_eq!(None, reader.bom_found());
+    reader.get_mut().get_mut().get_mut().extend_from_slice(b"\xFEa\x00");
+    assert_eq!(1, reader.read(&mut buf).unwrap());
+    assert_eq!(b"a", &buf[..1]);
+}
@@ -0,0 +1,75 @@
+#![cfg(all(feature = "encoding_rs", feature = "encoding_rs_io"))]
+
+use encoding_rs_io::DecodeReaderBytesBuilder;
+use skip_bom::*;
+use std::io::{ErrorKind, Read};
+
+fn encode(bom_type: Option<BomType>, text: &str) -> Vec<u8> {
+    let mut bytes = bom_type.map_or(Vec::new(), |bom_type| bom_type.bom_bytes().to_vec());
+    match bom_type {
+        Some(BomType::UTF16LE) => bytes.extend(text.encode_utf16().flat_map(u16::to_le_bytes)),
+        Some(BomType::UTF16BE) => bytes.extend(text.encode_utf16().flat_map(u16::to_be_bytes)),
+        _ => bytes.extend_from_slice(text.as_bytes()),
+    }
+    bytes
+}
+
+macro_rules! test_decode_reader_bytes {
+    ($name:ident, $bom:expr) => {
+        #[test]
+        fn $name() {
+            let bytes = encode($bom, "\u{FEFF}text");
+            // this crate owns the BOM: only the first one is removed, a second one is content
+            let (bom, mut reader) = decode_reader_bytes(BomType::all(), bytes.as_slice()).unwrap();
+            assert_eq!($bom, bom);
+            let mut text = String::new();
+            let _ = reader.read_to_string(&mut text).unwrap();
+            assert_eq!("\u{FEFF}text", text);
+            // encoding_rs_io owns the BOM: the BOM is kept for it to sniff and remove while decoding
+            let mut inner = SkipEncodingBom::new(BomType::all(), bytes.as_slice()).with_bom_kept();
+            assert_eq!($bom, inner.read_bom().unwrap());
+            let mut reader = DecodeReaderBytesBuilder::new().build(inner);
+            text.clear();
+            let _ = reader.read_to_string(&mut text).unwrap();
+            assert_eq!("\u{FEFF}text", text);
+        }
+    };
+}
+
+test_decode_reader_bytes!(test_decode_reader_bytes_utf8, Some(BomType::UTF8));
+test_decode_reader_bytes!(test_decode_reader_bytes_utf16le, Some(BomType::UTF16LE));
+test_decode_reader_bytes!(test_decode_reader_bytes_utf16be, Some(BomType::UTF16BE));
+
+#[test]
+fn test_decode_reader_bytes_no_bom() {
+    let (bom, mut reader) = decode_reader_bytes(BomType::all(), &b"text\xFF"[..]).unwrap();
+    assert_eq!(None, bom);
+    let mut bytes = Vec::new();
+    let _ = reader.read_to_end(&mut bytes).unwrap();
+    assert_eq!(b"text\xFF", bytes.as_slice());
+}
+
+#[test]
+fn test_decode_reader_bytes_gb18030() {
+    let (bom, mut reader) = decode_reader_bytes(BomType::all(), &b"\x84\x31\x95\x33\x84\x31\x95\x33\xC4\xE3\xBA\xC3"[..]).unwrap();
+    assert_eq!(Some(BomType::GB1803), bom);
+    let mut text = String::new();
+    let _ = reader.read_to_string(&mut text).unwrap();
+    assert_eq!("\u{FEFF}你好", text);
+}
+
+#[test]
+fn test_decode_reader_bytes_undecodable() {
+    for bytes in [&b"\xFF\xFE\x00\x00t\x00\x00\x00"[..], &b"\x2B\x2F\x76\x38text"[..]] {
+        let error = decode_reader_bytes(BomType::all(), bytes).map(|_| ()).unwrap_err();
+        assert_eq!(ErrorKind::InvalidData, error.kind());
+    }
+    let error = decode_reader_bytes(BomType::all(), &b"\xFF\xFE\x00\x00"[..]).map(|_| ()).unwrap_err();
+    assert_eq!(Some(BomErrorCode::DecoderUnavailable), BomErrorCode::of(&error));
+}
+
+#[test]
+fn test_encoding_rs_encoding() {
+    assert_eq!(Some(encoding_rs::UTF_16BE), encoding_rs_encoding(BomType::UTF16BE));
+    assert_eq!(None, encoding_rs_encoding(BomType::UTF32LE));
+}
@@ -0,0 +1,35 @@
+#![cfg(feature = "delimiter")]
+
+use skip_bom::*;
+
+macro_rules! test_guess_delimiter {
+    ($test_fn_name:ident, $text:expr, $delimiter:expr) => {
+        #[test]
+        fn $test_fn_name() {
+            assert_eq!($delimiter, guess_delimiter($text));
+        }
+    };
+}
+
+test_guess_delimiter!(test_guess_delimiter_comma, "id,name\n1,a\n2,b\n", Some(','));
+test_guess_delimiter!(test_guess_delimiter_semicolon, "id;name;price\r\n1;a;1,50\r\n2;b;2,00\r\n", Some(';'));
+test_guess_delimiter!(test_guess_delimiter_tab, "id\tname\n1\ta\n", Some('\t'));
+test_guess_delimiter!(test_guess_delimiter_pipe, "id|name\n1|a\n", Some('|'));
+test_guess_delimiter!(test_guess_delimiter_quoted, "id;name\n1;\"a, b, c\"\n2;\"d, e\"\n", Some(';'));
+// commas in the values of some rows do not outweigh the consistent delimiter
+test_guess_delimiter!(test_guess_delimiter_consistency, "a,b;c\nd;e\nf;g\nh,i;j\n", Some(';'));
+test_guess_delimiter!(test_guess_delimiter_tie, "a,b;c\n", Some(','));
+test_guess_delimiter!(test_guess_delimiter_single_column, "id\n1\n", None);
+test_guess_delimiter!(test_guess_delimiter_empty, "", None);
+// the incomplete last row of a truncated sample is ignored
+test_guess_delimiter!(test_guess_delimiter_truncated, "a;b\nc;d\ne;f,g,h,i,j", Some(';'));
+
+#[test]
+fn test_preflight_delimiter() {
+    let report = preflight_reader(&b"\xEF\xBB\xBFid;name\r\n1;a\r\n"[..], &DetectOptions::default()).unwrap();
+    assert_eq!(Some(';'), report.delimiter);
+    let mut utf16 = vec![0xFF, 0xFE];
+    utf16.extend("id\tname\n1\ta\n".encode_utf16().flat_map(u16::to_le_bytes));
+    assert_eq!(Some('\t'), preflight_reader(utf16.as_slice(), &DetectOptions::default()).unwrap().delimiter);
+    assert_eq!(None, preflight_reader(&b"\xFB\xEE\x28id\n"[..], &DetectOptions::default()).unwrap().delimiter);
+}
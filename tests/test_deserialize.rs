@@ -0,0 +1,69 @@
+use skip_bom::*;
+use std::io::{Error, ErrorKind, Read};
+
+fn utf16le(string: &str) -> Vec<u8> {
+    let mut bytes = vec![0xFF, 0xFE];
+    bytes.extend(string.encode_utf16().flat_map(u16::to_le_bytes));
+    bytes
+}
+
+fn parse_u32(mut reader: DecodeReader<'static, &[u8]>) -> Result<u32> {
+    let mut string = String::new();
+    reader.read_to_string(&mut string)?;
+    string.trim().parse::<u32>().map_err(|e| Error::new(ErrorKind::InvalidData, e))
+}
+
+#[test]
+fn test_from_bom_reader_utf8() {
+    assert_eq!(42, from_bom_reader(&b"\xEF\xBB\xBF42"[..], parse_u32).unwrap());
+}
+
+#[test]
+fn test_from_bom_reader_utf16() {
+    assert_eq!(42, from_bom_reader(utf16le("42").as_slice(), parse_u32).unwrap());
+}
+
+#[test]
+fn test_from_bom_reader_no_bom() {
+    assert_eq!(42, from_bom_reader(&b"42"[..], parse_u32).unwrap());
+}
+
+#[test]
+fn test_from_bom_reader_format_error() {
+    let error = from_bom_reader(&b"\xEF\xBB\xBFnot a number"[..], parse_u32).unwrap_err();
+    assert_eq!(ErrorKind::InvalidData, error.kind());
+    let error = from_bom_reader(&b"not a number"[..], |_| "format error".parse::<u32>()).unwrap_err();
+    assert_eq!(ErrorKind::InvalidData, error.kind());
+}
+
+#[test]
+fn test_from_bom_reader_undecodable() {
+    let error = from_bom_reader(&b"\xFB\xEE\x2842"[..], parse_u32).unwrap_err();
+    assert_eq!(Some(BomErrorCode::ExternalDecoderRequired), BomErrorCode::of(&error));
+}
+
+#[cfg(all(feature = "serde", feature = "serde_json"))]
+#[test]
+fn test_from_json_reader() {
+    let expected = vec![1u32, 2, 3];
+    assert_eq!(expected, from_json_reader::<Vec<u32>, _>(&b"\xEF\xBB\xBF[1, 2, 3]"[..]).unwrap());
+    assert_eq!(expected, from_json_reader::<Vec<u32>, _>(utf16le("[1, 2, 3]").as_slice()).unwrap());
+    assert_eq!(ErrorKind::InvalidData, from_json_reader::<Vec<u32>, _>(&b"\xEF\xBB\xBF[1, 2"[..]).unwrap_err().kind());
+}
+
+#[cfg(all(feature = "serde", feature = "serde_yaml"))]
+#[test]
+fn test_from_yaml_reader() {
+    let expected = vec![1u32, 2, 3];
+    assert_eq!(expected, from_yaml_reader::<Vec<u32>, _>(&b"\xEF\xBB\xBF- 1\n- 2\n- 3\n"[..]).unwrap());
+    assert_eq!(expected, from_yaml_reader::<Vec<u32>, _>(utf16le("- 1\n- 2\n- 3\n").as_slice()).unwrap());
+}
+
+#[cfg(all(feature = "serde", feature = "toml"))]
+#[test]
+fn test_from_toml_reader() {
+    let expected: std::collections::BTreeMap<String, u32> = vec![("key".to_string(), 1)].into_iter().collect();
+    assert_eq!(expected, from_toml_reader::<std::collections::BTreeMap<String, u32>, _>(&b"\xEF\xBB\xBFkey = 1\n"[..]).unwrap());
+    assert_eq!(expected, from_toml_reader::<std::collections::BTreeMap<String, u32>, _>(utf16le("key = 1\n").as_slice()).unwrap());
+    assert_eq!(ErrorKind::InvalidData, from_toml_reader::<std::collections::BTreeMap<String, u32>, _>(&b"key = "[..]).unwrap_err().kind());
+}
@@ -0,0 +1,261 @@
+use skip_bom::*;
+use std::borrow::Cow;
+use std::io::ErrorKind;
+
+fn best_candidate(bytes: &[u8]) -> Option<(BomType, CandidateSource)> {
+    let report = detect(bytes, &DetectOptions::default()).unwrap();
+    report.best().map(|candidate| (candidate.encoding, candidate.source))
+}
+
+macro_rules! test_detect_best {
+    ($test_fn_name:ident, $bytes:expr, $expected:expr) => {
+        #[test]
+        fn $test_fn_name() {
+            assert_eq!($expected, best_candidate($bytes));
+        }
+    };
+}
+
+test_detect_best!(test_detect_utf8_bom, b"\xEF\xBB\xBFhello", Some((BomType::UTF8, CandidateSource::Bom)));
+test_detect_best!(test_detect_utf8_no_bom, "h\u{e9}llo".as_bytes(), Some((BomType::UTF8, CandidateSource::Sniffer(BuiltinSniffer::Utf8))));
+test_detect_best!(test_detect_utf16be_no_bom, b"\x00h\x00e\x00l\x00l\x00o", Some((BomType::UTF16BE, CandidateSource::Sniffer(BuiltinSniffer::Utf16))));
+test_detect_best!(test_detect_utf32le_no_bom, b"h\x00\x00\x00e\x00\x00\x00", Some((BomType::UTF32LE, CandidateSource::Sniffer(BuiltinSniffer::Utf32))));
+test_detect_best!(test_detect_binary, b"\x89PNG\r\n\x1A\n\x00\x00\x00\rIHDR\x00\x00\x01\x00", None);
+test_detect_best!(test_detect_empty, b"", None);
+
+#[test]
+fn test_detect_utf32le_bom() {
+    let mut bytes = BomType::UTF32LE.bom_bytes().to_vec();
+    bytes.extend(b"h\x00\x00\x00");
+    assert_eq!(Some((BomType::UTF32LE, CandidateSource::Bom)), best_candidate(&bytes));
+}
+
+#[test]
+fn test_detect_ranking() {
+    let report = detect(&b"\xEF\xBB\xBFplain ascii"[..], &DetectOptions::default()).unwrap();
+    assert_eq!(Some(BomType::UTF8), report.bom);
+    assert_eq!(1, report.candidates.len(), "{:?}", report.candidates);
+    assert_eq!(BOM_CONFIDENCE, report.candidates[0].confidence);
+}
+
+#[test]
+fn test_detect_without_sniffers() {
+    let options = DetectOptions { sniffers: Cow::Borrowed(&[]), ..Default::default() };
+    let report = detect(&b"plain ascii"[..], &options).unwrap();
+    assert_eq!(None, report.bom);
+    assert!(report.candidates.is_empty());
+}
+
+#[test]
+fn test_detect_sample_len() {
+    let options = DetectOptions { sample_len: 2, ..Default::default() };
+    let report = detect(&b"\xEF\xBB\xBFtext"[..], &options).unwrap();
+    assert_eq!(None, report.bom);
+    assert_eq!(2, report.sample_len);
+}
+
+#[test]
+fn test_detect_only_some_boms() {
+    let options = DetectOptions { bom_types: Cow::Borrowed(&[BomType::UTF16LE]), ..Default::default() };
+    let report = detect(&b"\xFF\xFEh\x00"[..], &options).unwrap();
+    assert_eq!(Some(BomType::UTF16LE), report.bom);
+}
+
+#[test]
+fn test_detect_policy_required() {
+    let options = DetectOptions { policy: BomPolicy::Required, ..Default::default() };
+    assert_eq!(Some(BomType::UTF8), detect(&b"\xEF\xBB\xBFtext"[..], &options).unwrap().bom);
+    assert_eq!(ErrorKind::InvalidData, detect(&b"text"[..], &options).unwrap_err().kind());
+}
+
+#[test]
+fn test_detect_policy_forbidden() {
+    let options = DetectOptions { policy: BomPolicy::Forbidden, ..Default::default() };
+    assert_eq!(None, detect(&b"text"[..], &options).unwrap().bom);
+    assert_eq!(ErrorKind::InvalidData, detect(&b"\xEF\xBB\xBFtext"[..], &options).unwrap_err().kind());
+}
+
+#[test]
+fn test_detect_fallback() {
+    let options = DetectOptions { fallback: Some(BomType::UTF8), ..Default::default() };
+    let report = detect(&b"\x00\x00\x00\x00\xFF"[..], &options).unwrap();
+    assert_eq!(vec![EncodingCandidate { encoding: BomType::UTF8, confidence: 0, source: CandidateSource::Fallback }], report.candidates);
+    // the fallback is not added when there are other candidates
+    let report = detect(&b"\xFF\xFEtext"[..], &options).unwrap();
+    assert_eq!(BomType::UTF16LE, report.best().unwrap().encoding);
+    assert!(report.candidates.iter().all(|candidate| candidate.source != CandidateSource::Fallback));
+}
+
+#[test]
+fn test_detect_bom_only_empty() {
+    let report = detect(&b"\xEF\xBB\xBF"[..], &DetectOptions::default()).unwrap();
+    assert_eq!(Some(BomType::UTF8), report.bom);
+    assert!(!report.empty_with_bom);
+}
+
+#[test]
+fn test_detect_bom_only_error() {
+    let options = DetectOptions { bom_only: BomOnlyPolicy::Error, ..Default::default() };
+    assert_eq!(ErrorKind::InvalidData, detect(&b"\xEF\xBB\xBF"[..], &options).unwrap_err().kind());
+    assert_eq!(ErrorKind::InvalidData, detect(&b"\xFF\xFE"[..], &options).unwrap_err().kind());
+    assert_eq!(Some(BomType::UTF8), detect(&b"\xEF\xBB\xBFtext"[..], &options).unwrap().bom);
+    assert_eq!(None, detect(&b""[..], &options).unwrap().bom);
+}
+
+#[test]
+fn test_detect_bom_only_report() {
+    let options = DetectOptions { bom_only: BomOnlyPolicy::Report, ..Default::default() };
+    let report = detect(&b"\xFE\xFF"[..], &options).unwrap();
+    assert_eq!(Some(BomType::UTF16BE), report.bom);
+    assert!(report.empty_with_bom);
+    assert!(!detect(&b"\xFE\xFF\x00a"[..], &options).unwrap().empty_with_bom);
+    assert!(!detect(&b""[..], &options).unwrap().empty_with_bom);
+    // the stream may continue after the sample
+    let options = DetectOptions { sample_len: 3, ..options };
+    assert!(!detect(&b"\xEF\xBB\xBFtext"[..], &options).unwrap().empty_with_bom);
+    // the stream is exactly as long as the sample
+    assert!(detect(&b"\xEF\xBB\xBF"[..], &options).unwrap().empty_with_bom);
+    let options = DetectOptions { sample_len: 2, ..options };
+    assert!(detect(&b"\xFE\xFF"[..], &options).unwrap().empty_with_bom);
+    assert_eq!(None, detect(&b"\xFF\xFE\x00\x00"[..], &options).unwrap().bom);
+}
+
+test_detect_best!(test_detect_xml_declaration_utf16le, b"<\x00?\x00x\x00m\x00l\x00", Some((BomType::UTF16LE, CandidateSource::Sniffer(BuiltinSniffer::XmlDeclaration))));
+test_detect_best!(test_detect_xml_declaration_utf16be, b"\x00<\x00?\x00x\x00m\x00l", Some((BomType::UTF16BE, CandidateSource::Sniffer(BuiltinSniffer::XmlDeclaration))));
+test_detect_best!(test_detect_xml_declaration_utf32le, b"<\x00\x00\x00?\x00\x00\x00", Some((BomType::UTF32LE, CandidateSource::Sniffer(BuiltinSniffer::XmlDeclaration))));
+
+#[test]
+fn test_sniff_xml_declaration_ascii() {
+    assert_eq!(None, BuiltinSniffer::XmlDeclaration.sniff(b"<?xml"));
+    assert_eq!(None, BuiltinSniffer::XmlDeclaration.sniff(b"<"));
+}
+
+const PNG_START: &[u8] = b"\x89PNG\r\n\x1A\n\x00\x00\x00\rIHDR\x00\x00\x01\x00\x00\x00\x01\x00\x08\x06\x00\x00\x00";
+
+macro_rules! test_detect_binary {
+    ($test_fn_name:ident, $bytes:expr, $expected:expr) => {
+        #[test]
+        fn $test_fn_name() {
+            assert_eq!($expected, detect_binary($bytes));
+        }
+    };
+}
+
+test_detect_binary!(test_detect_binary_png, PNG_START, true);
+test_detect_binary!(test_detect_binary_gzip, b"\x1F\x8B\x08\x00\x00\x00\x00\x00\x00\x03", true);
+test_detect_binary!(test_detect_binary_random, b"\xC3\x28\xA0\xA1\xE2\x28\xA1\xF0\x28\x8C\xBC\xFF", true);
+test_detect_binary!(test_detect_binary_empty, b"", false);
+test_detect_binary!(test_detect_binary_ascii, b"id,name\r\n1,text\r\n", false);
+test_detect_binary!(test_detect_binary_utf8, "caf\u{e9}, na\u{ef}ve".as_bytes(), false);
+test_detect_binary!(test_detect_binary_latin1, b"caf\xE9, na\xEFve, \xE0 la carte", false);
+test_detect_binary!(test_detect_binary_utf16, b"i\x00d\x00,\x00n\x00a\x00m\x00e\x00", false);
+test_detect_binary!(test_detect_binary_utf32, b"\x00\x00\x00i\x00\x00\x00d", false);
+test_detect_binary!(test_detect_binary_truncated_utf8, b"caf\xC3", false);
+
+#[test]
+fn test_detect_binary_ignore() {
+    let report = detect(PNG_START, &DetectOptions::default()).unwrap();
+    assert!(!report.probably_binary);
+}
+
+#[test]
+fn test_detect_binary_error() {
+    let options = DetectOptions { binary: BinaryPolicy::Error, ..Default::default() };
+    assert_eq!(ErrorKind::InvalidData, detect(PNG_START, &options).unwrap_err().kind());
+    assert!(detect(&b"id,name\r\n"[..], &options).is_ok());
+    // the content after a BOM is not checked
+    assert!(detect(&b"\xEF\xBB\xBF\x00\x00\x00\x00"[..], &options).is_ok());
+}
+
+#[test]
+fn test_detect_binary_report() {
+    let options = DetectOptions { binary: BinaryPolicy::Report, ..Default::default() };
+    assert!(detect(PNG_START, &options).unwrap().probably_binary);
+    assert!(!detect(&b"id,name\r\n"[..], &options).unwrap().probably_binary);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_detect_options_from_toml() {
+    let options: DetectOptions = toml::from_str(r#"
+        bom_types = ["UTF8", "UTF16LE"]
+        policy = "Required"
+        bom_only = "Report"
+        sniffers = ["Utf16"]
+        fallback = "UTF8"
+        sample_len = 1024
+    "#).unwrap();
+    assert_eq!(&[BomType::UTF8, BomType::UTF16LE], options.bom_types.as_ref());
+    assert_eq!(BomPolicy::Required, options.policy);
+    assert_eq!(BomOnlyPolicy::Report, options.bom_only);
+    assert_eq!(&[BuiltinSniffer::Utf16], options.sniffers.as_ref());
+    assert_eq!(Some(BomType::UTF8), options.fallback);
+    assert_eq!(1024, options.sample_len);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_detect_options_from_toml_defaults() {
+    let options: DetectOptions = toml::from_str("policy = \"Forbidden\"").unwrap();
+    assert_eq!(DetectOptions { policy: BomPolicy::Forbidden, ..Default::default() }, options);
+}
+
+#[test]
+fn test_detect_decode_unavailable() {
+    for bom_type in BomType::all() {
+        let report = detect(bom_type.bom_bytes(), &DetectOptions::default()).unwrap();
+        assert_eq!(Some(*bom_type), report.bom);
+        assert_eq!(bom_type.requires_external_decoder(), report.decode_unavailable(), "{:?}", bom_type);
+        assert_eq!(bom_type.requires_external_decoder(), report.best().unwrap().requires_external_decoder(), "{:?}", bom_type);
+        assert_eq!(bom_type.requires_external_decoder(), report.ensure_decodable().is_err(), "{:?}", bom_type);
+    }
+    let report = detect(&b"text"[..], &DetectOptions::default()).unwrap();
+    assert!(!report.decode_unavailable());
+    assert!(report.ensure_decodable().is_ok());
+}
+
+#[test]
+fn test_reject_external_decoder_hook() {
+    let mut reader = SkipEncodingBom::new(BomType::all(), &b"\x0E\xFE\xFFSCSU"[..]).with_bom_hook(reject_external_decoder);
+    assert_eq!(ErrorKind::InvalidData, reader.read_bom().unwrap_err().kind());
+    let mut reader = SkipEncodingBom::new(BomType::all(), &b"\x84\x31\x95\x33GB18030"[..]).with_bom_hook(reject_external_decoder);
+    assert_eq!(Some(BomType::GB1803), reader.read_bom().unwrap());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_encoding_report_to_toml() {
+    let options = DetectOptions { binary: BinaryPolicy::Report, ..Default::default() };
+    let report = detect(&b"\xFF\xFEh\x00"[..], &options).unwrap();
+    let serialized = toml::to_string(&report).unwrap();
+    assert!(serialized.contains("bom = \"UTF16LE\""), "{}", serialized);
+    assert_eq!(report, toml::from_str(&serialized).unwrap());
+}
+
+macro_rules! test_detect_fingerprint {
+    ($test_fn_name:ident, $bytes:expr, $fingerprint_len:expr, $fingerprint:expr) => {
+        #[test]
+        fn $test_fn_name() {
+            let options = DetectOptions { fingerprint_len: $fingerprint_len, ..Default::default() };
+            assert_eq!($fingerprint, detect(&$bytes[..], &options).unwrap().fingerprint);
+        }
+    };
+}
+
+test_detect_fingerprint!(test_detect_fingerprint_after_bom, b"\xEF\xBB\xBFPK\x03\x04", 16, "504b0304");
+test_detect_fingerprint!(test_detect_fingerprint_no_bom, b"\x89PNG\r\n\x1A\n", 4, "89504e47");
+test_detect_fingerprint!(test_detect_fingerprint_disabled, b"text", 0, "");
+test_detect_fingerprint!(test_detect_fingerprint_bom_only, b"\xFF\xFE", 16, "");
+
+#[test]
+fn test_detect_fingerprint_within_sample() {
+    let options = DetectOptions { sample_len: 5, fingerprint_len: 16, ..Default::default() };
+    assert_eq!("0102", detect(&b"\xEF\xBB\xBF\x01\x02\x03\x04"[..], &options).unwrap().fingerprint);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_encoding_report_without_fingerprint_from_toml() {
+    let report: EncodingReport = toml::from_str("sample_len = 4\nempty_with_bom = false\nprobably_binary = false\ncandidates = []").unwrap();
+    assert_eq!("", report.fingerprint);
+}
@@ -0,0 +1,108 @@
+use skip_bom::*;
+use std::io::{Error, ErrorKind, Read};
+
+fn detect_error(bytes: &[u8], options: &DetectOptions) -> Error {
+    detect(bytes, options).unwrap_err()
+}
+
+macro_rules! test_error_code {
+    ($test_fn_name:ident, $error:expr, $code:expr, $bom_type:expr) => {
+        #[test]
+        fn $test_fn_name() {
+            let error: Error = $error;
+            assert_eq!(Some($code), BomErrorCode::of(&error));
+            let bom_error = BomError::of(&error).unwrap();
+            assert_eq!($code, bom_error.code());
+            assert_eq!($bom_type, bom_error.bom_type());
+            // the message of the io::Error is the message of the wrapped error
+            assert_eq!(bom_error.to_string(), error.to_string());
+        }
+    };
+}
+
+test_error_code!(
+    test_error_code_bom_required,
+    detect_error(b"text", &DetectOptions { policy: BomPolicy::Required, ..Default::default() }),
+    BomErrorCode::BomRequired,
+    None
+);
+test_error_code!(
+    test_error_code_bom_forbidden,
+    detect_error(b"\xFE\xFF\x00t", &DetectOptions { policy: BomPolicy::Forbidden, ..Default::default() }),
+    BomErrorCode::BomForbidden,
+    Some(BomType::UTF16BE)
+);
+test_error_code!(
+    test_error_code_bom_only,
+    detect_error(b"\xEF\xBB\xBF", &DetectOptions { bom_only: BomOnlyPolicy::Error, ..Default::default() }),
+    BomErrorCode::BomOnly,
+    Some(BomType::UTF8)
+);
+test_error_code!(
+    test_error_code_binary_content,
+    detect_error(b"\x89PNG\r\n\x1A\n\x00\x00\x00\rIHDR", &DetectOptions { binary: BinaryPolicy::Error, ..Default::default() }),
+    BomErrorCode::BinaryContent,
+    None
+);
+test_error_code!(
+    test_error_code_external_decoder_required,
+    reject_external_decoder(BomType::SCSU).unwrap_err(),
+    BomErrorCode::ExternalDecoderRequired,
+    Some(BomType::SCSU)
+);
+test_error_code!(
+    test_error_code_control_characters,
+    {
+        let options = SanitizeOptions { control_chars: ControlCharPolicy::Reject, ..Default::default() };
+        TextSanitizer::new(BomType::all(), &b"a\x00"[..]).with_options(options).read_to_end(&mut Vec::new()).unwrap_err()
+    },
+    BomErrorCode::ControlCharacters,
+    None
+);
+test_error_code!(
+    test_error_code_buffer_overflow,
+    BomBytesPushBuffer::from_slice(&[0; 16]).unwrap_err(),
+    BomErrorCode::BufferOverflow,
+    None
+);
+test_error_code!(
+    test_error_code_decode_external_decoder_required,
+    DecodeReader::new(BomType::all(), &b"\x2B\x2F\x76text"[..]).read_to_end(&mut Vec::new()).unwrap_err(),
+    BomErrorCode::ExternalDecoderRequired,
+    Some(BomType::UTF7)
+);
+#[cfg(not(feature = "encoding_rs"))]
+test_error_code!(
+    test_error_code_decoder_unavailable,
+    DecodeReader::new(BomType::all(), &b"\x84\x31\x95\x33text"[..]).read_to_end(&mut Vec::new()).unwrap_err(),
+    BomErrorCode::DecoderUnavailable,
+    Some(BomType::GB1803)
+);
+
+/// Reader reporting more bytes than the buffer it was given can hold.
+struct OverReportingReader;
+
+impl Read for OverReportingReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        Ok(buf.len() + 1)
+    }
+}
+
+test_error_code!(
+    test_error_code_reader_over_reported,
+    SkipEncodingBom::new(BomType::all(), OverReportingReader).read_bom().unwrap_err(),
+    BomErrorCode::ReaderOverReported,
+    None
+);
+
+#[test]
+fn test_error_code_other_errors() {
+    assert_eq!(None, BomErrorCode::of(&Error::new(ErrorKind::InvalidData, "other error")));
+    assert_eq!(None, BomErrorCode::of(&Error::from(ErrorKind::UnexpectedEof)));
+}
+
+#[test]
+fn test_error_code_as_str() {
+    assert_eq!("bom-required", BomErrorCode::BomRequired.as_str());
+    assert_eq!("external-decoder-required", BomErrorCode::ExternalDecoderRequired.as_str());
+}
@@ -0,0 +1,45 @@
+use skip_bom::*;
+
+macro_rules! test_explain {
+    ($test_fn_name:ident, $bytes:expr, $verdict:expr, $message:expr) => {
+        #[test]
+        fn $test_fn_name() {
+            let trace = explain($bytes, BomType::all());
+            assert_eq!($verdict, trace.verdict);
+            assert_eq!(trace.examined.len(), trace.steps.len());
+            assert_eq!($message, trace.to_string());
+        }
+    };
+}
+
+test_explain!(test_explain_utf8, b"\xEF\xBB\xBFtext", BomStatus::Bom(BomType::UTF8), "starts with EF BB BF (UTF-8 BOM)");
+test_explain!(test_explain_utf16be, b"\xFE\xFF\x00t", BomStatus::Bom(BomType::UTF16BE), "starts with FE FF (UTF-16BE BOM)");
+test_explain!(test_explain_utf32le, b"\xFF\xFE\x00\x00t\x00\x00\x00", BomStatus::Bom(BomType::UTF32LE), "starts with FF FE 00 00 (UTF-32LE BOM)");
+test_explain!(test_explain_utf16le, b"\xFF\xFEt\x00", BomStatus::Bom(BomType::UTF16LE), "starts with FF FE 74 (UTF-16LE BOM)");
+test_explain!(test_explain_gb18030, b"\x84\x31\x95\x33", BomStatus::Bom(BomType::GB1803), "starts with 84 31 95 33 (GB18030 BOM)");
+test_explain!(test_explain_no_bom, b"text", BomStatus::NoBom, "starts with 74 (no BOM)");
+test_explain!(test_explain_partial_bom, b"\xEF\xBBtext", BomStatus::NoBom, "starts with EF BB 74 (no BOM)");
+test_explain!(test_explain_incomplete, b"\xFF\xFE\x00", BomStatus::Undetermined, "starts with FF FE 00 (BOM presence undetermined)");
+test_explain!(test_explain_empty, b"", BomStatus::Undetermined, "is empty (BOM presence undetermined)");
+
+#[test]
+fn test_explain_steps() {
+    let trace = explain(b"\xFF\xFE\x00a", &[BomType::UTF8, BomType::UTF16LE, BomType::UTF32LE]);
+    assert_eq!(BomStatus::Bom(BomType::UTF16LE), trace.verdict);
+    assert_eq!(
+        vec![
+            TraceStep { byte: 0xFF, eliminated: vec![BomType::UTF8], remaining: vec![BomType::UTF32LE, BomType::UTF16LE] },
+            TraceStep { byte: 0xFE, eliminated: vec![], remaining: vec![BomType::UTF32LE, BomType::UTF16LE] },
+            TraceStep { byte: 0x00, eliminated: vec![], remaining: vec![BomType::UTF32LE, BomType::UTF16LE] },
+            TraceStep { byte: 0x61, eliminated: vec![BomType::UTF32LE], remaining: vec![BomType::UTF16LE] },
+        ],
+        trace.steps
+    );
+}
+
+#[test]
+fn test_explain_disabled_bom() {
+    let trace = explain(b"\xEF\xBB\xBFtext", &[BomType::UTF16LE]);
+    assert_eq!(BomStatus::NoBom, trace.verdict);
+    assert_eq!("starts with EF (no BOM)", trace.to_string());
+}
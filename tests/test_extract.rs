@@ -0,0 +1,54 @@
+#![cfg(feature = "axum")]
+
+use axum::body::Body;
+use axum::extract::{FromRequest, Request};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use skip_bom::{BomCleanText, BomCleanTextRejection, BomType, PolicyAction, PolicyCondition, ReadPolicy};
+
+async fn extract(body: &'static [u8], policy: Option<ReadPolicy>) -> Result<BomCleanText, BomCleanTextRejection> {
+    let mut request = Request::new(Body::from(body));
+    if let Some(policy) = policy {
+        let _ = request.extensions_mut().insert(policy);
+    }
+    BomCleanText::from_request(request, &()).await
+}
+
+macro_rules! test_extract {
+    ($name:ident, $body:expr, $text:expr, $bom:expr) => {
+        #[tokio::test]
+        async fn $name() {
+            let BomCleanText { text, report } = extract($body, None).await.unwrap();
+            assert_eq!($text, text);
+            assert_eq!($bom, report.bom);
+        }
+    };
+}
+
+test_extract!(test_extract_utf8, b"\xEF\xBB\xBFtext", "text", Some(BomType::UTF8));
+test_extract!(test_extract_utf16be, b"\xFE\xFF\x00t\x00e\x00x\x00t", "text", Some(BomType::UTF16BE));
+test_extract!(test_extract_utf32le, b"\xFF\xFE\x00\x00t\x00\x00\x00", "t", Some(BomType::UTF32LE));
+test_extract!(test_extract_no_bom, b"text", "text", None);
+test_extract!(test_extract_empty, b"", "", None);
+
+#[tokio::test]
+async fn test_extract_policy_rejected() {
+    let policy = ReadPolicy::default().rule(PolicyCondition::NoBom, PolicyAction::Reject);
+    let rejection = extract(b"text", Some(policy)).await.unwrap_err();
+    assert!(matches!(rejection, BomCleanTextRejection::Content(_)));
+    assert_eq!(StatusCode::UNSUPPORTED_MEDIA_TYPE, rejection.into_response().status());
+}
+
+#[tokio::test]
+async fn test_extract_policy_skip() {
+    let policy = ReadPolicy::default();
+    let rejection = extract(b"\xFF\xFE\xE9\x00", Some(policy.clone())).await.unwrap_err();
+    assert_eq!(StatusCode::BAD_REQUEST, rejection.into_response().status());
+    assert_eq!("text", extract(b"\xEF\xBB\xBFtext", Some(policy)).await.unwrap().text);
+}
+
+#[tokio::test]
+async fn test_extract_invalid_utf8() {
+    let rejection = extract(b"\xEF\xBB\xBF\xFF", None).await.unwrap_err();
+    assert_eq!(StatusCode::BAD_REQUEST, rejection.into_response().status());
+}
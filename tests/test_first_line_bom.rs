@@ -0,0 +1,61 @@
+use skip_bom::*;
+use std::io::Read;
+
+mod reader_test_utils;
+use reader_test_utils::*;
+
+fn test_first_line_bom(bytes: &[u8], expected: &[u8], expected_bom: Option<BomType>) {
+    for chunk_len in 1..=bytes.len().max(1) {
+        for buf_len in [1, 2, 3, 64].iter().copied() {
+            let mut reader = FirstLineBom::new(BomType::all(), ChunkedReader { bytes, chunk_len });
+            let mut output = Vec::new();
+            let mut buf = vec![0; buf_len];
+            loop {
+                match reader.read(&mut buf).unwrap() {
+                    0 => break,
+                    len => output.extend_from_slice(&buf[..len]),
+                }
+            }
+            assert_eq!(expected, output.as_slice(), "chunk {} buffer {}", chunk_len, buf_len);
+            assert_eq!(Some(expected_bom), reader.bom_found(), "chunk {} buffer {}", chunk_len, buf_len);
+        }
+    }
+}
+
+macro_rules! test_first_line_bom {
+    ($test_fn_name:ident, $bytes:expr, $expected:expr, $expected_bom:expr) => {
+        #[test]
+        fn $test_fn_name() {
+            test_first_line_bom($bytes, $expected, $expected_bom);
+        }
+    };
+}
+
+test_first_line_bom!(test_first_line_bom_at_start, b"\xEF\xBB\xBFGET /\r\nbody", b"GET /\r\nbody", Some(BomType::UTF8));
+test_first_line_bom!(test_first_line_bom_after_whitespace, b"  \xEF\xBB\xBFGET /\r\n", b"  GET /\r\n", Some(BomType::UTF8));
+test_first_line_bom!(test_first_line_bom_second_line, b"GET /\n\xEF\xBB\xBFbody", b"GET /\n\xEF\xBB\xBFbody", None);
+test_first_line_bom!(test_first_line_bom_after_cr, b"GET /\r\xEF\xBB\xBF", b"GET /\r\xEF\xBB\xBF", None);
+test_first_line_bom!(test_first_line_bom_only_first, b"\xEF\xBB\xBF\xEF\xBB\xBFa\n", b"\xEF\xBB\xBFa\n", Some(BomType::UTF8));
+test_first_line_bom!(test_first_line_bom_incomplete_before_newline, b"a\xEF\xBB\nb", b"a\xEF\xBB\nb", None);
+test_first_line_bom!(test_first_line_bom_incomplete_at_end, b"a\xEF\xBB", b"a\xEF\xBB", None);
+test_first_line_bom!(test_first_line_bom_no_newline, b"no newline", b"no newline", None);
+test_first_line_bom!(test_first_line_bom_empty, b"", b"", None);
+
+#[test]
+fn test_first_line_bom_undetermined() {
+    let mut reader = FirstLineBom::new(BomType::all(), ChunkedReader { bytes: b"GET / HTTP/1.1\r\n", chunk_len: 3 });
+    let mut buf = [0; 3];
+    assert_eq!(3, reader.read(&mut buf).unwrap());
+    assert_eq!(None, reader.bom_found());
+}
+
+#[test]
+fn test_first_line_bom_passthrough() {
+    let mut reader = FirstLineBom::new(&[BomType::UTF8], &b"\xEF\xBB\xBFa\nrest"[..]);
+    let mut buf = [0; 2];
+    reader.read_exact(&mut buf).unwrap();
+    assert_eq!(b"a\n", &buf);
+    // the rest is read directly from the underlying reader
+    assert_eq!(b"rest", reader.get_ref());
+    assert_eq!(b"rest", reader.into_inner());
+}
@@ -0,0 +1,92 @@
+#![cfg(feature = "follow")]
+
+use skip_bom::*;
+use std::io::{Read, Write};
+use std::path::PathBuf;
+use std::thread;
+use std::time::Duration;
+
+/// Temporary file path, the file being removed when dropped.
+struct TempPath(PathBuf);
+
+impl TempPath {
+    fn new(name: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("skip_bom_{}_{}", std::process::id(), name));
+        let _ = std::fs::remove_file(&path);
+        TempPath(path)
+    }
+}
+
+impl Drop for TempPath {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+fn append(path: &PathBuf, bytes: &[u8]) {
+    std::fs::OpenOptions::new().create(true).append(true).open(path).unwrap().write_all(bytes).unwrap();
+}
+
+fn follow(path: &PathBuf) -> FollowNoBom {
+    follow_no_bom(path).with_poll_interval(Duration::from_millis(5))
+}
+
+fn read_n(reader: &mut FollowNoBom, len: usize) -> Vec<u8> {
+    let mut buf = vec![0; len];
+    reader.read_exact(&mut buf).unwrap();
+    buf
+}
+
+#[test]
+fn test_follow_created_later() {
+    let temp = TempPath::new("test_follow_created_later");
+    let mut reader = follow(&temp.0);
+    assert_eq!(None, reader.bom_found());
+    let path = temp.0.clone();
+    let writer = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(50));
+        append(&path, b"\xEF\xBB");
+        thread::sleep(Duration::from_millis(50));
+        append(&path, b"\xBFline 1\n");
+        thread::sleep(Duration::from_millis(50));
+        append(&path, b"line 2\n");
+    });
+    assert_eq!(b"line 1\nline 2\n", read_n(&mut reader, 14).as_slice());
+    assert_eq!(Some(Some(BomType::UTF8)), reader.bom_found());
+    writer.join().unwrap();
+}
+
+#[test]
+fn test_follow_utf16le() {
+    let temp = TempPath::new("test_follow_utf16le");
+    append(&temp.0, b"\xFF\xFE");
+    let mut reader = follow(&temp.0);
+    let path = temp.0.clone();
+    let writer = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(50));
+        append(&path, b"a\x00");
+    });
+    // FF FE is held back until the next bytes rule out the UTF-32LE BOM
+    assert_eq!(b"a\x00", read_n(&mut reader, 2).as_slice());
+    assert_eq!(Some(Some(BomType::UTF16LE)), reader.bom_found());
+    writer.join().unwrap();
+}
+
+#[test]
+fn test_follow_truncated() {
+    let temp = TempPath::new("test_follow_truncated");
+    append(&temp.0, b"\xEF\xBB\xBFold log\n");
+    let mut reader = follow(&temp.0);
+    assert_eq!(b"old log\n", read_n(&mut reader, 8).as_slice());
+    std::fs::write(&temp.0, b"\xFF\xFEn\x00").unwrap();
+    assert_eq!(b"n\x00", read_n(&mut reader, 2).as_slice());
+    assert_eq!(Some(Some(BomType::UTF16LE)), reader.bom_found());
+}
+
+#[test]
+fn test_follow_empty_buffer() {
+    let temp = TempPath::new("test_follow_empty_buffer");
+    let mut reader = follow(&temp.0);
+    assert_eq!(0, reader.read(&mut []).unwrap());
+    assert_eq!(temp.0.as_path(), reader.path());
+}
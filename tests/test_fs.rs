@@ -0,0 +1,68 @@
+use skip_bom::*;
+use std::io::{ErrorKind, Read};
+
+mod fs_test_utils;
+use fs_test_utils::*;
+
+fn test_read_text(name: &str, content: &[u8], expected: &str) {
+    let file = TempFile::new(name, content);
+    assert_eq!(expected, file.0.read_text_no_bom().unwrap());
+    let mut text = String::new();
+    let _ = file.0.as_path().open_text_no_bom().unwrap().read_to_string(&mut text).unwrap();
+    assert_eq!(expected, text);
+}
+
+macro_rules! test_read_text {
+    ($test_fn_name:ident, $content:expr, $expected:expr) => {
+        #[test]
+        fn $test_fn_name() {
+            test_read_text(stringify!($test_fn_name), $content, $expected);
+        }
+    };
+}
+
+test_read_text!(test_read_text_utf8_bom, b"\xEF\xBB\xBFhello", "hello");
+test_read_text!(test_read_text_no_bom, b"hello", "hello");
+test_read_text!(test_read_text_utf16le_bom, b"\xFF\xFEh\x00i\x00", "hi");
+test_read_text!(test_read_text_utf16be_bom, b"\xFE\xFF\x00h\x00i", "hi");
+test_read_text!(test_read_text_empty, b"", "");
+
+#[test]
+fn test_read_text_invalid_utf8() {
+    let file = TempFile::new("test_read_text_invalid_utf8", b"\xEF\xBB\xBF\xFF");
+    assert_eq!(ErrorKind::InvalidData, file.0.read_text_no_bom().unwrap_err().kind());
+}
+
+#[test]
+fn test_open_text_not_found() {
+    let path = std::env::temp_dir().join(format!("skip_bom_{}_test_open_text_not_found", std::process::id()));
+    assert_eq!(ErrorKind::NotFound, path.open_text_no_bom().unwrap_err().kind());
+}
+
+fn test_load_config(name: &str, content: &[u8], expected: &str, expected_bom: Option<BomType>) {
+    let file = TempFile::new(name, content);
+    assert_eq!((expected.to_string(), expected_bom), load_config_str(&file.0).unwrap());
+}
+
+macro_rules! test_load_config {
+    ($test_fn_name:ident, $content:expr, $expected:expr, $expected_bom:expr) => {
+        #[test]
+        fn $test_fn_name() {
+            test_load_config(stringify!($test_fn_name), $content, $expected, $expected_bom);
+        }
+    };
+}
+
+test_load_config!(test_load_config_utf8_bom, b"\xEF\xBB\xBFkey = 1\r\nname = \"a\"\r\n", "key = 1\nname = \"a\"\n", Some(BomType::UTF8));
+test_load_config!(test_load_config_utf16le_bom, b"\xFF\xFEk\x00=\x001\x00\r\x00\n\x00", "k=1\n", Some(BomType::UTF16LE));
+test_load_config!(test_load_config_no_bom, b"key = 1\n", "key = 1\n", None);
+test_load_config!(test_load_config_lone_cr, b"a\rb\r\n", "a\rb\n", None);
+test_load_config!(test_load_config_empty, b"", "", None);
+
+#[test]
+fn test_load_config_toml() {
+    let file = TempFile::new("test_load_config_toml", b"\xEF\xBB\xBF[section]\r\nkey = 1\r\n");
+    let (config, _) = load_config_str(&file.0).unwrap();
+    let value: toml::Value = toml::from_str(&config).unwrap();
+    assert_eq!(Some(1), value["section"]["key"].as_integer());
+}
@@ -0,0 +1,83 @@
+use skip_bom::*;
+use std::io::{Cursor, Read};
+
+fn read_to_end<R: Read>(mut reader: SkipEncodingBom<R>) -> (Vec<u8>, Option<BomType>) {
+    let mut bytes = Vec::new();
+    let _ = reader.read_to_end(&mut bytes).unwrap();
+    (bytes, reader.bom_found().flatten())
+}
+
+/// Content and BOM expected for an in-memory input: those of the streaming reader, except that an input ending inside
+/// a possible BOM is returned as-is rather than dropped.
+fn expected(input: &[u8]) -> (Vec<u8>, Option<BomType>) {
+    let mut reader = SkipEncodingBom::new(BomType::all(), input);
+    let mut bytes = Vec::new();
+    let _ = reader.read_to_end(&mut bytes).unwrap();
+    match reader.bom_found() {
+        Some(bom_type) => (bytes, bom_type),
+        None => (input.to_vec(), None),
+    }
+}
+
+/// Every input of up to 5 bytes built from the bytes of the BOMs.
+fn inputs() -> Vec<Vec<u8>> {
+    let alphabet = [0x00, 0xEF, 0xBB, 0xBF, 0xFF, 0xFE, 0x61];
+    let mut inputs = vec![Vec::new()];
+    let mut previous = vec![Vec::new()];
+    for _ in 0..5 {
+        let next: Vec<Vec<u8>> = previous.iter().flat_map(|input| alphabet.iter().map(move |byte| [input.as_slice(), &[*byte]].concat())).collect();
+        inputs.extend(next.iter().cloned());
+        previous = next;
+    }
+    inputs
+}
+
+#[test]
+fn test_from_slice_same_as_reader() {
+    for input in inputs() {
+        let expected = expected(&input);
+        let reader = SkipEncodingBom::from_slice(BomType::all(), &input);
+        assert!(reader.bom_found().is_some());
+        assert_eq!(expected, read_to_end(reader), "input {:X?}", input);
+    }
+}
+
+#[test]
+fn test_from_cursor_same_as_reader() {
+    for input in inputs() {
+        let expected = expected(&input);
+        let reader = SkipEncodingBom::from_cursor(BomType::all(), Cursor::new(input.clone()));
+        assert!(reader.bom_found().is_some());
+        assert_eq!(expected, read_to_end(reader), "input {:X?}", input);
+    }
+}
+
+#[test]
+fn test_from_slice_empty() {
+    let reader = SkipEncodingBom::from_slice(BomType::all(), b"");
+    assert_eq!(BomStatus::NoBom, reader.status());
+}
+
+#[test]
+fn test_from_slice_bom_types() {
+    let reader = SkipEncodingBom::from_slice(&[BomType::UTF16LE], b"\xEF\xBB\xBFtext");
+    assert_eq!(Some(None), reader.bom_found());
+    assert_eq!(b"\xEF\xBB\xBFtext", reader.get_ref());
+}
+
+#[test]
+fn test_from_cursor_position() {
+    let mut cursor = Cursor::new(b"header\xEF\xBB\xBFtext");
+    cursor.set_position(6);
+    let reader = SkipEncodingBom::from_cursor(BomType::all(), cursor);
+    assert_eq!(Some(Some(BomType::UTF8)), reader.bom_found());
+    assert_eq!((b"text".to_vec(), Some(BomType::UTF8)), read_to_end(reader));
+}
+
+#[test]
+fn test_from_cursor_past_end() {
+    let mut cursor = Cursor::new(b"text");
+    cursor.set_position(10);
+    let reader = SkipEncodingBom::from_cursor(BomType::all(), cursor);
+    assert_eq!((Vec::new(), None), read_to_end(reader));
+}
@@ -0,0 +1,59 @@
+use skip_bom::*;
+use std::io::{Cursor, Read, Seek, SeekFrom};
+
+fn read_all<R: Read>(reader: &mut SkipEncodingBom<R>) -> Vec<u8> {
+    let mut buf = Vec::new();
+    let _ = reader.read_to_end(&mut buf).unwrap();
+    buf
+}
+
+#[test]
+fn test_inner_guard_touched_while_undetermined() {
+    let mut reader = SkipEncodingBom::new(BomType::all(), Cursor::new(b"\xEF\xBB".to_vec()));
+    assert_eq!(b"", read_all(&mut reader).as_slice());
+    *reader.inner_mut() = Cursor::new(b"\xFF\xFEt\x00".to_vec());
+    assert_eq!(BomStatus::Undetermined, reader.status());
+    assert_eq!(b"t\x00", read_all(&mut reader).as_slice());
+    assert_eq!(Some(Some(BomType::UTF16LE)), reader.bom_found());
+}
+
+#[test]
+fn test_inner_guard_touched_while_buffered() {
+    // the 4 bytes probe holds 2 bytes after the BOM
+    let mut reader = SkipEncodingBom::new(BomType::all(), Cursor::new(b"\xFF\xFEt\x00e\x00"));
+    let mut buf = [0; 1];
+    reader.read_exact(&mut buf).unwrap();
+    assert_eq!(BomStatus::Bom(BomType::UTF16LE), reader.status());
+    let _ = reader.inner_mut().seek(SeekFrom::Start(0)).unwrap();
+    assert_eq!(BomStatus::Undetermined, reader.status());
+    assert_eq!(b"t\x00e\x00", read_all(&mut reader).as_slice());
+}
+
+#[test]
+fn test_inner_guard_touched_when_final() {
+    let mut reader = SkipEncodingBom::new(BomType::all(), Cursor::new(b"\xEF\xBB\xBFtext"));
+    let mut buf = [0; 5];
+    reader.read_exact(&mut buf[..4]).unwrap();
+    // reading the underlying reader directly is passthrough once the buffered bytes are returned
+    assert_eq!(0, reader.inner_mut().read(&mut buf).unwrap());
+    assert_eq!(BomStatus::Bom(BomType::UTF8), reader.status());
+}
+
+#[test]
+fn test_inner_guard_not_touched() {
+    let mut reader = SkipEncodingBom::new(BomType::all(), Cursor::new(b"\xEF\xBB\xBFtext"));
+    let mut buf = [0; 1];
+    reader.read_exact(&mut buf).unwrap();
+    assert_eq!(7, reader.inner_mut().get_ref().len());
+    assert_eq!(BomStatus::Bom(BomType::UTF8), reader.status());
+    assert_eq!(b"ext", read_all(&mut reader).as_slice());
+}
+
+#[test]
+fn test_get_mut_keeps_state() {
+    // appending through get_mut is the deliberate way to continue a stream
+    let mut reader = SkipEncodingBom::new(BomType::all(), Cursor::new(b"\xEF\xBB".to_vec()));
+    assert_eq!(b"", read_all(&mut reader).as_slice());
+    reader.get_mut().get_mut().extend_from_slice(b"\xBFtext");
+    assert_eq!(b"text", read_all(&mut reader).as_slice());
+}
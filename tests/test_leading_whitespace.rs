@@ -0,0 +1,51 @@
+use skip_bom::*;
+use std::io::Read;
+
+fn lenient(leading_whitespace: usize) -> DetectOptions<'static> {
+    DetectOptions { leading_whitespace, ..Default::default() }
+}
+
+macro_rules! test_leading_whitespace {
+    ($test_fn_name:ident, $bytes:expr, $leading_whitespace:expr, $bom:expr, $skipped:expr, $fingerprint:expr) => {
+        #[test]
+        fn $test_fn_name() {
+            let report = detect(&$bytes[..], &lenient($leading_whitespace)).unwrap();
+            assert_eq!(($bom, $skipped), (report.bom, report.leading_whitespace));
+            assert_eq!($fingerprint, report.fingerprint);
+        }
+    };
+}
+
+test_leading_whitespace!(test_leading_whitespace_utf8, b"\r\n \xEF\xBB\xBFtext", 8, Some(BomType::UTF8), 3, "74657874");
+test_leading_whitespace!(test_leading_whitespace_utf16le, b"\n\xFF\xFEt\x00", 8, Some(BomType::UTF16LE), 1, "7400");
+test_leading_whitespace!(test_leading_whitespace_exact_limit, b"\t\t\xEF\xBB\xBFtext", 2, Some(BomType::UTF8), 2, "74657874");
+test_leading_whitespace!(test_leading_whitespace_over_limit, b"   \xEF\xBB\xBFtext", 2, None, 0, "202020efbbbf74657874");
+test_leading_whitespace!(test_leading_whitespace_strict, b" \xEF\xBB\xBFtext", 0, None, 0, "20efbbbf74657874");
+test_leading_whitespace!(test_leading_whitespace_no_bom, b"  text", 8, None, 0, "202074657874");
+test_leading_whitespace!(test_leading_whitespace_only, b"  \n", 8, None, 0, "20200a");
+test_leading_whitespace!(test_leading_whitespace_none, b"\xEF\xBB\xBFtext", 8, Some(BomType::UTF8), 0, "74657874");
+
+#[test]
+fn test_leading_whitespace_policy() {
+    let options = DetectOptions { policy: BomPolicy::Forbidden, ..lenient(4) };
+    assert!(detect(&b" \xEF\xBB\xBFtext"[..], &options).is_err());
+    assert!(detect(&b" \xEF\xBB\xBFtext"[..], &DetectOptions { policy: BomPolicy::Forbidden, ..Default::default() }).is_ok());
+}
+
+macro_rules! test_leading_whitespace_managed {
+    ($test_fn_name:ident, $bytes:expr, $action:expr, $content:expr) => {
+        #[test]
+        fn $test_fn_name() {
+            let policy = ReadPolicy::default().rule(PolicyCondition::Utf16, PolicyAction::Transcode);
+            let mut reader = ManagedReader::with_detect_options(&$bytes[..], &policy, &lenient(4)).unwrap();
+            let mut content = Vec::new();
+            let _ = reader.read_to_end(&mut content).unwrap();
+            assert_eq!($action, reader.action());
+            assert_eq!(&$content[..], content.as_slice());
+        }
+    };
+}
+
+test_leading_whitespace_managed!(test_leading_whitespace_managed_skip, b"\r\n\xEF\xBB\xBFtext", PolicyAction::Skip, b"text");
+test_leading_whitespace_managed!(test_leading_whitespace_managed_transcode, b" \xFF\xFEH\x00i\x00", PolicyAction::Transcode, b"Hi");
+test_leading_whitespace_managed!(test_leading_whitespace_managed_no_bom, b" text", PolicyAction::Skip, b" text");
@@ -0,0 +1,45 @@
+use skip_bom::*;
+use std::io::{Cursor, Read};
+
+fn chain_to_end<R: Read>(reader: SkipEncodingBom<R>) -> Vec<u8> {
+    let mut buf = Vec::new();
+    let _ = reader.leftover_chain().read_to_end(&mut buf).unwrap();
+    buf
+}
+
+#[test]
+fn test_leftover_chain_nothing_read() {
+    let reader = SkipEncodingBom::new(BomType::all(), Cursor::new(b"\xEF\xBB\xBFThis stream has a BOM."));
+    // the BOM was not read by the wrapper yet
+    assert_eq!(b"\xEF\xBB\xBFThis stream has a BOM.", chain_to_end(reader).as_slice());
+}
+
+#[test]
+fn test_leftover_chain_after_bom() {
+    let mut reader = SkipEncodingBom::new(BomType::all(), Cursor::new(b"\xEF\xBB\xBFThis stream has a BOM."));
+    assert_eq!(Some(BomType::UTF8), reader.read_bom().unwrap());
+    assert_eq!(b"This stream has a BOM.", chain_to_end(reader).as_slice());
+}
+
+#[test]
+fn test_leftover_chain_post_init_buffer() {
+    let mut reader = SkipEncodingBom::new(BomType::all(), Cursor::new(b"\xEF\xBBThis stream has no BOM."));
+    let mut small_buf = [0u8; 1];
+    assert_eq!(1, reader.read(&mut small_buf).unwrap());
+    assert_eq!(b"\xBBThis stream has no BOM.", chain_to_end(reader).as_slice());
+}
+
+#[test]
+fn test_leftover_chain_final() {
+    let mut reader = SkipEncodingBom::new(BomType::all(), Cursor::new(b"\xFF\xFEThis stream has a BOM."));
+    let mut small_buf = [0u8; 4];
+    reader.read_exact(&mut small_buf).unwrap();
+    assert_eq!(b" stream has a BOM.", chain_to_end(reader).as_slice());
+}
+
+#[test]
+fn test_leftover_chain_incomplete_bom() {
+    let mut reader = SkipEncodingBom::new(BomType::all(), Cursor::new(b"\xEF\xBB"));
+    assert_eq!(None, reader.read_bom().unwrap());
+    assert_eq!(b"\xEF\xBB", chain_to_end(reader).as_slice());
+}
@@ -0,0 +1,38 @@
+use skip_bom::*;
+use std::io::{BufRead, Cursor, Error, ErrorKind};
+
+fn stripped_lines(text: &[u8]) -> Vec<std::io::Result<String>> {
+    strip_bom_from_first_line(Cursor::new(text).lines()).collect()
+}
+
+#[test]
+fn test_strip_first_line_bom() {
+    let lines = stripped_lines(b"\xEF\xBB\xBFfirst\nsecond\n");
+    assert_eq!(vec!["first", "second"], lines.into_iter().map(Result::unwrap).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_strip_first_line_no_bom() {
+    let lines = stripped_lines(b"first\n\xEF\xBB\xBFsecond");
+    assert_eq!(vec!["first", "\u{FEFF}second"], lines.into_iter().map(Result::unwrap).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_strip_first_line_only_bom() {
+    let lines = stripped_lines(b"\xEF\xBB\xBF");
+    assert_eq!(vec![""], lines.into_iter().map(Result::unwrap).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_strip_first_line_empty() {
+    assert!(stripped_lines(b"").is_empty());
+}
+
+#[test]
+fn test_strip_first_line_error() {
+    let lines = vec![Err(Error::new(ErrorKind::InvalidData, "error")), Ok("\u{FEFF}second".to_owned())];
+    let mut stripped = strip_bom_from_first_line(lines);
+    assert_eq!(ErrorKind::InvalidData, stripped.next().unwrap().unwrap_err().kind());
+    assert_eq!("\u{FEFF}second", stripped.next().unwrap().unwrap());
+    assert!(stripped.next().is_none());
+}
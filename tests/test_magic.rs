@@ -0,0 +1,36 @@
+#![cfg(feature = "magic")]
+
+use skip_bom::magic::*;
+use skip_bom::BomType;
+
+macro_rules! test_find_signature {
+    ($test_fn_name:ident, $bytes:expr, $expected:expr) => {
+        #[test]
+        fn $test_fn_name() {
+            assert_eq!($expected, find_signature($bytes, BomType::all()));
+        }
+    };
+}
+
+test_find_signature!(test_find_signature_utf8, b"\xEF\xBB\xBFtext", Some(Signature::Bom(BomType::UTF8)));
+test_find_signature!(test_find_signature_utf32le, b"\xFF\xFE\x00\x00t\x00\x00\x00", Some(Signature::Bom(BomType::UTF32LE)));
+test_find_signature!(test_find_signature_utf16le_at_end, b"\xFF\xFE", Some(Signature::Bom(BomType::UTF16LE)));
+test_find_signature!(test_find_signature_gzip, b"\x1F\x8B\x08\x00\x00\x00\x00\x00", Some(Signature::Other("gzip")));
+test_find_signature!(test_find_signature_zip, b"PK\x03\x04\x14\x00\x00\x00", Some(Signature::Other("zip")));
+test_find_signature!(test_find_signature_empty_zip, b"PK\x05\x06\x00\x00\x00\x00", Some(Signature::Other("zip")));
+test_find_signature!(test_find_signature_pdf, b"%PDF-1.7\n", Some(Signature::Other("pdf")));
+test_find_signature!(test_find_signature_png, b"\x89PNG\r\n\x1A\n\x00\x00", Some(Signature::Other("png")));
+test_find_signature!(test_find_signature_truncated_png, b"\x89PNG", None);
+test_find_signature!(test_find_signature_text, b"PKZIP is a file archiver", None);
+test_find_signature!(test_find_signature_empty, b"", None);
+
+#[test]
+fn test_find_signature_disabled_bom() {
+    assert_eq!(None, find_signature(b"\xEF\xBB\xBFtext", &[BomType::UTF16LE]));
+}
+
+#[test]
+fn test_magic_probe_len() {
+    assert!(MAGIC_NUMBERS.iter().all(|(_, magic)| magic.len() <= MAGIC_PROBE_LEN));
+    assert!(BomType::max_probe_len(BomType::all()) <= MAGIC_PROBE_LEN);
+}
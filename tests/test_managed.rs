@@ -0,0 +1,135 @@
+use skip_bom::*;
+use std::io::{ErrorKind, Read};
+
+fn ingestion_policy() -> ReadPolicy {
+    ReadPolicy::default()
+        .rule(PolicyCondition::Utf16, PolicyAction::Transcode)
+        .rule(PolicyCondition::NoBom, PolicyAction::Skip)
+        .rule(PolicyCondition::Bom(BomType::SCSU), PolicyAction::Reject)
+}
+
+fn read_managed(bytes: &[u8], policy: &ReadPolicy) -> Result<(PolicyAction, Vec<u8>)> {
+    let mut reader = ManagedReader::new(bytes, policy)?;
+    let mut content = Vec::new();
+    let _ = reader.read_to_end(&mut content)?;
+    Ok((reader.action(), content))
+}
+
+macro_rules! test_managed {
+    ($name:ident, $bytes:expr, $action:expr, $content:expr) => {
+        #[test]
+        fn $name() {
+            let (action, content) = read_managed($bytes, &ingestion_policy()).unwrap();
+            assert_eq!($action, action);
+            assert_eq!(&$content[..], content.as_slice());
+        }
+    };
+}
+
+test_managed!(test_managed_utf16le, b"\xFF\xFEH\x00i\x00", PolicyAction::Transcode, b"Hi");
+test_managed!(test_managed_utf16be, b"\xFE\xFF\x00H\x00i", PolicyAction::Transcode, b"Hi");
+test_managed!(test_managed_no_bom, b"plain", PolicyAction::Skip, b"plain");
+test_managed!(test_managed_empty, b"", PolicyAction::Skip, b"");
+// no rule: the default action skips the BOM
+test_managed!(test_managed_utf8, b"\xEF\xBB\xBFtext", PolicyAction::Skip, b"text");
+test_managed!(test_managed_utf32_default, b"\xFF\xFE\x00\x00t\x00\x00\x00", PolicyAction::Skip, b"t\x00\x00\x00");
+
+#[test]
+fn test_managed_rejected() {
+    let error = read_managed(b"\x0E\xFE\xFFscsu", &ingestion_policy()).unwrap_err();
+    assert_eq!(ErrorKind::InvalidData, error.kind());
+    assert_eq!(Some(BomErrorCode::PolicyRejected), BomErrorCode::of(&error));
+    assert_eq!(Some(BomType::SCSU), BomError::of(&error).unwrap().bom_type());
+}
+
+#[test]
+fn test_managed_keep() {
+    let policy = ReadPolicy { default_action: PolicyAction::Keep, ..Default::default() };
+    let (action, content) = read_managed(b"\xEF\xBB\xBFtext", &policy).unwrap();
+    assert_eq!(PolicyAction::Keep, action);
+    assert_eq!(b"\xEF\xBB\xBFtext", content.as_slice());
+}
+
+#[test]
+fn test_managed_first_rule_applies() {
+    let policy = ReadPolicy::default().rule(PolicyCondition::AnyBom, PolicyAction::Keep).rule(PolicyCondition::Utf16, PolicyAction::Reject);
+    assert_eq!(PolicyAction::Keep, policy.action(Some(BomType::UTF16LE)));
+    assert_eq!(PolicyAction::Skip, policy.action(None));
+}
+
+#[test]
+fn test_managed_longer_than_sample() {
+    let mut bytes = vec![0xFF, 0xFE];
+    bytes.extend("long text ".repeat(1000).encode_utf16().flat_map(u16::to_le_bytes));
+    let options = DetectOptions { sample_len: 16, ..Default::default() };
+    let mut reader = ManagedReader::with_detect_options(bytes.as_slice(), &ingestion_policy(), &options).unwrap();
+    assert_eq!(16, reader.report().sample_len);
+    let mut text = String::new();
+    let _ = reader.read_to_string(&mut text).unwrap();
+    assert_eq!("long text ".repeat(1000), text);
+}
+
+#[test]
+fn test_managed_sample_of_bom_length() {
+    // the byte read to tell whether the stream ends with the sample is content
+    let options = DetectOptions { sample_len: 3, bom_only: BomOnlyPolicy::Error, ..Default::default() };
+    let mut reader = ManagedReader::with_detect_options(&b"\xEF\xBB\xBFtext"[..], &ReadPolicy::default(), &options).unwrap();
+    assert_eq!(Some(BomType::UTF8), reader.report().bom);
+    let mut text = String::new();
+    let _ = reader.read_to_string(&mut text).unwrap();
+    assert_eq!("text", text);
+    let error = ManagedReader::with_detect_options(&b"\xEF\xBB\xBF"[..], &ReadPolicy::default(), &options).err().unwrap();
+    assert_eq!(Some(BomErrorCode::BomOnly), BomErrorCode::of(&error));
+}
+
+#[test]
+fn test_policy_conditions() {
+    assert!(PolicyCondition::ExternalDecoder.matches(Some(BomType::BOCU1)));
+    assert!(!PolicyCondition::ExternalDecoder.matches(Some(BomType::UTF8)));
+    assert!(PolicyCondition::Utf32.matches(Some(BomType::UTF32BE)));
+    assert!(!PolicyCondition::AnyBom.matches(None));
+    assert!(PolicyCondition::Any.matches(None));
+    assert!(!PolicyCondition::NoBom.matches(Some(BomType::UTF8)));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_read_policy_from_toml() {
+    let policy: ReadPolicy = toml::from_str(r#"
+        default_action = "Keep"
+
+        [[rules]]
+        when = "Utf16"
+        action = "Transcode"
+
+        [[rules]]
+        when = { Bom = "SCSU" }
+        action = "Reject"
+    "#).unwrap();
+    assert_eq!(ingestion_policy().rules[0], policy.rules[0]);
+    assert_eq!(ingestion_policy().rules[2], policy.rules[1]);
+    assert_eq!(PolicyAction::Keep, policy.default_action);
+    assert_eq!(ReadPolicy::default(), toml::from_str("").unwrap());
+}
+
+macro_rules! test_into_utf8_string {
+    ($name:ident, $bytes:expr, $text:expr, $bom:expr) => {
+        #[test]
+        fn $name() {
+            let (text, report) = ManagedReader::new(&$bytes[..], &ingestion_policy()).unwrap().into_utf8_string().unwrap();
+            assert_eq!($text, text);
+            assert_eq!($bom, report.bom);
+        }
+    };
+}
+
+test_into_utf8_string!(test_into_utf8_string_utf16le, b"\xFF\xFEH\x00\xE9\x00", "H\u{E9}", Some(BomType::UTF16LE));
+test_into_utf8_string!(test_into_utf8_string_utf8, b"\xEF\xBB\xBFtext", "text", Some(BomType::UTF8));
+test_into_utf8_string!(test_into_utf8_string_no_bom, b"text", "text", None);
+
+#[test]
+fn test_into_utf8_string_invalid() {
+    // UTF-32 is not transcoded by the policy: its content is not UTF-8
+    let reader = ManagedReader::new(&b"\xFF\xFE\x00\x00\xE9\x00\x00\x00"[..], &ingestion_policy()).unwrap();
+    assert_eq!(ErrorKind::InvalidData, reader.into_utf8_string().unwrap_err().kind());
+}
@@ -0,0 +1,43 @@
+use skip_bom::*;
+use std::io::{Cursor, Read};
+
+/// Reader wrapper counting the bytes read.
+struct CountingReader<R> {
+    inner: R,
+    count: usize,
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let bytes_read = self.inner.read(buf)?;
+        self.count += bytes_read;
+        Ok(bytes_read)
+    }
+}
+
+#[test]
+fn test_map_inner_incomplete_bom() {
+    let mut reader = SkipEncodingBom::new(BomType::all(), Cursor::new(b"\xEF\xBB".to_vec()));
+    assert_eq!(None, reader.read_bom().unwrap());
+    let mut reader = reader.map_inner(|inner| CountingReader { inner, count: 0 });
+    reader.get_mut().inner.get_mut().extend_from_slice(b"\xBFThis stream has a BOM.");
+    let mut string = String::new();
+    let _ = reader.read_to_string(&mut string).unwrap();
+    assert_eq!("This stream has a BOM.", &string);
+    assert_eq!(Some(Some(BomType::UTF8)), reader.bom_found());
+    // the bytes buffered before the mapping were not read again
+    assert_eq!(b"\xBFThis stream has a BOM.".len(), reader.get_ref().count);
+}
+
+#[test]
+fn test_map_inner_post_init_buffer() {
+    let mut reader = SkipEncodingBom::new(BomType::all(), Cursor::new(b"\xEF\xBB\xBFThis stream has a BOM.".as_slice()));
+    let mut small_buf = [0u8; 1];
+    assert_eq!(1, reader.read(&mut small_buf).unwrap());
+    let mut reader = reader.map_inner(|inner| CountingReader { inner, count: 0 });
+    let mut string = String::new();
+    let _ = reader.read_to_string(&mut string).unwrap();
+    assert_eq!("his stream has a BOM.", &string);
+    assert_eq!(Some(Some(BomType::UTF8)), reader.bom_found());
+    assert_eq!(b"his stream has a BOM.".len(), reader.get_ref().count);
+}
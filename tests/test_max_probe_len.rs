@@ -0,0 +1,43 @@
+use skip_bom::*;
+use std::io::{Cursor, Read};
+
+fn test_probe(bytes: &[u8], bom_types: &[BomType], expected_probe_len: usize, expected_bom: Option<BomType>) {
+    let mut reader = SkipEncodingBom::new(bom_types, Cursor::new(bytes));
+    assert_eq!(expected_probe_len, reader.max_probe_len());
+    assert_eq!(expected_bom, reader.read_bom().unwrap());
+    // no byte past the probe was read from the underlying reader
+    assert_eq!(expected_probe_len.min(bytes.len()) as u64, reader.get_ref().position());
+    let mut buf = Vec::new();
+    let _ = reader.read_to_end(&mut buf).unwrap();
+    assert_eq!(&bytes[expected_bom.map_or(0, |bom_type| bom_type.bom_length())..], buf.as_slice());
+    // same with read_bom_from
+    let mut cursor = Cursor::new(bytes);
+    assert_eq!(expected_bom, read_bom_from(&mut cursor, bom_types).unwrap().0);
+    assert_eq!(expected_probe_len.min(bytes.len()) as u64, cursor.position());
+}
+
+macro_rules! test_probe {
+    ($test_fn_name:ident, $bytes:expr, $bom_types:expr, $expected_probe_len:expr, $expected_bom:expr) => {
+        #[test]
+        fn $test_fn_name() {
+            test_probe($bytes, $bom_types, $expected_probe_len, $expected_bom);
+        }
+    };
+}
+
+test_probe!(test_probe_utf8_only, b"\xEF\xBB\xBFabcd", &[BomType::UTF8], 3, Some(BomType::UTF8));
+test_probe!(test_probe_utf8_only_no_bom, b"abcd", &[BomType::UTF8], 3, None);
+test_probe!(test_probe_utf16_only, b"\xFF\xFEa\x00", &[BomType::UTF16LE, BomType::UTF16BE], 2, Some(BomType::UTF16LE));
+test_probe!(test_probe_windows_text, b"\xFF\xFEa\x00", &BomTypeSet::WINDOWS_TEXT, 3, Some(BomType::UTF16LE));
+test_probe!(test_probe_all, b"\xEF\xBB\xBFabcd", BomType::all(), 4, Some(BomType::UTF8));
+test_probe!(test_probe_none, b"\xEF\xBB\xBFabcd", &[], 0, None);
+
+#[test]
+fn test_probe_incremental() {
+    let mut reader = SkipEncodingBom::new(&[BomType::UTF8], Cursor::new(b"\xEF".to_vec()));
+    assert_eq!(None, reader.read_bom().unwrap());
+    assert_eq!(None, reader.bom_found());
+    reader.get_mut().get_mut().extend_from_slice(b"\xBB\xBFabcd");
+    assert_eq!(Some(BomType::UTF8), reader.read_bom().unwrap());
+    assert_eq!(3, reader.get_ref().position());
+}
@@ -0,0 +1,41 @@
+#![cfg(all(feature = "encoding_rs", feature = "encoding_rs_io"))]
+
+use skip_bom::*;
+use std::io::{ErrorKind, Read};
+
+fn decode(content_type: &str, bytes: &[u8]) -> std::io::Result<(CharsetReconciliation, String)> {
+    let (reconciliation, mut reader) = decode_mime_part(content_type, bytes)?;
+    let mut text = String::new();
+    let _ = reader.read_to_string(&mut text)?;
+    Ok((reconciliation, text))
+}
+
+macro_rules! test_mime_part {
+    ($name:ident, $content_type:expr, $bytes:expr, $reconciliation:expr, $text:expr) => {
+        #[test]
+        fn $name() {
+            let (reconciliation, text) = decode($content_type, $bytes).unwrap();
+            assert_eq!($reconciliation, reconciliation);
+            assert_eq!($text, text);
+        }
+    };
+}
+
+test_mime_part!(test_mime_part_agree, "text/plain; charset=\"UTF-8\"", b"\xEF\xBB\xBFcaf\xC3\xA9", CharsetReconciliation::Agree(BomType::UTF8), "café");
+test_mime_part!(test_mime_part_utf16_agree, "text/plain; charset=utf-16", b"\xFE\xFF\x00H\x00i", CharsetReconciliation::Agree(BomType::UTF16BE), "Hi");
+// the BOM wins over the declared charset
+test_mime_part!(test_mime_part_conflict, "text/plain; charset=windows-1252", b"\xEF\xBB\xBFcaf\xC3\xA9", CharsetReconciliation::Conflict(BomType::UTF8), "café");
+test_mime_part!(test_mime_part_gb18030, "text/plain; charset=utf-8", b"\x84\x31\x95\x33\xD2\xBB", CharsetReconciliation::Conflict(BomType::GB1803), "\u{4E00}");
+// without a BOM, the declared charset applies
+test_mime_part!(test_mime_part_declared, "text/plain; charset=windows-1252", b"caf\xE9", CharsetReconciliation::Unknown, "café");
+test_mime_part!(test_mime_part_declared_utf16le, "utf-16le", b"H\x00i\x00", CharsetReconciliation::Unknown, "Hi");
+test_mime_part!(test_mime_part_no_charset, "text/plain", b"plain", CharsetReconciliation::Unknown, "plain");
+test_mime_part!(test_mime_part_unknown_charset, "text/plain; charset=x-made-up", b"plain", CharsetReconciliation::Unknown, "plain");
+test_mime_part!(test_mime_part_empty, "text/plain; charset=utf-8", b"", CharsetReconciliation::Unknown, "");
+
+#[test]
+fn test_mime_part_decoder_unavailable() {
+    let error = decode("text/plain; charset=utf-32", b"\xFF\xFE\x00\x00t\x00\x00\x00").unwrap_err();
+    assert_eq!(ErrorKind::InvalidData, error.kind());
+    assert_eq!(Some(BomErrorCode::DecoderUnavailable), BomErrorCode::of(&error));
+}
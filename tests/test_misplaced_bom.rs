@@ -0,0 +1,82 @@
+use skip_bom::*;
+use std::sync::atomic::AtomicBool;
+
+macro_rules! test_find_misplaced_bom {
+    ($test_fn_name:ident, $bytes:expr, $window:expr, $expected:expr) => {
+        #[test]
+        fn $test_fn_name() {
+            assert_eq!($expected, find_misplaced_bom($bytes, $window));
+        }
+    };
+}
+
+test_find_misplaced_bom!(test_find_misplaced_bom_first_token, b"@e\xEF\xBB\xBFcho off", 8, Some(2));
+test_find_misplaced_bom!(test_find_misplaced_bom_window_edge, b"param\xEF\xBB\xBF(", 5, Some(5));
+test_find_misplaced_bom!(test_find_misplaced_bom_outside_window, b"param\xEF\xBB\xBF(", 4, None);
+test_find_misplaced_bom!(test_find_misplaced_bom_at_start, b"\xEF\xBB\xBFparam(", 8, None);
+test_find_misplaced_bom!(test_find_misplaced_bom_after_non_ascii, b"\xC3\xA9\xEF\xBB\xBF", 8, None);
+test_find_misplaced_bom!(test_find_misplaced_bom_incomplete, b"rem\xEF\xBB", 8, None);
+test_find_misplaced_bom!(test_find_misplaced_bom_none, b"@echo off", 8, None);
+test_find_misplaced_bom!(test_find_misplaced_bom_disabled, b"@e\xEF\xBB\xBFcho off", 0, None);
+test_find_misplaced_bom!(test_find_misplaced_bom_empty, b"", 8, None);
+
+fn copy_repaired(bytes: &[u8], bom_types: &[BomType], window: usize) -> (Vec<u8>, u64, Option<BomType>) {
+    let mut output = Vec::new();
+    let options = CopyOptions { misplaced_bom_window: window, ..Default::default() };
+    let (written, bom) = copy_without_bom(bom_types, bytes, &mut output, &options).unwrap();
+    (output, written, bom)
+}
+
+macro_rules! test_copy_misplaced_bom {
+    ($test_fn_name:ident, $bytes:expr, $window:expr, $expected:expr, $expected_bom:expr) => {
+        #[test]
+        fn $test_fn_name() {
+            let expected: &[u8] = $expected;
+            assert_eq!((expected.to_vec(), expected.len() as u64, $expected_bom), copy_repaired($bytes, BomType::all(), $window));
+        }
+    };
+}
+
+test_copy_misplaced_bom!(test_copy_misplaced_bom_removed, b"@e\xEF\xBB\xBFcho off", 8, b"@echo off", Some(BomType::UTF8));
+test_copy_misplaced_bom!(test_copy_misplaced_bom_outside_window, b"@echo\xEF\xBB\xBF off", 4, b"@echo\xEF\xBB\xBF off", None);
+test_copy_misplaced_bom!(test_copy_misplaced_bom_disabled, b"@e\xEF\xBB\xBFcho off", 0, b"@e\xEF\xBB\xBFcho off", None);
+test_copy_misplaced_bom!(test_copy_misplaced_bom_only_first, b"\xEF\xBB\xBFa\xEF\xBB\xBF", 8, b"a\xEF\xBB\xBF", Some(BomType::UTF8));
+test_copy_misplaced_bom!(test_copy_misplaced_bom_utf16, b"\xFF\xFEa\x00", 8, b"a\x00", Some(BomType::UTF16LE));
+test_copy_misplaced_bom!(test_copy_misplaced_bom_short, b"ab", 8, b"ab", None);
+test_copy_misplaced_bom!(test_copy_misplaced_bom_empty, b"", 8, b"", None);
+
+#[test]
+fn test_copy_misplaced_bom_large() {
+    let mut bytes = b"#\xEF\xBB\xBF".to_vec();
+    bytes.extend((0..200_000).map(|i| (i % 128) as u8));
+    let (output, written, bom) = copy_repaired(&bytes, BomType::all(), 4);
+    assert_eq!((bytes.len() as u64 - 3, Some(BomType::UTF8)), (written, bom));
+    assert_eq!(b"#", &output[..1]);
+    assert_eq!(&bytes[4..], &output[1..]);
+}
+
+#[test]
+fn test_copy_misplaced_bom_types() {
+    // only UTF-8 BOMs are repaired, and only if they are looked for
+    let bytes = b"@e\xEF\xBB\xBFcho off";
+    assert_eq!((bytes.to_vec(), bytes.len() as u64, None), copy_repaired(bytes, &[BomType::UTF16LE], 8));
+}
+
+#[test]
+fn test_copy_misplaced_bom_cancelled() {
+    let cancel = AtomicBool::new(true);
+    let options = CopyOptions { cancel: Some(&cancel), misplaced_bom_window: 8, ..Default::default() };
+    let mut output = Vec::new();
+    let error = copy_without_bom(BomType::all(), &b"@e\xEF\xBB\xBFcho off"[..], &mut output, &options).unwrap_err();
+    assert_eq!(Some(BomErrorCode::Cancelled), BomErrorCode::of(&error));
+    assert!(output.is_empty());
+}
+
+#[test]
+fn test_copy_misplaced_bom_window_clamped() {
+    let bytes = b"@e\xEF\xBB\xBFcho off";
+    assert_eq!((b"@echo off".to_vec(), 9, Some(BomType::UTF8)), copy_repaired(bytes, BomType::all(), usize::MAX));
+    let mut bytes = vec![b'#'; MAX_MISPLACED_BOM_WINDOW + 1];
+    bytes.extend_from_slice(b"\xEF\xBB\xBF");
+    assert_eq!((bytes.clone(), bytes.len() as u64, None), copy_repaired(&bytes, BomType::all(), usize::MAX));
+}
@@ -0,0 +1,57 @@
+use skip_bom::BomType;
+use std::io::{Read, Write};
+
+#[test]
+fn test_module_layout_reader() {
+    let mut reader = skip_bom::reader::SkipEncodingBom::new(BomType::all(), &b"\xEF\xBB\xBFcontent"[..]);
+    let mut content = Vec::new();
+    let _ = reader.read_to_end(&mut content).unwrap();
+    assert_eq!(b"content", content.as_slice());
+    let policy = skip_bom::reader::ReadPolicy::default().rule(skip_bom::reader::PolicyCondition::NoBom, skip_bom::reader::PolicyAction::Reject);
+    assert!(skip_bom::reader::ManagedReader::new(&b"content"[..], &policy).is_err());
+}
+
+#[test]
+fn test_module_layout_writer() {
+    let mut writer = skip_bom::writer::StripBomWriter::new(BomType::all(), Vec::new());
+    writer.write_all(b"\xFF\xFEt\x00").unwrap();
+    assert_eq!(b"t\x00", writer.finish().unwrap().as_slice());
+}
+
+#[test]
+fn test_module_layout_detect() {
+    let report = skip_bom::detect::detect(&b"\xFE\xFF\x00H"[..], &skip_bom::detect::DetectOptions::default()).unwrap();
+    assert_eq!(Some(BomType::UTF16BE), report.bom);
+    let report = skip_bom::detect::preflight_reader(&b"\xEF\xBB\xBFa\r\nb\r\n"[..], &skip_bom::detect::DetectOptions::default()).unwrap();
+    assert_eq!(Some(BomType::UTF8), report.encoding.bom);
+}
+
+#[test]
+fn test_module_layout_decode() {
+    let mut text = String::new();
+    let _ = skip_bom::decode::DecodeReader::new(BomType::all(), &b"\xFF\xFEH\x00i\x00"[..]).read_to_string(&mut text).unwrap();
+    assert_eq!("Hi", text);
+}
+
+#[test]
+fn test_module_layout_fs() {
+    use skip_bom::fs::PathExt;
+
+    assert!(std::path::Path::new("tests/missing.txt").read_text_no_bom().is_err());
+    let _ = skip_bom::fs::StripFileOptions::default();
+}
+
+#[test]
+fn test_module_layout_root_compatibility() {
+    // the items stay available at the root of the crate
+    assert_eq!(skip_bom::reader::SkipEncodingBom::new(BomType::all(), &b""[..]).bom_found(), skip_bom::SkipEncodingBom::new(BomType::all(), &b""[..]).bom_found());
+    let report: skip_bom::detect::EncodingReport = skip_bom::detect(&b"\xEF\xBB\xBF"[..], &skip_bom::DetectOptions::default()).unwrap();
+    assert_eq!(Some(BomType::UTF8), report.bom);
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn test_module_layout_asynchronous() {
+    let mut reader = skip_bom::asynchronous::AsyncSkipEncodingBom::new(BomType::all(), &b"\xEF\xBB\xBFcontent"[..]);
+    assert_eq!(Some(BomType::UTF8), reader.read_bom().await.unwrap());
+}
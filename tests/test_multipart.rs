@@ -0,0 +1,80 @@
+use skip_bom::*;
+use std::io::{ErrorKind, Read};
+
+fn upload_policy() -> ReadPolicy {
+    ReadPolicy::default()
+        .rule(PolicyCondition::Utf16, PolicyAction::Transcode)
+        .rule(PolicyCondition::ExternalDecoder, PolicyAction::Reject)
+}
+
+/// Field name, bytes, content read and BOM of a part.
+type Part = (&'static str, &'static [u8], &'static [u8], Option<BomType>);
+
+const PARTS: &[Part] = &[
+    ("title", b"\xEF\xBB\xBFtitle", b"title", Some(BomType::UTF8)),
+    ("notes", b"\xFE\xFF\x00H\x00i", b"Hi", Some(BomType::UTF16BE)),
+    ("raw", b"\x00\x01binary", b"\x00\x01binary", None),
+    ("empty", b"", b"", None),
+];
+
+#[test]
+fn test_multipart_sanitize() {
+    let mut sanitizer = MultipartSanitizer::new(upload_policy());
+    for (field_name, bytes, content, _) in PARTS {
+        let mut read = Vec::new();
+        let _ = sanitizer.sanitize(field_name, *bytes).unwrap().read_to_end(&mut read).unwrap();
+        assert_eq!(content, &read.as_slice());
+    }
+    let reports = sanitizer.into_reports();
+    assert_eq!(PARTS.len(), reports.len());
+    for (field_name, _, _, bom) in PARTS {
+        assert_eq!(*bom, reports[*field_name].bom);
+    }
+}
+
+#[test]
+fn test_multipart_repeated_field() {
+    let mut sanitizer = MultipartSanitizer::new(upload_policy());
+    let _ = sanitizer.sanitize("files", &b"\xEF\xBB\xBFfirst"[..]).unwrap();
+    let _ = sanitizer.sanitize("files", &b"\xFF\xFEs\x00"[..]).unwrap();
+    assert_eq!(1, sanitizer.reports().len());
+    assert_eq!(Some(BomType::UTF16LE), sanitizer.reports()["files"].bom);
+}
+
+#[test]
+fn test_multipart_rejected() {
+    let mut sanitizer = MultipartSanitizer::new(upload_policy());
+    let error = sanitizer.sanitize("scsu", &b"\x0E\xFE\xFFscsu"[..]).unwrap_err();
+    assert_eq!(ErrorKind::InvalidData, error.kind());
+    assert_eq!(Some(BomErrorCode::PolicyRejected), BomErrorCode::of(&error));
+    assert!(sanitizer.reports().is_empty());
+}
+
+#[test]
+fn test_multipart_detect_options() {
+    let options = DetectOptions { binary: BinaryPolicy::Error, ..Default::default() };
+    let mut sanitizer = MultipartSanitizer::with_detect_options(upload_policy(), options);
+    assert!(sanitizer.sanitize("raw", &b"\x00\x01binary"[..]).is_err());
+    assert!(sanitizer.sanitize("title", &b"title"[..]).is_ok());
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn test_multipart_sanitize_async() {
+    use tokio::io::AsyncReadExt;
+
+    let options = DetectOptions { sample_len: 4, ..Default::default() };
+    let mut sanitizer = MultipartSanitizer::with_detect_options(upload_policy(), options);
+    let long_utf16 = b"\xFF\xFEl\x00o\x00n\x00g\x00";
+    let long_utf8 = b"\xEF\xBB\xBFlong part";
+    let parts: &[(&str, &[u8], &[u8])] = &[("utf16", long_utf16, b"long"), ("utf8", long_utf8, b"long part"), ("empty", b"", b"")];
+    for (field_name, bytes, content) in parts {
+        let mut read = Vec::new();
+        let _ = sanitizer.sanitize_async(field_name, *bytes).await.unwrap().read_to_end(&mut read).await.unwrap();
+        assert_eq!(content, &read.as_slice());
+    }
+    assert_eq!(Some(BomType::UTF16LE), sanitizer.reports()["utf16"].bom);
+    assert_eq!(Some(BomType::UTF8), sanitizer.reports()["utf8"].bom);
+    assert_eq!(None, sanitizer.reports()["empty"].bom);
+    assert!(sanitizer.sanitize_async("scsu", &b"\x0E\xFE\xFFscsu"[..]).await.is_err());
+}
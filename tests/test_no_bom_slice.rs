@@ -0,0 +1,31 @@
+use skip_bom::*;
+
+macro_rules! test_no_bom_slice {
+    ($test_fn_name:ident, $bytes:expr, $expected_bom:expr, $expected_content:expr) => {
+        #[test]
+        fn $test_fn_name() {
+            let slice = NoBomSlice::new(BomType::all(), $bytes);
+            assert_eq!($expected_bom, slice.bom());
+            assert_eq!(&$expected_content[..], &*slice);
+            assert_eq!(&$expected_content[..], slice.as_ref());
+            assert_eq!(&$expected_content[..], slice.content());
+        }
+    };
+}
+
+test_no_bom_slice!(test_no_bom_slice_utf8, b"\xEF\xBB\xBFtext", Some(BomType::UTF8), b"text");
+test_no_bom_slice!(test_no_bom_slice_utf32le, b"\xFF\xFE\x00\x00t\x00\x00\x00", Some(BomType::UTF32LE), b"t\x00\x00\x00");
+test_no_bom_slice!(test_no_bom_slice_utf16le_at_end, b"\xFF\xFE\x00", Some(BomType::UTF16LE), b"\x00");
+test_no_bom_slice!(test_no_bom_slice_no_bom, b"text", None, b"text");
+test_no_bom_slice!(test_no_bom_slice_partial_bom, b"\xEF\xBB", None, b"\xEF\xBB");
+test_no_bom_slice!(test_no_bom_slice_empty, b"", None, b"");
+
+#[test]
+fn test_no_bom_slice_zero_copy() {
+    let bytes = b"\xEF\xBB\xBF{\"key\": 1}".to_vec();
+    let slice = NoBomSlice::new(BomType::all(), &bytes);
+    assert_eq!(bytes[3..].as_ptr(), slice.as_ptr());
+    // the content is borrowed from the input rather than from the view
+    let content = NoBomSlice::new(BomType::all(), &bytes).content();
+    assert_eq!(std::str::from_utf8(content).unwrap(), "{\"key\": 1}");
+}
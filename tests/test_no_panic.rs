@@ -0,0 +1,168 @@
+use skip_bom::*;
+use std::io::{Error, ErrorKind, Read};
+use std::panic::{catch_unwind, RefUnwindSafe};
+
+/// Misbehavior of a test reader.
+#[derive(Debug, Clone, Copy)]
+enum Misbehavior {
+    None,
+    /// Return at most one byte per read.
+    ByteByByte,
+    /// Fail every other read with an interruption.
+    Interrupted,
+    /// Fail after the first read.
+    Error,
+    /// Claim one more byte than the buffer can hold.
+    OverReport,
+}
+
+const MISBEHAVIORS: &[Misbehavior] = &[Misbehavior::None, Misbehavior::ByteByByte, Misbehavior::Interrupted, Misbehavior::Error, Misbehavior::OverReport];
+
+struct TestReader<'a> {
+    bytes: &'a [u8],
+    misbehavior: Misbehavior,
+    reads: usize,
+}
+
+impl<'a> TestReader<'a> {
+    fn new(bytes: &'a [u8], misbehavior: Misbehavior) -> Self {
+        TestReader { bytes, misbehavior, reads: 0 }
+    }
+}
+
+impl Read for TestReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.reads += 1;
+        let max_count = match self.misbehavior {
+            Misbehavior::Interrupted if self.reads.is_multiple_of(2) => return Err(Error::new(ErrorKind::Interrupted, "interrupted")),
+            Misbehavior::Error if self.reads > 1 => return Err(Error::new(ErrorKind::InvalidData, "error")),
+            Misbehavior::ByteByByte => 1,
+            _ => usize::MAX,
+        };
+        let count = buf.len().min(self.bytes.len()).min(max_count);
+        buf[..count].copy_from_slice(&self.bytes[..count]);
+        self.bytes = &self.bytes[count..];
+        match self.misbehavior {
+            Misbehavior::OverReport => Ok(buf.len() + 1),
+            _ => Ok(count),
+        }
+    }
+}
+
+/// Bytes from which the test inputs are built: the first bytes of every BOM, and a few others.
+fn alphabet() -> Vec<u8> {
+    let mut alphabet: Vec<u8> = BomType::all().iter().flat_map(|bom_type| bom_type.bom_bytes().iter().copied()).collect();
+    alphabet.extend(b"a\x01\x80\xD8\xDC");
+    alphabet.sort_unstable();
+    alphabet.dedup();
+    alphabet
+}
+
+/// Every input of up to 3 bytes from the alphabet, every BOM followed by every byte of the alphabet, and a few longer inputs.
+fn inputs() -> Vec<Vec<u8>> {
+    let alphabet = alphabet();
+    let mut inputs = vec![Vec::new()];
+    let mut previous_length_inputs = vec![Vec::new()];
+    for _ in 0..3 {
+        previous_length_inputs = previous_length_inputs
+            .iter()
+            .flat_map(|input| alphabet.iter().map(move |byte| [input.as_slice(), &[*byte]].concat()))
+            .collect();
+        inputs.extend(previous_length_inputs.iter().cloned());
+    }
+    for bom_type in BomType::all() {
+        for byte in &alphabet {
+            inputs.push([bom_type.bom_bytes(), &[*byte]].concat());
+            inputs.push([bom_type.bom_bytes(), &[*byte, *byte, 0xDC, *byte, b'a']].concat());
+        }
+    }
+    inputs
+}
+
+/// Read until the end of the stream, an error or too many reads, with buffers of `chunk_len` bytes.
+fn read_chunks<R: Read>(reader: &mut R, chunk_len: usize) {
+    let mut buf = vec![0u8; chunk_len];
+    for _ in 0..64 {
+        match reader.read(&mut buf) {
+            Ok(0) if chunk_len > 0 => break,
+            Ok(_) => (),
+            Err(e) if e.kind() == ErrorKind::Interrupted => (),
+            Err(_) => break,
+        }
+    }
+}
+
+fn for_each_case(f: impl Fn(&[u8], Misbehavior, usize) + RefUnwindSafe) {
+    let previous_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(|_| ()));
+    let mut panicking_case = None;
+    'cases: for input in inputs() {
+        for misbehavior in MISBEHAVIORS {
+            for chunk_len in 0..=5 {
+                if catch_unwind(|| f(&input, *misbehavior, chunk_len)).is_err() {
+                    panicking_case = Some((input.clone(), *misbehavior, chunk_len));
+                    break 'cases;
+                }
+            }
+        }
+    }
+    std::panic::set_hook(previous_hook);
+    if let Some((input, misbehavior, chunk_len)) = panicking_case {
+        // run the case again with the panic hook restored to report the panic message
+        f(&input, misbehavior, chunk_len);
+        panic!("panic for {:02X?} with {:?} and {} byte chunks", input, misbehavior, chunk_len);
+    }
+}
+
+#[test]
+fn test_no_panic_skip_encoding_bom() {
+    for_each_case(|input, misbehavior, chunk_len| {
+        let mut reader = SkipEncodingBom::new(BomType::all(), TestReader::new(input, misbehavior));
+        read_chunks(&mut reader, chunk_len);
+        let _ = reader.bom_found();
+        let mut reader = SkipEncodingBom::new(BomType::all(), TestReader::new(input, misbehavior));
+        let _ = reader.read_bom();
+        let _ = reader.read_exact(&mut vec![0u8; chunk_len]);
+        let _ = reader.leftover_chain().read(&mut [0u8; 8]);
+    });
+}
+
+#[test]
+fn test_no_panic_decode_reader() {
+    for_each_case(|input, misbehavior, chunk_len| {
+        let mut reader = DecodeReader::new(BomType::all(), TestReader::new(input, misbehavior));
+        read_chunks(&mut reader, chunk_len);
+    });
+}
+
+#[test]
+fn test_no_panic_tee() {
+    for_each_case(|input, misbehavior, chunk_len| {
+        let mut reader = SkipEncodingBom::new_tee(BomType::all(), TestReader::new(input, misbehavior), Vec::new());
+        read_chunks(&mut reader, chunk_len);
+    });
+}
+
+#[test]
+fn test_no_panic_read_bom_from() {
+    for_each_case(|input, misbehavior, _| {
+        let _ = read_bom_from(&mut TestReader::new(input, misbehavior), BomType::all());
+    });
+}
+
+#[test]
+fn test_no_panic_detect() {
+    for_each_case(|input, misbehavior, chunk_len| {
+        let options = DetectOptions { sample_len: chunk_len, bom_only: BomOnlyPolicy::Report, ..Default::default() };
+        let _ = detect(TestReader::new(input, misbehavior), &options);
+        let _ = detect(TestReader::new(input, misbehavior), &DetectOptions::default());
+    });
+}
+
+#[test]
+fn test_no_panic_text_sanitizer() {
+    for_each_case(|input, misbehavior, chunk_len| {
+        let mut reader = TextSanitizer::new(BomType::all(), TestReader::new(input, misbehavior));
+        read_chunks(&mut reader, chunk_len);
+    });
+}
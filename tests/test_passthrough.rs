@@ -0,0 +1,46 @@
+use skip_bom::*;
+use std::io::Read;
+
+/// Reader recording the length of the buffers it is given.
+struct RecordingReader<'a> {
+    bytes: &'a [u8],
+    buffer_lengths: Vec<usize>,
+}
+
+impl Read for RecordingReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.buffer_lengths.push(buf.len());
+        let count = buf.len().min(self.bytes.len());
+        buf[..count].copy_from_slice(&self.bytes[..count]);
+        self.bytes = &self.bytes[count..];
+        Ok(count)
+    }
+}
+
+fn test_passthrough(bytes: &[u8]) {
+    let mut reader = SkipEncodingBom::new(BomType::all(), RecordingReader { bytes, buffer_lengths: Vec::new() });
+    let mut buf = [0u8; 16];
+    // resolve the BOM and return the bytes buffered after it
+    for _ in 0..BomBytesPushBuffer::CAPACITY {
+        assert_eq!(1, reader.read(&mut buf[..1]).unwrap());
+    }
+    reader.get_mut().buffer_lengths.clear();
+    // every read is now passed directly to the underlying reader
+    for len in [16, 3, 0, 7] {
+        let _ = reader.read(&mut buf[..len]).unwrap();
+    }
+    assert_eq!(vec![16, 3, 0, 7], reader.get_ref().buffer_lengths);
+}
+
+macro_rules! test_passthrough {
+    ($test_fn_name:ident, $bytes:expr) => {
+        #[test]
+        fn $test_fn_name() {
+            test_passthrough($bytes);
+        }
+    };
+}
+
+test_passthrough!(test_passthrough_utf8_bom, b"\xEF\xBB\xBFThis stream has a BOM and enough bytes to read.");
+test_passthrough!(test_passthrough_no_bom, b"This stream has no BOM but enough bytes to read.");
+test_passthrough!(test_passthrough_utf16le_bom, b"\xFF\xFEa\x00b\x00c\x00d\x00e\x00f\x00g\x00h\x00i\x00j\x00k\x00l\x00m\x00n\x00");
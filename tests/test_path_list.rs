@@ -0,0 +1,46 @@
+use skip_bom::*;
+use std::path::PathBuf;
+
+fn utf16le_bom(string: &str) -> Vec<u8> {
+    let mut bytes = vec![0xFF, 0xFE];
+    bytes.extend(string.encode_utf16().flat_map(u16::to_le_bytes));
+    bytes
+}
+
+fn paths(bytes: &[u8]) -> Vec<PathBuf> {
+    path_lines(bytes).collect::<std::io::Result<_>>().unwrap()
+}
+
+macro_rules! test_path_lines {
+    ($test_fn_name:ident, $bytes:expr, $expected:expr) => {
+        #[test]
+        fn $test_fn_name() {
+            let expected: Vec<PathBuf> = $expected.iter().map(PathBuf::from).collect();
+            assert_eq!(expected, paths(&$bytes));
+        }
+    };
+}
+
+test_path_lines!(test_path_lines_utf8_bom, *b"\xEF\xBB\xBFa.obj\nb.obj\n", ["a.obj", "b.obj"]);
+test_path_lines!(test_path_lines_utf16le_bom, utf16le_bom("a.obj\r\nb.obj\r\n"), ["a.obj", "b.obj"]);
+test_path_lines!(test_path_lines_no_bom, *b"a.obj\nb.obj", ["a.obj", "b.obj"]);
+test_path_lines!(test_path_lines_whitespace, *b"  a.obj\t\r\n\r\n   \r\nb.obj  \n", ["a.obj", "b.obj"]);
+test_path_lines!(test_path_lines_quoted, *b"\"dir with spaces\\a.obj\"\n\"\"\n\"\n", ["dir with spaces\\a.obj", "\""]);
+test_path_lines!(test_path_lines_stray_bom, *b"a.obj\n\xEF\xBB\xBFb.obj\n", ["a.obj", "b.obj"]);
+test_path_lines!(test_path_lines_empty, *b"", [] as [&str; 0]);
+
+#[test]
+fn test_path_lines_invalid_utf8() {
+    let mut lines = path_lines(&b"a.obj\n\xFF\n"[..]);
+    assert!(lines.next().unwrap().is_ok());
+    assert!(lines.next().unwrap().is_err());
+}
+
+#[test]
+fn test_read_path_list() {
+    let path = std::env::temp_dir().join(format!("skip_bom_{}_test_read_path_list", std::process::id()));
+    std::fs::write(&path, utf16le_bom("a.obj\r\nb.obj\r\n")).unwrap();
+    let list = read_path_list(&path);
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(vec![PathBuf::from("a.obj"), PathBuf::from("b.obj")], list.unwrap());
+}
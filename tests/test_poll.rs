@@ -0,0 +1,283 @@
+#![cfg(feature = "tokio")]
+
+use skip_bom::*;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::io::{AsyncRead, ReadBuf};
+
+/// Reader returning at most `chunk_len` bytes per poll, and [`Poll::Pending`] before each chunk.
+struct PendingReader<'b> {
+    bytes: &'b [u8],
+    chunk_len: usize,
+    ready: bool,
+}
+
+impl AsyncRead for PendingReader<'_> {
+    fn poll_read(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<std::io::Result<()>> {
+        if !self.ready {
+            self.ready = true;
+            cx.waker().wake_by_ref();
+            return Poll::Pending;
+        }
+        self.ready = false;
+        let len = self.chunk_len.min(buf.remaining()).min(self.bytes.len());
+        buf.put_slice(&self.bytes[..len]);
+        self.bytes = &self.bytes[len..];
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// Future polling a closure.
+struct PollFn<F>(F);
+
+impl<T, F: FnMut(&mut Context<'_>) -> Poll<T> + Unpin> Future for PollFn<F> {
+    type Output = T;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        (self.0)(cx)
+    }
+}
+
+fn poll_fn<T, F: FnMut(&mut Context<'_>) -> Poll<T> + Unpin>(f: F) -> PollFn<F> {
+    PollFn(f)
+}
+
+/// Read the whole content after the BOM with `poll_skip_bom`.
+async fn read_content(reader: &mut PendingReader<'_>, state: &mut BomPollState<'_>, buf_len: usize) -> Vec<u8> {
+    let mut content = Vec::new();
+    loop {
+        let mut bytes = vec![0; buf_len];
+        let filled = poll_fn(|cx| {
+            let mut buf = ReadBuf::new(&mut bytes);
+            poll_skip_bom(cx, Pin::new(&mut *reader), state, &mut buf).map_ok(|()| buf.filled().len())
+        })
+        .await
+        .unwrap();
+        if filled == 0 {
+            return content;
+        }
+        content.extend_from_slice(&bytes[..filled]);
+    }
+}
+
+macro_rules! test_poll_skip_bom {
+    ($name:ident, $bytes:expr, $content:expr, $bom:expr) => {
+        #[tokio::test]
+        async fn $name() {
+            let bytes: &[u8] = $bytes;
+            for chunk_len in 1..=bytes.len().max(1) {
+                for buf_len in 1..=4 {
+                    let mut reader = PendingReader { bytes, chunk_len, ready: false };
+                    let mut state = BomPollState::new(BomType::all());
+                    assert_eq!(&$content[..], read_content(&mut reader, &mut state, buf_len).await.as_slice());
+                    assert_eq!(Some($bom), state.bom_found());
+                    assert!(state.is_resolved());
+                }
+            }
+        }
+    };
+}
+
+test_poll_skip_bom!(test_poll_skip_bom_utf8, b"\xEF\xBB\xBFtext", b"text", Some(BomType::UTF8));
+test_poll_skip_bom!(test_poll_skip_bom_utf32le, b"\xFF\xFE\x00\x00t\x00\x00\x00", b"t\x00\x00\x00", Some(BomType::UTF32LE));
+test_poll_skip_bom!(test_poll_skip_bom_utf16le_at_end, b"\xFF\xFE", b"", Some(BomType::UTF16LE));
+test_poll_skip_bom!(test_poll_skip_bom_no_bom, b"text", b"text", None);
+test_poll_skip_bom!(test_poll_skip_bom_incomplete, b"\xEF\xBB", b"\xEF\xBB", None);
+test_poll_skip_bom!(test_poll_skip_bom_empty, b"", b"", None);
+
+#[tokio::test]
+async fn test_poll_skip_bom_cancelled() {
+    // a read cancelled while the BOM is being detected loses no byte: the start bytes are kept in the state
+    let mut reader = PendingReader { bytes: b"\xEF\xBB\xBFtext", chunk_len: 1, ready: true };
+    let mut state = BomPollState::new(BomType::all());
+    let mut bytes = [0; 8];
+    let polled = poll_fn(|cx| {
+        let mut buf = ReadBuf::new(&mut bytes);
+        Poll::Ready(poll_skip_bom(cx, Pin::new(&mut reader), &mut state, &mut buf).is_pending())
+    })
+    .await;
+    assert!(polled);
+    assert_eq!(None, state.bom_found());
+    assert_eq!(b"text", read_content(&mut reader, &mut state, 8).await.as_slice());
+    assert_eq!(Some(Some(BomType::UTF8)), state.bom_found());
+}
+
+#[tokio::test]
+async fn test_poll_skip_bom_select() {
+    let mut reader = PendingReader { bytes: b"\xFF\xFEt\x00e\x00", chunk_len: 1, ready: false };
+    let mut state = BomPollState::new(BomType::all());
+    let mut content = Vec::new();
+    loop {
+        let mut bytes = [0; 8];
+        tokio::select! {
+            biased;
+            filled = poll_fn(|cx| {
+                let mut buf = ReadBuf::new(&mut bytes);
+                poll_skip_bom(cx, Pin::new(&mut reader), &mut state, &mut buf).map_ok(|()| buf.filled().len())
+            }) => match filled.unwrap() {
+                0 => break,
+                filled => content.extend_from_slice(&bytes[..filled]),
+            },
+            // cancels the read on every other iteration
+            _ = std::future::ready(()), if content.len() % 2 == 1 => content.push(b'|'),
+        }
+    }
+    assert!(content.contains(&b'|'));
+    content.retain(|&byte| byte != b'|');
+    assert_eq!(b"t\x00e\x00", content.as_slice());
+}
+
+#[tokio::test]
+async fn test_poll_take_leftover() {
+    let mut reader = PendingReader { bytes: b"\xFF\xFEtext", chunk_len: 4, ready: true };
+    let mut state = BomPollState::new(BomType::all());
+    let mut bytes = [0; 1];
+    poll_fn(|cx| poll_skip_bom(cx, Pin::new(&mut reader), &mut state, &mut ReadBuf::new(&mut bytes))).await.unwrap();
+    assert_eq!(b"t", &bytes);
+    assert_eq!(b"e", state.take_leftover().as_slice());
+    assert!(state.is_resolved());
+    assert_eq!(Some(Some(BomType::UTF16LE)), state.bom_found());
+    assert_eq!(b"xt", reader.bytes);
+}
+
+/// Buffered reader counting the bytes consumed from its buffer.
+struct CountingBufReader<'b> {
+    bytes: &'b [u8],
+    consumed: usize,
+}
+
+impl AsyncRead for CountingBufReader<'_> {
+    fn poll_read(mut self: Pin<&mut Self>, _: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<std::io::Result<()>> {
+        let len = buf.remaining().min(self.bytes.len()).min(1);
+        buf.put_slice(&self.bytes[..len]);
+        self.bytes = &self.bytes[len..];
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl tokio::io::AsyncBufRead for CountingBufReader<'_> {
+    fn poll_fill_buf(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<std::io::Result<&[u8]>> {
+        Poll::Ready(Ok(self.get_mut().bytes))
+    }
+
+    fn consume(mut self: Pin<&mut Self>, amt: usize) {
+        self.bytes = &self.bytes[amt..];
+        self.consumed += amt;
+    }
+}
+
+macro_rules! test_async_copy {
+    ($name:ident, $bytes:expr, $content:expr, $bom:expr) => {
+        #[tokio::test]
+        async fn $name() {
+            let bytes: &[u8] = $bytes;
+            for chunk_len in 1..=bytes.len().max(1) {
+                let mut reader = AsyncSkipEncodingBom::new(BomType::all(), tokio::io::BufReader::new(PendingReader { bytes, chunk_len, ready: false }));
+                let mut writer = Vec::new();
+                let copied = tokio::io::copy_buf(&mut reader, &mut writer).await.unwrap();
+                assert_eq!(&$content[..], writer.as_slice());
+                assert_eq!($content.len() as u64, copied);
+                assert_eq!(Some($bom), reader.bom_found());
+
+                let mut reader = AsyncSkipEncodingBom::new(BomType::all(), PendingReader { bytes, chunk_len, ready: false });
+                let mut writer = Vec::new();
+                let _ = tokio::io::copy(&mut reader, &mut writer).await.unwrap();
+                assert_eq!(&$content[..], writer.as_slice());
+                assert_eq!(Some($bom), reader.bom_found());
+
+                let mut reader = AsyncSkipEncodingBom::new(BomType::all(), PendingReader { bytes, chunk_len, ready: false });
+                assert_eq!($bom, reader.read_bom().await.unwrap());
+                let (mut content, mut reader) = reader.into_inner_async();
+                let _ = tokio::io::AsyncReadExt::read_to_end(&mut reader, &mut content).await.unwrap();
+                assert_eq!(&$content[..], content.as_slice());
+            }
+        }
+    };
+}
+
+test_async_copy!(test_async_copy_utf8, b"\xEF\xBB\xBFtext", b"text", Some(BomType::UTF8));
+test_async_copy!(test_async_copy_utf16be, b"\xFE\xFF\x00t\x00e", b"\x00t\x00e", Some(BomType::UTF16BE));
+test_async_copy!(test_async_copy_no_bom, b"text", b"text", None);
+test_async_copy!(test_async_copy_incomplete, b"\xEF\xBB", b"\xEF\xBB", None);
+test_async_copy!(test_async_copy_empty, b"", b"", None);
+
+#[tokio::test]
+async fn test_async_buf_read_passthrough() {
+    // once resolved, the buffer of the underlying reader is consumed directly
+    let mut reader = AsyncSkipEncodingBom::new(BomType::all(), CountingBufReader { bytes: b"\xEF\xBB\xBFcontent", consumed: 0 });
+    assert_eq!(Some(BomType::UTF8), reader.read_bom().await.unwrap());
+    let mut writer = Vec::new();
+    let _ = tokio::io::copy_buf(&mut reader, &mut writer).await.unwrap();
+    assert_eq!(b"content", writer.as_slice());
+    assert_eq!(b"content".len(), reader.get_ref().consumed);
+}
+
+#[tokio::test]
+async fn test_async_read_select() {
+    // the read futures of AsyncSkipEncodingBom are cancellation safe: dropping them while pending loses no byte
+    let mut reader = AsyncSkipEncodingBom::new(BomType::all(), PendingReader { bytes: b"\xEF\xBB\xBFtext", chunk_len: 1, ready: false });
+    let mut cancelled = 0;
+    let bom = loop {
+        tokio::select! {
+            biased;
+            bom = reader.read_bom() => break bom.unwrap(),
+            _ = std::future::ready(()) => cancelled += 1,
+        }
+    };
+    assert_eq!(Some(BomType::UTF8), bom);
+    let mut content = Vec::new();
+    loop {
+        let mut bytes = [0; 8];
+        tokio::select! {
+            biased;
+            read = tokio::io::AsyncReadExt::read(&mut reader, &mut bytes) => match read.unwrap() {
+                0 => break,
+                read => content.extend_from_slice(&bytes[..read]),
+            },
+            _ = std::future::ready(()) => cancelled += 1,
+        }
+    }
+    assert_eq!(b"text", content.as_slice());
+    assert!(cancelled > 0);
+}
+
+/// Reader returning [`Poll::Pending`] on its first poll only, then all its bytes that fit in the buffer.
+struct PendingOnceReader<'b> {
+    bytes: &'b [u8],
+    polls: usize,
+}
+
+impl AsyncRead for PendingOnceReader<'_> {
+    fn poll_read(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<std::io::Result<()>> {
+        self.polls += 1;
+        if self.polls == 1 {
+            cx.waker().wake_by_ref();
+            return Poll::Pending;
+        }
+        let len = buf.remaining().min(self.bytes.len());
+        buf.put_slice(&self.bytes[..len]);
+        self.bytes = &self.bytes[len..];
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[tokio::test]
+async fn test_poll_skip_bom_pending_once() {
+    let mut reader = PendingOnceReader { bytes: b"\xFF\xFE\x00\x00t\x00\x00\x00", polls: 0 };
+    let mut state = BomPollState::new(BomType::all());
+    let mut bytes = [0; 16];
+    let mut polls = 0;
+    let filled = poll_fn(|cx| {
+        polls += 1;
+        let mut buf = ReadBuf::new(&mut bytes);
+        poll_skip_bom(cx, Pin::new(&mut reader), &mut state, &mut buf).map_ok(|()| buf.filled().len())
+    })
+    .await
+    .unwrap();
+    // the future is polled again once after the pending read: the BOM is read, then the content after it
+    assert_eq!(2, polls);
+    assert_eq!(3, reader.polls);
+    assert_eq!(b"t\x00\x00\x00", &bytes[..filled]);
+    assert_eq!(Some(Some(BomType::UTF32LE)), state.bom_found());
+}
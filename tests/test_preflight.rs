@@ -0,0 +1,86 @@
+use skip_bom::*;
+use std::path::PathBuf;
+
+fn utf16le(string: &str) -> Vec<u8> {
+    string.encode_utf16().flat_map(u16::to_le_bytes).collect()
+}
+
+fn preflight_bytes(bytes: &[u8]) -> PreflightReport {
+    preflight_reader(bytes, &DetectOptions::default()).unwrap()
+}
+
+macro_rules! test_preflight {
+    ($test_fn_name:ident, $bytes:expr, $bom:expr, $encoding:expr, $line_ending:expr, $header:expr) => {
+        #[test]
+        fn $test_fn_name() {
+            let report = preflight_bytes(&$bytes);
+            assert_eq!($bom, report.bom());
+            assert_eq!($encoding, report.guessed_encoding());
+            assert_eq!($line_ending, report.line_ending);
+            assert_eq!($header, report.header.as_deref());
+        }
+    };
+}
+
+test_preflight!(test_preflight_utf8_bom_crlf, *b"\xEF\xBB\xBFid;name\r\n1;a\r\n", Some(BomType::UTF8), Some(BomType::UTF8), LineEnding::CrLf, Some("id;name"));
+test_preflight!(test_preflight_no_bom_lf, *b"id,name\n1,a\n", None, Some(BomType::UTF8), LineEnding::Lf, Some("id,name"));
+test_preflight!(test_preflight_utf16le_bom, [&b"\xFF\xFE"[..], &utf16le("id\tname\r\n1\ta\r\n")].concat(), Some(BomType::UTF16LE), Some(BomType::UTF16LE), LineEnding::CrLf, Some("id\tname"));
+test_preflight!(test_preflight_utf16le_no_bom, utf16le("id,name\n1,a\n"), None, Some(BomType::UTF16LE), LineEnding::Lf, Some("id,name"));
+test_preflight!(test_preflight_cr, *b"id\r1\r", None, Some(BomType::UTF8), LineEnding::Cr, Some("id"));
+test_preflight!(test_preflight_mixed, *b"id\r\n1\n", None, Some(BomType::UTF8), LineEnding::Mixed, Some("id"));
+test_preflight!(test_preflight_single_line, *b"id,name", None, Some(BomType::UTF8), LineEnding::None, Some("id,name"));
+test_preflight!(test_preflight_empty, *b"", None, None, LineEnding::None, Some(""));
+test_preflight!(test_preflight_external_decoder, *b"\xFB\xEE\x28id\n", Some(BomType::BOCU1), Some(BomType::BOCU1), LineEnding::None, None);
+
+#[test]
+fn test_preflight_header_past_sample() {
+    let options = DetectOptions { sample_len: 8, ..Default::default() };
+    let report = preflight_reader(&b"a long header line\n"[..], &options).unwrap();
+    assert_eq!(None, report.header);
+    assert_eq!(LineEnding::None, report.line_ending);
+}
+
+#[test]
+fn test_preflight_sample_ends_with_cr() {
+    let options = DetectOptions { sample_len: 4, ..Default::default() };
+    let report = preflight_reader(&b"a\nb\r\nc\r\n"[..], &options).unwrap();
+    assert_eq!(LineEnding::Lf, report.line_ending);
+    assert_eq!(Some("a"), report.header.as_deref());
+}
+
+#[test]
+fn test_preflight_file() {
+    let path: PathBuf = std::env::temp_dir().join(format!("skip_bom_{}_test_preflight_file", std::process::id()));
+    std::fs::write(&path, b"\xEF\xBB\xBFid;name\r\n").unwrap();
+    let report = preflight(&path);
+    let _ = std::fs::remove_file(&path);
+    let report = report.unwrap();
+    assert_eq!(Some(BomType::UTF8), report.bom());
+    assert_eq!(Some("id;name"), report.header.as_deref());
+}
+
+macro_rules! test_dominant_line_ending {
+    ($test_fn_name:ident, $text:expr, $counts:expr, $dominant:expr) => {
+        #[test]
+        fn $test_fn_name() {
+            let (lf, crlf, cr) = $counts;
+            let report = preflight_bytes($text.as_bytes());
+            assert_eq!(LineEndingCounts { lf, crlf, cr }, report.line_ending_counts);
+            assert_eq!(lf + crlf + cr, report.line_ending_counts.total());
+            assert_eq!($dominant, report.dominant_line_ending());
+        }
+    };
+}
+
+test_dominant_line_ending!(test_dominant_line_ending_crlf, "a\r\nb\r\nc\n", (1, 2, 0), LineEnding::CrLf);
+test_dominant_line_ending!(test_dominant_line_ending_lf, "a\nb\nc\r", (2, 0, 1), LineEnding::Lf);
+test_dominant_line_ending!(test_dominant_line_ending_cr, "a\rb\r", (0, 0, 2), LineEnding::Cr);
+test_dominant_line_ending!(test_dominant_line_ending_tie, "a\nb\r\n", (1, 1, 0), LineEnding::Mixed);
+test_dominant_line_ending!(test_dominant_line_ending_none, "a", (0, 0, 0), LineEnding::None);
+
+#[test]
+fn test_dominant_line_ending_utf16() {
+    let report = preflight_bytes(&[&b"\xFF\xFE"[..], &utf16le("a\r\nb\r\nc\n")].concat());
+    assert_eq!(LineEnding::Mixed, report.line_ending);
+    assert_eq!(LineEnding::CrLf, report.dominant_line_ending());
+}
@@ -0,0 +1,44 @@
+use skip_bom::*;
+use std::io::Read;
+
+const BYTES: &[u8] = b"\xFF\xFE\x00\x00t\x00\x00\x00";
+
+fn read_with_prelude(prelude_len: usize) -> (Vec<u8>, Option<Option<BomType>>) {
+    let (prelude, rest) = BYTES.split_at(prelude_len);
+    let mut reader = SkipEncodingBom::with_prelude(BomType::all(), prelude, rest);
+    let mut bytes = Vec::new();
+    let _ = reader.read_to_end(&mut bytes).unwrap();
+    (bytes, reader.bom_found())
+}
+
+macro_rules! test_prelude {
+    ($test_fn_name:ident, $prelude_len:expr) => {
+        #[test]
+        fn $test_fn_name() {
+            assert_eq!((b"t\x00\x00\x00".to_vec(), Some(Some(BomType::UTF32LE))), read_with_prelude($prelude_len));
+        }
+    };
+}
+
+test_prelude!(test_prelude_empty, 0);
+test_prelude!(test_prelude_inside_bom, 2);
+test_prelude!(test_prelude_bom, 4);
+test_prelude!(test_prelude_after_bom, 6);
+test_prelude!(test_prelude_whole_stream, 8);
+
+#[test]
+fn test_prelude_owned() {
+    let mut reader = SkipEncodingBom::with_prelude(BomType::all(), b"\xEF\xBB".to_vec(), &b"\xBFtext"[..]);
+    let mut string = String::new();
+    let _ = reader.read_to_string(&mut string).unwrap();
+    assert_eq!("text", &string);
+}
+
+#[test]
+fn test_prelude_no_bom() {
+    let mut reader = SkipEncodingBom::with_prelude(BomType::all(), &b"\xEF"[..], &b"text"[..]);
+    let mut bytes = Vec::new();
+    let _ = reader.read_to_end(&mut bytes).unwrap();
+    assert_eq!(b"\xEFtext", bytes.as_slice());
+    assert_eq!(Some(None), reader.bom_found());
+}
@@ -0,0 +1,43 @@
+use skip_bom::prelude::*;
+use std::io::{BufReader, Read};
+
+#[test]
+fn test_prelude_module_readers() {
+    let mut reader = SkipEncodingBom::new(BomType::all(), &b"\xEF\xBB\xBFcontent"[..]);
+    let mut content = String::new();
+    let _ = reader.read_to_string(&mut content).unwrap();
+    assert_eq!("content", content);
+    assert_eq!(BomStatus::Bom(BomType::UTF8), reader.status());
+
+    let mut reader = SkipEncodingBomBuf::new(BomTypeSet::WEB.bom_types(), BufReader::new(&b"\xFE\xFF\x00H"[..]));
+    assert_eq!(Some(BomType::UTF16BE), reader.read_bom().unwrap());
+
+    let mut reader = DecodeReader::new(BomType::all(), &b"\xFF\xFEH\x00i\x00"[..]);
+    let mut text = String::new();
+    let _ = reader.read_to_string(&mut text).unwrap();
+    assert_eq!("Hi", text);
+}
+
+#[test]
+fn test_prelude_module_policy() {
+    let policy = ReadPolicy::default().rule(PolicyCondition::NoBom, PolicyAction::Reject);
+    let error = ManagedReader::new(&b"content"[..], &policy).unwrap_err();
+    assert_eq!(Some(BomErrorCode::PolicyRejected), BomErrorCode::of(&error));
+    assert!(BomError::of(&error).is_some());
+    let report: EncodingReport = detect(&b"\xEF\xBB\xBFcontent"[..], &DetectOptions::default()).unwrap();
+    assert_eq!(Some(BomType::UTF8), report.bom);
+}
+
+#[test]
+fn test_prelude_module_traits() {
+    // the extension traits are in scope
+    assert!(std::path::Path::new("tests/missing.txt").read_text_no_bom().is_err());
+    let _ = <std::process::Child as ChildExt>::take_stdout_no_bom;
+}
+
+#[cfg(feature = "unstable")]
+#[test]
+fn test_prelude_module_unstable() {
+    let sniffer = |sample: &[u8]| if sample.starts_with(b"%") { Some((BomType::UTF8, 50)) } else { None };
+    assert_eq!(Some((BomType::UTF8, 50)), Sniffer::sniff(&sniffer, b"%PDF"));
+}
@@ -0,0 +1,58 @@
+#![cfg(unix)]
+
+use skip_bom::*;
+use std::io::Read;
+use std::process::{Child, Command, Stdio};
+
+/// Spawn a shell printing `stdout` and `stderr`, given as `printf` format strings.
+fn spawn_printf(stdout: &str, stderr: &str) -> Child {
+    Command::new("sh")
+        .arg("-c")
+        .arg(format!("printf '{}'; printf '{}' >&2", stdout, stderr))
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap()
+}
+
+fn read_to_end(mut reader: impl Read) -> Vec<u8> {
+    let mut buf = Vec::new();
+    let _ = reader.read_to_end(&mut buf).unwrap();
+    buf
+}
+
+fn read_to_string(mut reader: impl Read) -> String {
+    let mut string = String::new();
+    let _ = reader.read_to_string(&mut string).unwrap();
+    string
+}
+
+#[test]
+fn test_take_no_bom() {
+    let mut child = spawn_printf("\\357\\273\\277out", "\\377\\376e\\000");
+    let mut stdout = child.take_stdout_no_bom().unwrap();
+    let mut stderr = child.take_stderr_no_bom().unwrap();
+    assert_eq!(b"out", read_to_end(&mut stdout).as_slice());
+    assert_eq!(b"e\x00", read_to_end(&mut stderr).as_slice());
+    assert_eq!(Some(Some(BomType::UTF8)), stdout.bom_found());
+    assert_eq!(Some(Some(BomType::UTF16LE)), stderr.bom_found());
+    assert!(child.wait().unwrap().success());
+}
+
+#[test]
+fn test_take_text_no_bom() {
+    let mut child = spawn_printf("\\377\\376o\\000u\\000t\\000", "err");
+    let stdout = child.take_stdout_text_no_bom().unwrap();
+    let stderr = child.take_stderr_text_no_bom().unwrap();
+    assert_eq!("out", read_to_string(stdout));
+    assert_eq!("err", read_to_string(stderr));
+    assert!(child.wait().unwrap().success());
+}
+
+#[test]
+fn test_take_not_piped() {
+    let mut child = Command::new("sh").arg("-c").arg("true").stdout(Stdio::null()).stderr(Stdio::null()).spawn().unwrap();
+    assert!(child.take_stdout_no_bom().is_none());
+    assert!(child.take_stderr_text_no_bom().is_none());
+    assert!(child.wait().unwrap().success());
+}
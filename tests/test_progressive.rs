@@ -0,0 +1,90 @@
+use skip_bom::*;
+use std::io::{Cursor, Read};
+
+fn append(reader: &mut SkipEncodingBom<Cursor<Vec<u8>>>, bytes: &[u8]) {
+    reader.get_mut().get_mut().extend_from_slice(bytes);
+}
+
+#[test]
+fn test_poll_more_bom_in_pieces() {
+    let mut reader = SkipEncodingBom::new(BomType::all(), Cursor::new(Vec::new()));
+    let mut buf = [0; 16];
+    assert_eq!(ProgressiveRead::NoData, reader.poll_more(&mut buf).unwrap());
+    append(&mut reader, b"\xFF");
+    assert_eq!(ProgressiveRead::Pending { held_bytes: 1 }, reader.poll_more(&mut buf).unwrap());
+    append(&mut reader, b"\xFE");
+    // FF FE may still be the start of the UTF-32LE BOM
+    assert_eq!(ProgressiveRead::Pending { held_bytes: 2 }, reader.poll_more(&mut buf).unwrap());
+    append(&mut reader, b"a\x00");
+    assert_eq!(ProgressiveRead::Read(2), reader.poll_more(&mut buf).unwrap());
+    assert_eq!(b"a\x00", &buf[..2]);
+    assert_eq!(Some(Some(BomType::UTF16LE)), reader.bom_found());
+    assert_eq!(ProgressiveRead::NoData, reader.poll_more(&mut buf).unwrap());
+    append(&mut reader, b"b\x00");
+    assert_eq!(ProgressiveRead::Read(2), reader.poll_more(&mut buf).unwrap());
+}
+
+#[test]
+fn test_poll_more_no_bom() {
+    let mut reader = SkipEncodingBom::new(BomType::all(), Cursor::new(Vec::new()));
+    let mut buf = [0; 16];
+    append(&mut reader, b"\xEF\xBB");
+    assert_eq!(ProgressiveRead::Pending { held_bytes: 2 }, reader.poll_more(&mut buf).unwrap());
+    append(&mut reader, b"log");
+    assert_eq!(ProgressiveRead::Read(5), reader.poll_more(&mut buf).unwrap());
+    assert_eq!(b"\xEF\xBBlog", &buf[..5]);
+}
+
+#[test]
+fn test_end_of_stream_held_bytes() {
+    let mut reader = SkipEncodingBom::new(BomType::all(), Cursor::new(b"\xEF\xBB".to_vec()));
+    let mut buf = [0; 16];
+    assert_eq!(ProgressiveRead::Pending { held_bytes: 2 }, reader.poll_more(&mut buf).unwrap());
+    assert_eq!(None, reader.end_of_stream().unwrap());
+    assert_eq!(BomStatus::NoBom, reader.status());
+    assert_eq!(ProgressiveRead::Read(2), reader.poll_more(&mut buf).unwrap());
+    assert_eq!(b"\xEF\xBB", &buf[..2]);
+    assert_eq!(ProgressiveRead::NoData, reader.poll_more(&mut buf).unwrap());
+}
+
+#[test]
+fn test_end_of_stream_shorter_bom() {
+    let mut reader = SkipEncodingBom::new(BomType::all(), Cursor::new(b"\xFF\xFE".to_vec()));
+    assert_eq!(Some(BomType::UTF16LE), reader.end_of_stream().unwrap());
+    let mut buf = Vec::new();
+    let _ = reader.read_to_end(&mut buf).unwrap();
+    assert!(buf.is_empty());
+}
+
+#[test]
+fn test_end_of_stream_before_reading() {
+    let mut reader = SkipEncodingBom::new(BomType::all(), Cursor::new(b"\xEF\xBB\xBFtext".to_vec()));
+    assert_eq!(Some(BomType::UTF8), reader.end_of_stream().unwrap());
+    let mut buf = Vec::new();
+    let _ = reader.read_to_end(&mut buf).unwrap();
+    assert_eq!(b"text", buf.as_slice());
+}
+
+#[test]
+fn test_end_of_stream_empty() {
+    let mut reader = SkipEncodingBom::new(BomType::all(), Cursor::new(Vec::new()));
+    assert_eq!(None, reader.end_of_stream().unwrap());
+    assert_eq!(BomStatus::NoBom, reader.status());
+}
+
+#[test]
+fn test_poll_more_longer_bom_later() {
+    let mut reader = SkipEncodingBom::new(BomType::all(), Cursor::new(b"\xFF\xFE".to_vec()));
+    let mut buf = [0; 16];
+    assert_eq!(ProgressiveRead::Pending { held_bytes: 2 }, reader.poll_more(&mut buf).unwrap());
+    append(&mut reader, b"\x00\x00a\x00\x00\x00");
+    assert_eq!(ProgressiveRead::Read(4), reader.poll_more(&mut buf).unwrap());
+    assert_eq!(Some(Some(BomType::UTF32LE)), reader.bom_found());
+}
+
+#[test]
+fn test_poll_more_bom_hook() {
+    let mut reader = SkipEncodingBom::new(BomType::all(), Cursor::new(b"\x2B\x2F\x76text".to_vec())).with_bom_hook(reject_external_decoder);
+    assert!(reader.poll_more(&mut [0; 16]).is_err());
+    assert_eq!(Some(Some(BomType::UTF7)), reader.bom_found());
+}
@@ -0,0 +1,88 @@
+use skip_bom::*;
+use std::io::{Cursor, Read};
+
+mod reader_test_utils;
+use reader_test_utils::*;
+
+fn read_all<R: Read>(mut reader: R, bom_types: &[BomType]) -> (Option<BomType>, Vec<u8>) {
+    let (bom_type, leftover) = read_bom_from(&mut reader, bom_types).unwrap();
+    let mut buf = leftover.bytes().to_vec();
+    let _ = reader.read_to_end(&mut buf).unwrap();
+    (bom_type, buf)
+}
+
+macro_rules! test_read_bom_from {
+    ($test_fn_name:ident, $bytes:expr, $bom_types:expr, $expected_bom:expr, $expected_content:expr) => {
+        #[test]
+        fn $test_fn_name() {
+            let expected = ($expected_bom, $expected_content.to_vec());
+            assert_eq!(expected, read_all(Cursor::new(&$bytes[..]), $bom_types));
+            assert_eq!(expected, read_all(ByteByByteReader(&$bytes[..]), $bom_types));
+        }
+    };
+}
+
+test_read_bom_from!(test_read_bom_from_utf8, b"\xEF\xBB\xBFThis stream has a BOM.", BomType::all(), Some(BomType::UTF8), b"This stream has a BOM.");
+test_read_bom_from!(test_read_bom_from_utf16le, b"\xFF\xFEa\x00", BomType::all(), Some(BomType::UTF16LE), b"a\x00");
+test_read_bom_from!(test_read_bom_from_no_bom, b"This stream has no BOM.", BomType::all(), None, b"This stream has no BOM.");
+test_read_bom_from!(test_read_bom_from_other_bom, b"\xEF\xBB\xBFThis stream has a BOM.", &[BomType::UTF16LE], None, b"\xEF\xBB\xBFThis stream has a BOM.");
+test_read_bom_from!(test_read_bom_from_only_bom, b"\xEF\xBB\xBF", BomType::all(), Some(BomType::UTF8), b"");
+test_read_bom_from!(test_read_bom_from_short, b"\xEF\xBB", BomType::all(), None, b"\xEF\xBB");
+test_read_bom_from!(test_read_bom_from_utf16le_only, b"\xFF\xFE", BomType::all(), Some(BomType::UTF16LE), b"");
+test_read_bom_from!(test_read_bom_from_utf16le_one_byte, b"\xFF\xFE\x00", BomType::all(), Some(BomType::UTF16LE), b"\x00");
+test_read_bom_from!(test_read_bom_from_utf16le_not_enabled, b"\xFF\xFE\x00", &[BomType::UTF32LE], None, b"\xFF\xFE\x00");
+test_read_bom_from!(test_read_bom_from_empty, b"", BomType::all(), None, b"");
+
+/// Reader returning the bytes of `bytes` one at a time, every other read failing with an error of kind `error_kind`.
+struct FailingReader<'a> {
+    bytes: &'a [u8],
+    error_kind: std::io::ErrorKind,
+    failed: bool,
+}
+
+impl Read for FailingReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.failed = !self.failed;
+        if self.failed {
+            return Err(std::io::Error::new(self.error_kind, "read failed"));
+        }
+        let count = buf.len().min(self.bytes.len()).min(1);
+        buf[..count].copy_from_slice(&self.bytes[..count]);
+        self.bytes = &self.bytes[count..];
+        Ok(count)
+    }
+}
+
+#[test]
+fn test_read_bom_from_interrupted() {
+    let mut reader = FailingReader { bytes: b"\xEF\xBB\xBFtext", error_kind: std::io::ErrorKind::Interrupted, failed: false };
+    let (bom_type, leftover) = read_bom_from(&mut reader, BomType::all()).unwrap();
+    assert_eq!(Some(BomType::UTF8), bom_type);
+    assert!(leftover.bytes().is_empty());
+}
+
+#[test]
+fn test_read_bom_from_error() {
+    let mut reader = FailingReader { bytes: b"\xEF\xBB\xBFtext", error_kind: std::io::ErrorKind::Other, failed: false };
+    assert_eq!(std::io::ErrorKind::Other, read_bom_from(&mut reader, BomType::all()).unwrap_err().kind());
+}
+
+#[test]
+fn test_read_bom_from_error_after_bytes() {
+    // the byte read before the error is returned instead of being lost with it
+    let mut reader = FailingReader { bytes: b"\xEF\xBB\xBFtext", error_kind: std::io::ErrorKind::Other, failed: true };
+    let (bom_type, leftover) = read_bom_from(&mut reader, BomType::all()).unwrap();
+    assert_eq!(None, bom_type);
+    assert_eq!(b"\xEF", leftover.bytes());
+    let mut buf = [0; 4];
+    assert_eq!(1, reader.read(&mut buf).unwrap());
+    assert_eq!(b'\xBB', buf[0]);
+}
+
+#[test]
+fn test_read_bom_from_unsized() {
+    let reader: &mut dyn Read = &mut &b"\xFF\xFEa\x00"[..];
+    let (bom_type, leftover) = read_bom_from(reader, BomType::all()).unwrap();
+    assert_eq!(Some(BomType::UTF16LE), bom_type);
+    assert_eq!(b"a\x00", leftover.bytes());
+}
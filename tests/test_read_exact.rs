@@ -0,0 +1,58 @@
+use skip_bom::*;
+use std::io::{ErrorKind, Read};
+
+mod reader_test_utils;
+use reader_test_utils::*;
+
+fn test_read_exact(bytes: &[u8], bom_types: &[BomType], expected_bom: Option<BomType>, expected_content: &[u8]) {
+    for chunk_len in 1..=expected_content.len().max(1) {
+        for byte_by_byte in [false, true] {
+            let mut reader = if byte_by_byte {
+                SkipEncodingBom::new(bom_types, Box::new(ByteByByteReader(bytes)) as Box<dyn Read>)
+            } else {
+                SkipEncodingBom::new(bom_types, Box::new(bytes) as Box<dyn Read>)
+            };
+            let mut content: Vec<u8> = Vec::new();
+            let mut chunk = vec![0u8; chunk_len];
+            for _ in 0..(expected_content.len() / chunk_len) {
+                reader.read_exact(&mut chunk).unwrap();
+                content.extend(&chunk);
+            }
+            let remaining = expected_content.len() % chunk_len;
+            reader.read_exact(&mut chunk[..remaining]).unwrap();
+            content.extend(&chunk[..remaining]);
+            assert_eq!(expected_content, content.as_slice());
+            // the stream is over
+            assert_eq!(ErrorKind::UnexpectedEof, reader.read_exact(&mut [0u8; 1]).unwrap_err().kind());
+            assert_eq!(Some(expected_bom), reader.bom_found());
+        }
+    }
+}
+
+macro_rules! test_read_exact {
+    ($test_fn_name:ident, $bytes:expr, $bom_types:expr, $expected_bom:expr, $expected_content:expr) => {
+        #[test]
+        fn $test_fn_name() {
+            test_read_exact($bytes, $bom_types, $expected_bom, $expected_content);
+        }
+    };
+}
+
+test_read_exact!(test_read_exact_utf8, b"\xEF\xBB\xBFcontent", BomType::all(), Some(BomType::UTF8), b"content");
+test_read_exact!(test_read_exact_no_bom, b"content", BomType::all(), None, b"content");
+test_read_exact!(test_read_exact_utf32le, b"\xFF\xFE\x00\x00a\x00\x00\x00", BomType::all(), Some(BomType::UTF32LE), b"a\x00\x00\x00");
+test_read_exact!(test_read_exact_utf16le, b"\xFF\xFEa\x00", BomType::all(), Some(BomType::UTF16LE), b"a\x00");
+test_read_exact!(test_read_exact_utf16le_one_byte, b"\xFF\xFE\x00", BomType::all(), Some(BomType::UTF16LE), b"\x00");
+test_read_exact!(test_read_exact_utf16le_two_bytes, b"\xFF\xFE\x00\x01", BomType::all(), Some(BomType::UTF16LE), b"\x00\x01");
+test_read_exact!(test_read_exact_utf16le_only, b"\xFF\xFE", BomType::all(), Some(BomType::UTF16LE), b"");
+test_read_exact!(test_read_exact_utf16le_not_enabled, b"\xFF\xFE\x00", &[BomType::UTF32LE], None, b"\xFF\xFE\x00");
+test_read_exact!(test_read_exact_empty, b"", BomType::all(), None, b"");
+
+#[test]
+fn test_read_exact_after_read_bom() {
+    let mut reader = SkipEncodingBom::new(BomType::all(), ByteByByteReader(b"\xFF\xFE\x00"));
+    assert_eq!(Some(BomType::UTF16LE), reader.read_bom().unwrap());
+    let mut buf = [0xFFu8; 1];
+    reader.read_exact(&mut buf).unwrap();
+    assert_eq!([0u8], buf);
+}
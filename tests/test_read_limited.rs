@@ -0,0 +1,59 @@
+use skip_bom::*;
+use std::io::{ErrorKind, Read};
+
+fn utf16le(string: &str) -> Vec<u8> {
+    let mut bytes = vec![0xFF, 0xFE];
+    bytes.extend(string.encode_utf16().flat_map(u16::to_le_bytes));
+    bytes
+}
+
+fn assert_limit_exceeded<T: std::fmt::Debug>(result: Result<T>) {
+    let error = result.unwrap_err();
+    assert_eq!(ErrorKind::InvalidData, error.kind());
+    assert_eq!(Some(BomErrorCode::LimitExceeded), BomErrorCode::of(&error));
+}
+
+macro_rules! test_read_text_limited {
+    ($name:ident, $bytes:expr, $text:expr) => {
+        #[test]
+        fn $name() {
+            let bytes: &[u8] = &$bytes;
+            let text: &str = $text;
+            // the limit applies to the UTF-8 text, without the BOM
+            assert_eq!(text, read_text_limited(bytes, text.len()).unwrap());
+            assert_eq!(text, read_text_limited(bytes, text.len() + 100).unwrap());
+            if !text.is_empty() {
+                assert_limit_exceeded(read_text_limited(bytes, text.len() - 1));
+            }
+        }
+    };
+}
+
+test_read_text_limited!(test_read_text_limited_utf8, *b"\xEF\xBB\xBFtext", "text");
+test_read_text_limited!(test_read_text_limited_no_bom, *b"text", "text");
+test_read_text_limited!(test_read_text_limited_utf16, utf16le("h\u{E9}llo"), "h\u{E9}llo");
+test_read_text_limited!(test_read_text_limited_empty, *b"", "");
+test_read_text_limited!(test_read_text_limited_bom_only, *b"\xEF\xBB\xBF", "");
+
+#[test]
+fn test_read_text_limited_invalid_utf8() {
+    assert_eq!(ErrorKind::InvalidData, read_text_limited(&b"\xFFtext"[..], 100).unwrap_err().kind());
+    // a character cut by the limit is reported as exceeding it
+    assert_limit_exceeded(read_text_limited("h\u{E9}llo".as_bytes(), 2));
+}
+
+#[test]
+fn test_read_text_limited_bounded() {
+    // an endless stream is read no further than the limit
+    assert_limit_exceeded(read_text_limited(std::io::repeat(b'a'), 1024));
+    assert_limit_exceeded(read_text_limited(std::io::repeat(b'a').take(1 << 20), 1024));
+}
+
+#[test]
+fn test_read_bytes_limited() {
+    assert_eq!(utf16le("hi")[2..], read_bytes_limited(utf16le("hi").as_slice(), 4).unwrap()[..]);
+    assert_limit_exceeded(read_bytes_limited(utf16le("hi").as_slice(), 3));
+    assert_limit_exceeded(read_bytes_limited(std::io::repeat(0), 16));
+    // no limit
+    assert_eq!(b"text", read_bytes_limited(&b"\xEF\xBB\xBFtext"[..], usize::MAX).unwrap().as_slice());
+}
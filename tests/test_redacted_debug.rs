@@ -0,0 +1,65 @@
+use skip_bom::*;
+use std::io::{Cursor, Read};
+
+const SECRET: &[u8] = b"\xEF\xBB\xBFpassword=hunter2";
+
+/// Returns `true` if the Debug output shows any part of the secret bytes, whether as text or as byte values.
+fn leaks(debug: &str) -> bool {
+    debug.contains("hunter2") || debug.contains("112, 97") || debug.contains("[112") || debug.contains("112]")
+}
+
+macro_rules! test_redacted_debug {
+    ($name:ident, $bytes:expr, $read_len:expr, $state:expr) => {
+        #[test]
+        fn $name() {
+            let mut reader = SkipEncodingBom::new(BomType::all(), Cursor::new($bytes)).with_redacted_debug();
+            let mut buf = vec![0; $read_len];
+            let _ = reader.read(&mut buf).unwrap();
+            let debug = format!("{:?}", reader);
+            assert!(debug.contains($state), "{}", debug);
+            assert!(!leaks(&debug), "{}", debug);
+            assert!(!debug.contains("Cursor"), "{}", debug);
+        }
+    };
+}
+
+test_redacted_debug!(test_redacted_debug_final, SECRET, 64, "Final(Some(UTF8))");
+test_redacted_debug!(test_redacted_debug_post_init_buffer, &b"\xFF\xFEpa"[..], 1, "PostInitBuffer { buffered: 1, bom_type: Some(UTF16LE) }");
+
+#[test]
+fn test_redacted_debug_initial() {
+    let mut reader = SkipEncodingBom::new(BomType::all(), Cursor::new(b"\xEF\xBB".to_vec())).with_redacted_debug();
+    assert_eq!(ProgressiveRead::Pending { held_bytes: 2 }, reader.poll_more(&mut [0; 8]).unwrap());
+    let debug = format!("{:?}", reader);
+    assert!(debug.contains("Initial { buffered: 2 }"), "{}", debug);
+    assert!(!debug.contains("239"), "{}", debug);
+}
+
+#[test]
+fn test_debug_not_redacted_by_default() {
+    let mut reader = SkipEncodingBom::new(BomType::all(), Cursor::new(SECRET));
+    let _ = reader.read(&mut [0; 64]).unwrap();
+    let debug = format!("{:?}", reader);
+    assert!(debug.contains("Cursor"), "{}", debug);
+    assert!(leaks(&debug), "{}", debug);
+}
+
+#[test]
+fn test_redacted_debug_kept() {
+    let reader = SkipEncodingBom::new(BomType::all(), Cursor::new(SECRET)).with_redacted_debug();
+    assert!(!leaks(&format!("{:?}", reader.clone())));
+    assert!(!leaks(&format!("{:?}", reader.map_inner(std::io::BufReader::new))));
+}
+
+#[test]
+fn test_redacted_debug_decode_reader() {
+    let inner = SkipEncodingBom::new(BomType::all(), Cursor::new(SECRET)).with_redacted_debug();
+    let mut reader = DecodeReader::from_skip_encoding_bom(inner);
+    let _ = reader.read(&mut [0; 4]).unwrap();
+    let debug = format!("{:?}", reader);
+    assert!(debug.contains("output_length"), "{}", debug);
+    assert!(!leaks(&debug), "{}", debug);
+    let mut reader = DecodeReader::new(BomType::all(), Cursor::new(SECRET));
+    let _ = reader.read(&mut [0; 4]).unwrap();
+    assert!(leaks(&format!("{:?}", reader)));
+}
@@ -0,0 +1,67 @@
+use skip_bom::*;
+use std::io::{Cursor, Read};
+
+fn test_resolved(bytes: &[u8], expected_bom: Option<BomType>, expected_content: &[u8]) {
+    // read everything through the guard
+    let mut reader = SkipEncodingBom::new(BomType::all(), Cursor::new(bytes));
+    {
+        let mut resolved = reader.resolve().unwrap().unwrap();
+        assert_eq!(expected_bom, resolved.bom());
+        let mut buf = Vec::new();
+        let _ = resolved.read_to_end(&mut buf).unwrap();
+        assert_eq!(expected_content, buf.as_slice());
+        assert!(resolved.buffered().is_empty());
+    }
+    assert_eq!(Some(expected_bom), reader.bom_found());
+    // read one byte through the guard and the rest through the reader
+    let mut reader = SkipEncodingBom::new(BomType::all(), Cursor::new(bytes));
+    let mut first = [0u8; 1];
+    let first_len = reader.resolve().unwrap().unwrap().read(&mut first).unwrap();
+    let mut buf = first[..first_len].to_vec();
+    let _ = reader.read_to_end(&mut buf).unwrap();
+    assert_eq!(expected_content, buf.as_slice());
+    assert_eq!(Some(expected_bom), reader.bom_found());
+}
+
+macro_rules! test_resolved {
+    ($test_fn_name:ident, $bytes:expr, $expected_bom:expr, $expected_content:expr) => {
+        #[test]
+        fn $test_fn_name() {
+            test_resolved($bytes, $expected_bom, $expected_content);
+        }
+    };
+}
+
+test_resolved!(test_resolved_utf8, b"\xEF\xBB\xBFThis stream has a BOM.", Some(BomType::UTF8), b"This stream has a BOM.");
+test_resolved!(test_resolved_utf16le, b"\xFF\xFEa\x00", Some(BomType::UTF16LE), b"a\x00");
+test_resolved!(test_resolved_utf32be, b"\x00\x00\xFE\xFFabcd", Some(BomType::UTF32BE), b"abcd");
+test_resolved!(test_resolved_no_bom, b"This stream has no BOM.", None, b"This stream has no BOM.");
+test_resolved!(test_resolved_bom_only, b"\xEF\xBB\xBF", Some(BomType::UTF8), b"");
+
+#[test]
+fn test_resolved_buffered() {
+    let mut reader = SkipEncodingBom::new(BomType::all(), Cursor::new(b"\xEF\xBB\xBFabcdef"));
+    let resolved = reader.resolve().unwrap().unwrap();
+    assert_eq!(b"a", resolved.buffered());
+    assert_eq!(4, resolved.reader().position());
+}
+
+#[test]
+fn test_resolved_undetermined() {
+    let mut reader = SkipEncodingBom::new(BomType::all(), Cursor::new(b"\xEF\xBB".to_vec()));
+    assert!(reader.resolve().unwrap().is_none());
+    reader.get_mut().get_mut().extend_from_slice(b"\xBFabc");
+    assert_eq!(Some(BomType::UTF8), reader.resolve().unwrap().unwrap().bom());
+}
+
+#[test]
+fn test_resolved_after_read() {
+    let mut reader = SkipEncodingBom::new(BomType::all(), Cursor::new(b"\xFE\xFF\x00a\x00b"));
+    let mut buf = [0u8; 1];
+    let _ = reader.read(&mut buf).unwrap();
+    let mut resolved = reader.resolve().unwrap().unwrap();
+    assert_eq!(Some(BomType::UTF16BE), resolved.bom());
+    let mut rest = Vec::new();
+    let _ = resolved.read_to_end(&mut rest).unwrap();
+    assert_eq!(b"a\x00b", rest.as_slice());
+}
@@ -0,0 +1,53 @@
+use skip_bom::*;
+use std::io::{Cursor, Read, Seek, SeekFrom};
+
+fn read_all<R: Read + ?Sized>(reader: &mut SkipEncodingBom<R>) -> Vec<u8> {
+    let mut buf = Vec::new();
+    let _ = reader.read_to_end(&mut buf).unwrap();
+    buf
+}
+
+macro_rules! test_restart {
+    ($test_fn_name:ident, $bytes:expr, $expected:expr, $expected_bom:expr) => {
+        #[test]
+        fn $test_fn_name() {
+            let mut reader = SkipEncodingBom::new(BomType::all(), Cursor::new($bytes));
+            assert_eq!(&$expected[..], read_all(&mut reader).as_slice());
+            // rewinding without restarting the detection returns the BOM as content
+            let _ = reader.get_mut().seek(SeekFrom::Start(0)).unwrap();
+            assert_eq!(&$bytes[..], read_all(&mut reader).as_slice());
+            let _ = reader.get_mut().seek(SeekFrom::Start(0)).unwrap();
+            reader.restart_detection();
+            assert_eq!(BomStatus::Undetermined, reader.status());
+            assert_eq!(&$expected[..], read_all(&mut reader).as_slice());
+            assert_eq!(Some($expected_bom), reader.bom_found());
+            reader.rewind().unwrap();
+            assert_eq!(&$expected[..], read_all(&mut reader).as_slice());
+            assert_eq!(Some($expected_bom), reader.bom_found());
+        }
+    };
+}
+
+test_restart!(test_restart_utf8_bom, b"\xEF\xBB\xBFtext", b"text", Some(BomType::UTF8));
+test_restart!(test_restart_utf16le_bom, b"\xFF\xFEt\x00", b"t\x00", Some(BomType::UTF16LE));
+test_restart!(test_restart_no_bom, b"text", b"text", None);
+
+#[test]
+fn test_restart_while_undetermined() {
+    let mut reader = SkipEncodingBom::new(BomType::all(), Cursor::new(b"\xEF\xBB".to_vec()));
+    assert_eq!(b"", read_all(&mut reader).as_slice());
+    // the stream is replaced rather than appended to
+    *reader.get_mut() = Cursor::new(b"\xFF\xFEt\x00".to_vec());
+    reader.restart_detection();
+    assert_eq!(b"t\x00", read_all(&mut reader).as_slice());
+    assert_eq!(Some(Some(BomType::UTF16LE)), reader.bom_found());
+}
+
+#[test]
+fn test_restart_with_buffered_bytes() {
+    let mut reader = SkipEncodingBom::new(BomType::all(), Cursor::new(b"\xEF\xBB\xBFtext"));
+    let mut buf = [0; 1];
+    assert_eq!(1, reader.read(&mut buf).unwrap());
+    reader.rewind().unwrap();
+    assert_eq!(b"text", read_all(&mut reader).as_slice());
+}
@@ -0,0 +1,56 @@
+#![cfg(feature = "rspfile")]
+
+use skip_bom::rspfile::*;
+
+fn utf16le_bom(string: &str) -> Vec<u8> {
+    let mut bytes = vec![0xFF, 0xFE];
+    bytes.extend(string.encode_utf16().flat_map(u16::to_le_bytes));
+    bytes
+}
+
+macro_rules! test_split_args {
+    ($test_fn_name:ident, $text:expr, $expected:expr) => {
+        #[test]
+        fn $test_fn_name() {
+            let expected: Vec<String> = $expected.iter().map(|arg: &&str| arg.to_string()).collect();
+            assert_eq!(expected, split_args($text));
+        }
+    };
+}
+
+test_split_args!(test_split_args_plain, "/c /nologo main.c", ["/c", "/nologo", "main.c"]);
+test_split_args!(test_split_args_lines, "/c\r\n\r\n  main.c\t\r\nutil.c\r\n", ["/c", "main.c", "util.c"]);
+test_split_args!(test_split_args_quoted, "\"dir with spaces\\a.c\" /Fo\"out dir\\\\\"", ["dir with spaces\\a.c", "/Foout dir\\"]);
+test_split_args!(test_split_args_empty_quotes, "a \"\" b", ["a", "", "b"]);
+test_split_args!(test_split_args_escaped_quote, "/DNAME=\\\"value\\\"", ["/DNAME=\"value\""]);
+test_split_args!(test_split_args_double_quote_in_quotes, "\"say \"\"hi\"\"\"", ["say \"hi\""]);
+test_split_args!(test_split_args_literal_backslashes, "C:\\dir\\\\file.c \\\\\\\"", ["C:\\dir\\\\file.c", "\\\""]);
+test_split_args!(test_split_args_newline_in_quotes, "\"a\nb\" c", ["a\nb", "c"]);
+test_split_args!(test_split_args_unclosed_quote, "\"a b", ["a b"]);
+test_split_args!(test_split_args_stray_bom, "a \u{FEFF}b", ["a", "b"]);
+test_split_args!(test_split_args_empty, "  \r\n", [] as [&str; 0]);
+
+#[test]
+fn test_read_args_utf16le_bom() {
+    assert_eq!(vec!["/c", "main c.c"], read_args(&utf16le_bom("/c\r\n\"main c.c\"\r\n")[..]).unwrap());
+}
+
+#[test]
+fn test_read_args_utf8() {
+    assert_eq!(vec!["/c", "main.c"], read_args(&b"\xEF\xBB\xBF/c main.c"[..]).unwrap());
+    assert_eq!(vec!["/c", "main.c"], read_args(&b"/c main.c"[..]).unwrap());
+}
+
+#[test]
+fn test_read_args_invalid() {
+    assert_eq!(std::io::ErrorKind::InvalidData, read_args(&b"/c \xFF"[..]).unwrap_err().kind());
+}
+
+#[test]
+fn test_read_file() {
+    let path = std::env::temp_dir().join(format!("skip_bom_{}_test_rspfile_read_file.rsp", std::process::id()));
+    std::fs::write(&path, utf16le_bom("/nologo main.c")).unwrap();
+    let args = read_file(&path);
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(vec!["/nologo", "main.c"], args.unwrap());
+}
@@ -0,0 +1,32 @@
+#![cfg(feature = "schemars")]
+
+use schemars::schema::{InstanceType, SingleOrVec};
+use schemars::schema_for;
+use skip_bom::*;
+
+#[test]
+fn test_schema_bom_type() {
+    let schema = schema_for!(BomType);
+    // a documented string constant per variant
+    assert_eq!(BomType::all().len(), schema.schema.subschemas.as_ref().unwrap().one_of.as_ref().unwrap().len());
+}
+
+#[test]
+fn test_schema_bom_status() {
+    let schema = schema_for!(BomStatus);
+    assert_eq!(3, schema.schema.subschemas.as_ref().unwrap().one_of.as_ref().unwrap().len());
+    assert!(schema.definitions.contains_key("BomType"));
+}
+
+#[test]
+fn test_schema_encoding_report() {
+    let schema = schema_for!(EncodingReport);
+    assert_eq!(Some(SingleOrVec::Single(Box::new(InstanceType::Object))), schema.schema.instance_type);
+    let properties = &schema.schema.object.as_ref().unwrap().properties;
+    for property in &["bom", "sample_len", "empty_with_bom", "probably_binary", "candidates", "fingerprint"] {
+        assert!(properties.contains_key(*property), "{}", property);
+    }
+    for definition in &["BomType", "EncodingCandidate", "CandidateSource", "BuiltinSniffer"] {
+        assert!(schema.definitions.contains_key(*definition), "{}", definition);
+    }
+}
@@ -0,0 +1,65 @@
+use skip_bom::*;
+use std::io::Read;
+
+mod reader_test_utils;
+use reader_test_utils::*;
+
+fn test_shebang_bom(bytes: &[u8], expected: &[u8], expected_shebang: bool, expected_bom: Option<Option<BomType>>) {
+    for chunk_len in 1..=bytes.len().max(1) {
+        for buf_len in [1, 2, 3, 64].iter().copied() {
+            let mut reader = ShebangBom::new(BomType::all(), ChunkedReader { bytes, chunk_len });
+            let mut output = Vec::new();
+            let mut buf = vec![0; buf_len];
+            loop {
+                match reader.read(&mut buf).unwrap() {
+                    0 => break,
+                    len => output.extend_from_slice(&buf[..len]),
+                }
+            }
+            assert_eq!(expected, output.as_slice(), "chunk {} buffer {}", chunk_len, buf_len);
+            assert_eq!(Some(expected_shebang), reader.shebang(), "chunk {} buffer {}", chunk_len, buf_len);
+            assert_eq!(expected_bom, reader.bom_found(), "chunk {} buffer {}", chunk_len, buf_len);
+        }
+    }
+}
+
+macro_rules! test_shebang_bom {
+    ($test_fn_name:ident, $bytes:expr, $expected:expr, $expected_shebang:expr, $expected_bom:expr) => {
+        #[test]
+        fn $test_fn_name() {
+            test_shebang_bom($bytes, $expected, $expected_shebang, $expected_bom);
+        }
+    };
+}
+
+test_shebang_bom!(test_shebang_bom_after_shebang, b"#!/bin/sh\n\xEF\xBB\xBFecho", b"#!/bin/sh\necho", true, Some(Some(BomType::UTF8)));
+test_shebang_bom!(test_shebang_bom_after_crlf, b"#!/usr/bin/env python\r\n\xEF\xBB\xBFprint()", b"#!/usr/bin/env python\r\nprint()", true, Some(Some(BomType::UTF8)));
+test_shebang_bom!(test_shebang_bom_no_bom, b"#!/bin/sh\necho", b"#!/bin/sh\necho", true, Some(None));
+test_shebang_bom!(test_shebang_bom_in_shebang_line, b"#!\xEF\xBB\xBF/bin/sh\necho", b"#!\xEF\xBB\xBF/bin/sh\necho", true, Some(None));
+test_shebang_bom!(test_shebang_bom_third_line, b"#!/bin/sh\n\n\xEF\xBB\xBF", b"#!/bin/sh\n\n\xEF\xBB\xBF", true, Some(None));
+// the stream ends before the BOM presence can be determined, like with SkipEncodingBom
+test_shebang_bom!(test_shebang_bom_only_shebang, b"#!/bin/sh", b"#!/bin/sh", true, None);
+test_shebang_bom!(test_shebang_bom_at_start, b"\xEF\xBB\xBF#!/bin/sh\n", b"#!/bin/sh\n", false, Some(Some(BomType::UTF8)));
+test_shebang_bom!(test_shebang_bom_without_shebang, b"\xFF\xFEa\x00", b"a\x00", false, Some(Some(BomType::UTF16LE)));
+test_shebang_bom!(test_shebang_bom_hash, b"#", b"#", false, Some(None));
+test_shebang_bom!(test_shebang_bom_empty, b"", b"", false, None);
+
+#[test]
+fn test_shebang_bom_undetermined() {
+    let mut reader = ShebangBom::new(BomType::all(), ChunkedReader { bytes: b"#!/bin/sh\n", chunk_len: 3 });
+    assert_eq!(None, reader.shebang());
+    let mut buf = [0; 3];
+    assert_eq!(2, reader.read(&mut buf).unwrap());
+    assert_eq!((Some(true), None), (reader.shebang(), reader.bom_found()));
+}
+
+#[test]
+fn test_shebang_bom_into_inner() {
+    let mut reader = ShebangBom::new(&[BomType::UTF8], &b"#!/bin/sh\n\xEF\xBB\xBFecho"[..]);
+    let mut buf = [0; 10];
+    reader.read_exact(&mut buf).unwrap();
+    assert_eq!(b"#!/bin/sh\n", &buf);
+    // the BOM is not read until the next read
+    assert_eq!(b"\xEF\xBB\xBFecho", reader.get_ref());
+    assert_eq!(b"\xEF\xBB\xBFecho", reader.into_inner());
+}
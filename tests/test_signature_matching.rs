@@ -0,0 +1,76 @@
+use skip_bom::*;
+
+/// Reference implementation testing each BOM type in turn.
+fn reference_try_find_bytes_bom<'a>(tested_bytes: &'a [u8], bom_types_tested: &[BomType]) -> BomsBytesTest<'a> {
+    let order = [
+        BomType::UTF8,
+        BomType::UTF32LE,
+        BomType::UTF32BE,
+        BomType::UTF16LE,
+        BomType::UTF16BE,
+        BomType::UTF7,
+        BomType::UTF1,
+        BomType::UTFEBDIC,
+        BomType::SCSU,
+        BomType::BOCU1,
+        BomType::GB1803,
+    ];
+    let mut result = BomsBytesTest::Complete { bom_type: None, additional_bytes: tested_bytes };
+    for bom_type in order.iter().filter(|bom_type| bom_types_tested.contains(bom_type)) {
+        match bom_type.test_bytes(tested_bytes) {
+            BomBytesTest::Incomplete => result = BomsBytesTest::Incomplete,
+            BomBytesTest::NotBom => (),
+            BomBytesTest::StartsWithBom if result == BomsBytesTest::Incomplete => (),
+            BomBytesTest::StartsWithBom => return BomsBytesTest::Complete { bom_type: Some(*bom_type), additional_bytes: &tested_bytes[bom_type.bom_length()..] },
+        }
+    }
+    result
+}
+
+/// Every input of up to 5 bytes built from the bytes of the BOMs.
+fn inputs() -> Vec<Vec<u8>> {
+    let mut alphabet: Vec<u8> = BomType::all().iter().flat_map(|bom_type| bom_type.bom_bytes().iter().copied()).collect();
+    alphabet.push(b'a');
+    alphabet.sort_unstable();
+    alphabet.dedup();
+    let mut inputs = vec![Vec::new()];
+    let mut previous_length_inputs = vec![Vec::new()];
+    for length in 1..=5 {
+        // past the longest BOM, only extend the BOMs
+        let extended: Vec<Vec<u8>> = if length <= 3 { previous_length_inputs.clone() } else { BomType::all().iter().map(|bom_type| bom_type.bom_bytes().to_vec()).collect() };
+        previous_length_inputs = extended.iter().flat_map(|input| alphabet.iter().map(move |byte| [input.as_slice(), &[*byte]].concat())).collect();
+        inputs.extend(previous_length_inputs.iter().cloned());
+    }
+    inputs
+}
+
+fn test_matches_reference(bom_types: &[BomType]) {
+    for input in inputs() {
+        assert_eq!(reference_try_find_bytes_bom(&input, bom_types), BomType::try_find_bytes_bom(&input, bom_types), "{:02X?} with {:?}", input, bom_types);
+    }
+}
+
+macro_rules! test_matches_reference {
+    ($test_fn_name:ident, $bom_types:expr) => {
+        #[test]
+        fn $test_fn_name() {
+            test_matches_reference($bom_types);
+        }
+    };
+}
+
+test_matches_reference!(test_matching_all, BomType::all());
+test_matches_reference!(test_matching_none, &[]);
+test_matches_reference!(test_matching_utf8, &[BomType::UTF8]);
+test_matches_reference!(test_matching_utf16le, &[BomType::UTF16LE]);
+test_matches_reference!(test_matching_utf32le, &[BomType::UTF32LE]);
+test_matches_reference!(test_matching_web, &BomTypeSet::WEB);
+test_matches_reference!(test_matching_windows_text, &BomTypeSet::WINDOWS_TEXT);
+test_matches_reference!(test_matching_reversed, &[BomType::GB1803, BomType::UTF16LE, BomType::SCSU, BomType::UTF32LE, BomType::UTF8]);
+
+#[test]
+fn test_matching_every_single_type() {
+    for bom_type in BomType::all() {
+        test_matches_reference(&[*bom_type]);
+    }
+}
@@ -0,0 +1,60 @@
+use skip_bom::*;
+use std::io::{Cursor, Read};
+
+fn read_utf7(bytes: &[u8]) -> (Option<BomType>, Vec<u8>) {
+    let mut reader = SkipEncodingBom::new(BomType::all(), Cursor::new(bytes));
+    let mut buf = Vec::new();
+    let _ = reader.read_to_end(&mut buf).unwrap();
+    (reader.bom_found().flatten(), buf)
+}
+
+macro_rules! test_utf7_signature {
+    ($test_fn_name:ident, $bytes:expr, $signature_len:expr) => {
+        #[test]
+        fn $test_fn_name() {
+            let bytes: &[u8] = $bytes;
+            let pattern = BomType::UTF7.signature_pattern();
+            assert_eq!($signature_len, pattern.signature_len(bytes));
+            // the prefix is detected and skipped whatever follows it
+            assert_eq!((Some(BomType::UTF7), bytes[3..].to_vec()), read_utf7(bytes));
+            assert_eq!(Some(&bytes[3..]), BomType::UTF7.strip_prefix(bytes));
+        }
+    };
+}
+
+test_utf7_signature!(test_utf7_signature_38, b"\x2B\x2F\x76\x38text", Some(4));
+test_utf7_signature!(test_utf7_signature_38_2d, b"\x2B\x2F\x76\x38\x2Dtext", Some(5));
+test_utf7_signature!(test_utf7_signature_39, b"\x2B\x2F\x76\x39AA", Some(4));
+test_utf7_signature!(test_utf7_signature_39_2d, b"\x2B\x2F\x76\x39\x2D", Some(4));
+test_utf7_signature!(test_utf7_signature_2b, b"\x2B\x2F\x76\x2BAA", Some(4));
+test_utf7_signature!(test_utf7_signature_2f, b"\x2B\x2F\x76\x2FAA", Some(4));
+test_utf7_signature!(test_utf7_signature_other, b"\x2B\x2F\x76text", None::<usize>);
+
+#[test]
+fn test_signature_pattern_fixed() {
+    for bom_type in BomType::all().iter().filter(|bom_type| **bom_type != BomType::UTF7) {
+        let pattern = bom_type.signature_pattern();
+        assert_eq!(bom_type.bom_bytes(), pattern.prefix);
+        assert_eq!(bom_type.bom_length(), pattern.min_len());
+        assert_eq!(bom_type.bom_length(), pattern.max_len());
+        assert_eq!(Some(bom_type.bom_length()), pattern.signature_len(bom_type.bom_bytes()));
+    }
+}
+
+#[test]
+fn test_signature_pattern_utf7_lengths() {
+    let pattern = BomType::UTF7.signature_pattern();
+    assert_eq!(4, pattern.min_len());
+    assert_eq!(5, pattern.max_len());
+    assert_eq!(3, BomType::UTF7.bom_length());
+    assert_eq!(3, BomType::max_probe_len(&[BomType::UTF7]));
+}
+
+#[test]
+fn test_signature_pattern_incomplete() {
+    let pattern = BomType::UTF7.signature_pattern();
+    assert_eq!(BomBytesTest::Incomplete, pattern.test(b"\x2B\x2F\x76"));
+    assert_eq!(BomBytesTest::NotBom, pattern.test(b"\x2B\x2F\x76\x00"));
+    assert_eq!(BomBytesTest::StartsWithBom, BomType::UTF7.test_bytes(b"\x2B\x2F\x76"));
+    assert_eq!(Some(BomType::UTF7), BomType::find_bytes_bom_at_end(b"\x2B\x2F\x76", BomType::all()));
+}
@@ -0,0 +1,61 @@
+#![cfg(feature = "unstable")]
+
+use skip_bom::*;
+
+/// Proprietary format: records starting with a magic number, in UTF-16BE.
+struct RecordSniffer;
+
+impl Sniffer for RecordSniffer {
+    fn sniff(&self, sample: &[u8]) -> Option<(BomType, u8)> {
+        if sample.starts_with(b"REC1") {
+            Some((BomType::UTF16BE, 95))
+        } else {
+            None
+        }
+    }
+}
+
+fn never(_: &[u8]) -> Option<(BomType, u8)> {
+    None
+}
+
+fn best(bytes: &[u8], sniffers: &[&dyn Sniffer]) -> Option<(BomType, CandidateSource)> {
+    let report = detect_with_sniffers(bytes, &DetectOptions::default(), sniffers).unwrap();
+    report.best().map(|candidate| (candidate.encoding, candidate.source))
+}
+
+macro_rules! test_sniffer_chain {
+    ($name:ident, $bytes:expr, $expected:expr) => {
+        #[test]
+        fn $name() {
+            let sniffers: [&dyn Sniffer; 2] = [&never, &RecordSniffer];
+            assert_eq!($expected, best($bytes, &sniffers));
+        }
+    };
+}
+
+test_sniffer_chain!(test_sniffer_chain_recognized, b"REC1\x00a\x00b", Some((BomType::UTF16BE, CandidateSource::Custom(1))));
+// the chain only runs when no BOM is found
+test_sniffer_chain!(test_sniffer_chain_after_bom, b"\xEF\xBB\xBFREC1", Some((BomType::UTF8, CandidateSource::Bom)));
+test_sniffer_chain!(test_sniffer_chain_unrecognized, b"plain text", Some((BomType::UTF8, CandidateSource::Sniffer(BuiltinSniffer::Utf8))));
+
+#[test]
+fn test_sniffer_chain_empty() {
+    let bytes = b"h\x00e\x00l\x00l\x00o\x00";
+    assert_eq!(detect(&bytes[..], &DetectOptions::default()).unwrap(), detect_with_sniffers(&bytes[..], &DetectOptions::default(), &[]).unwrap());
+}
+
+#[test]
+fn test_sniffer_chain_confidence() {
+    // a custom candidate only replaces a built-in one for the same encoding with a higher confidence
+    let weak = |_: &[u8]| Some((BomType::UTF8, 1));
+    let report = detect_with_sniffers(&b"plain text"[..], &DetectOptions::default(), &[&weak]).unwrap();
+    assert_eq!(1, report.candidates.len());
+    assert_eq!(CandidateSource::Sniffer(BuiltinSniffer::Utf8), report.candidates[0].source);
+}
+
+#[test]
+fn test_builtin_sniffer_trait() {
+    let sniffer: &dyn Sniffer = &BuiltinSniffer::Utf16;
+    assert_eq!(BuiltinSniffer::Utf16.sniff(b"h\x00i\x00"), sniffer.sniff(b"h\x00i\x00"));
+}
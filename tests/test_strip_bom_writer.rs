@@ -0,0 +1,60 @@
+use skip_bom::*;
+use std::io::Write;
+
+fn write_chunks(bytes: &[u8], chunk_len: usize, flush_policy: FlushPolicy) -> (BomStatus, Vec<u8>) {
+    let mut writer = StripBomWriter::new(BomType::all(), Vec::new()).with_flush_policy(flush_policy);
+    for chunk in bytes.chunks(chunk_len) {
+        writer.write_all(chunk).unwrap();
+    }
+    let status = writer.status();
+    (status, writer.finish().unwrap())
+}
+
+fn test_strip(bytes: &[u8], flush_policy: FlushPolicy, expected_status: BomStatus, expected: &[u8]) {
+    for chunk_len in 1..=bytes.len().max(1) {
+        let (status, written) = write_chunks(bytes, chunk_len, flush_policy);
+        assert_eq!(expected, written.as_slice(), "chunks of {} bytes", chunk_len);
+        if expected_status.is_determined() {
+            assert_eq!(expected_status, status);
+        }
+    }
+}
+
+macro_rules! test_strip {
+    ($test_fn_name:ident, $bytes:expr, $flush_policy:expr, $expected_status:expr, $expected:expr) => {
+        #[test]
+        fn $test_fn_name() {
+            test_strip($bytes, $flush_policy, $expected_status, $expected);
+        }
+    };
+}
+
+test_strip!(test_strip_utf8, b"\xEF\xBB\xBFThis stream has a BOM.", FlushPolicy::WriteBuffered, BomStatus::Bom(BomType::UTF8), b"This stream has a BOM.");
+test_strip!(test_strip_utf32le, b"\xFF\xFE\x00\x00a\x00\x00\x00", FlushPolicy::WriteBuffered, BomStatus::Bom(BomType::UTF32LE), b"a\x00\x00\x00");
+test_strip!(test_strip_no_bom, b"This stream has no BOM.", FlushPolicy::WriteBuffered, BomStatus::NoBom, b"This stream has no BOM.");
+test_strip!(test_strip_bom_only, b"\xEF\xBB\xBF", FlushPolicy::WriteBuffered, BomStatus::Bom(BomType::UTF8), b"");
+test_strip!(test_strip_empty, b"", FlushPolicy::WriteBuffered, BomStatus::Undetermined, b"");
+test_strip!(test_strip_ambiguous_write, b"\xEF\xBB", FlushPolicy::WriteBuffered, BomStatus::Undetermined, b"\xEF\xBB");
+test_strip!(test_strip_ambiguous_drop, b"\xEF\xBB", FlushPolicy::Drop, BomStatus::Undetermined, b"");
+test_strip!(test_strip_ambiguous_utf16le_write, b"\xFF\xFE\x00", FlushPolicy::WriteBuffered, BomStatus::Undetermined, b"\x00");
+test_strip!(test_strip_ambiguous_utf16le_drop, b"\xFF\xFE\x00", FlushPolicy::Drop, BomStatus::Undetermined, b"");
+
+#[test]
+fn test_strip_status_after_finish_policy() {
+    let mut writer = StripBomWriter::new(BomType::all(), Vec::new());
+    writer.write_all(b"\xFF\xFE").unwrap();
+    assert_eq!(None, writer.bom_found());
+    assert!(writer.get_ref().is_empty());
+    assert!(writer.finish().unwrap().is_empty());
+}
+
+#[test]
+fn test_strip_flush_writes_pending() {
+    let mut writer = StripBomWriter::new(&[BomType::UTF8], Vec::new());
+    assert_eq!(3, writer.write(b"abcdef").unwrap());
+    assert_eq!(BomStatus::NoBom, writer.status());
+    writer.flush().unwrap();
+    assert_eq!(b"abc", writer.get_ref().as_slice());
+    writer.write_all(b"def").unwrap();
+    assert_eq!(b"abcdef", writer.finish().unwrap().as_slice());
+}
@@ -0,0 +1,181 @@
+use skip_bom::*;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+mod fs_test_utils;
+use fs_test_utils::*;
+
+fn test_strip_file(name: &str, content: &[u8], expected_bom: Option<BomType>) {
+    let file = TempFile::new(name, content);
+    let bom_len = expected_bom.map_or(0, |bom_type| bom_type.bom_length());
+    // dry run
+    let options = StripFileOptions { dry_run: true, ..Default::default() };
+    let report = strip_bom_file(&file.0, BomType::all(), &options).unwrap();
+    assert_eq!(expected_bom, report.bom);
+    assert_eq!(content.len() as u64, report.original_len);
+    assert_eq!(0..bom_len as u64, report.removed_range());
+    assert_eq!(&content[..bom_len], report.removed_bytes());
+    assert_eq!((content.len() - bom_len) as u64, report.new_len());
+    assert!(!report.modified);
+    assert_eq!(content, std::fs::read(&file.0).unwrap().as_slice());
+    // actual run
+    let report = strip_bom_file(&file.0, BomType::all(), &StripFileOptions::default()).unwrap();
+    assert_eq!(expected_bom, report.bom);
+    assert_eq!(expected_bom.is_some(), report.modified);
+    assert_eq!(None, report.backup);
+    assert_eq!(&content[bom_len..], std::fs::read(&file.0).unwrap().as_slice());
+}
+
+macro_rules! test_strip_file {
+    ($test_fn_name:ident, $content:expr, $expected_bom:expr) => {
+        #[test]
+        fn $test_fn_name() {
+            test_strip_file(stringify!($test_fn_name), $content, $expected_bom);
+        }
+    };
+}
+
+test_strip_file!(test_strip_file_utf8_bom, b"\xEF\xBB\xBFid,name\r\n1,a\r\n", Some(BomType::UTF8));
+test_strip_file!(test_strip_file_utf32le_bom, b"\xFF\xFE\x00\x00a\x00\x00\x00", Some(BomType::UTF32LE));
+test_strip_file!(test_strip_file_bom_only, b"\xFF\xFE", Some(BomType::UTF16LE));
+test_strip_file!(test_strip_file_no_bom, b"id,name\r\n", None);
+test_strip_file!(test_strip_file_incomplete_bom, b"\xEF\xBB", None);
+test_strip_file!(test_strip_file_empty, b"", None);
+
+#[test]
+fn test_strip_file_backup() {
+    let file = TempFile::new("test_strip_file_backup", b"\xEF\xBB\xBFtext");
+    let options = StripFileOptions { backup: true, ..Default::default() };
+    let report = strip_bom_file(&file.0, BomType::all(), &options).unwrap();
+    assert_eq!(Some(file.backup_path()), report.backup);
+    assert_eq!(b"\xEF\xBB\xBFtext", std::fs::read(file.backup_path()).unwrap().as_slice());
+    assert_eq!(b"text", std::fs::read(&file.0).unwrap().as_slice());
+}
+
+#[test]
+fn test_strip_file_backup_unchanged() {
+    let file = TempFile::new("test_strip_file_backup_unchanged", b"text");
+    let options = StripFileOptions { backup: true, ..Default::default() };
+    assert_eq!(None, strip_bom_file(&file.0, BomType::all(), &options).unwrap().backup);
+    assert!(!file.backup_path().exists());
+    // no backup in a dry run either
+    let file = TempFile::new("test_strip_file_backup_dry_run", b"\xEF\xBB\xBFtext");
+    let options = StripFileOptions { backup: true, dry_run: true, ..Default::default() };
+    assert_eq!(None, strip_bom_file(&file.0, BomType::all(), &options).unwrap().backup);
+    assert!(!file.backup_path().exists());
+}
+
+#[test]
+fn test_strip_file_only_some_boms() {
+    let file = TempFile::new("test_strip_file_only_some_boms", b"\xEF\xBB\xBFtext");
+    let report = strip_bom_file(&file.0, &[BomType::UTF16LE], &StripFileOptions::default()).unwrap();
+    assert_eq!(None, report.bom);
+    assert_eq!(b"\xEF\xBB\xBFtext", std::fs::read(&file.0).unwrap().as_slice());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_strip_file_permissions() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let file = TempFile::new("test_strip_file_permissions", b"\xEF\xBB\xBF#!/bin/sh\n");
+    std::fs::set_permissions(&file.0, std::fs::Permissions::from_mode(0o751)).unwrap();
+    let _ = strip_bom_file(&file.0, BomType::all(), &StripFileOptions::default()).unwrap();
+    assert_eq!(0o751, std::fs::metadata(&file.0).unwrap().permissions().mode() & 0o777);
+}
+
+#[test]
+fn test_strip_file_not_found() {
+    let path = std::env::temp_dir().join(format!("skip_bom_{}_test_strip_file_not_found", std::process::id()));
+    assert_eq!(ErrorKind::NotFound, strip_bom_file(&path, BomType::all(), &StripFileOptions::default()).unwrap_err().kind());
+}
+
+#[test]
+fn test_strip_file_preserve_metadata() {
+    let content: Vec<u8> = b"\xEF\xBB\xBF".iter().copied().chain((0..200_000).map(|i| (i % 251) as u8)).collect();
+    let file = TempFile::new("test_strip_file_preserve_metadata", &content);
+    let options = StripFileOptions { preserve_metadata: true, ..Default::default() };
+    let report = strip_bom_file(&file.0, BomType::all(), &options).unwrap();
+    assert!(report.modified);
+    assert_eq!(&content[3..], std::fs::read(&file.0).unwrap().as_slice());
+}
+
+#[test]
+fn test_strip_file_preserve_metadata_hard_link() {
+    let file = TempFile::new("test_strip_file_preserve_metadata_hard_link", b"\xFF\xFEa\x00");
+    let link = TempFile(std::env::temp_dir().join(format!("skip_bom_{}_test_strip_file_preserve_metadata_hard_link.link", std::process::id())));
+    let _ = std::fs::remove_file(&link.0);
+    std::fs::hard_link(&file.0, &link.0).unwrap();
+    let options = StripFileOptions { preserve_metadata: true, ..Default::default() };
+    let _ = strip_bom_file(&file.0, BomType::all(), &options).unwrap();
+    // the file is the same: the change is visible through the other link
+    assert_eq!(b"a\x00", std::fs::read(&link.0).unwrap().as_slice());
+    // the replacement breaks the link
+    std::fs::write(&file.0, b"\xFF\xFEb\x00").unwrap();
+    let _ = strip_bom_file(&file.0, BomType::all(), &StripFileOptions::default()).unwrap();
+    assert_eq!(b"\xFF\xFEb\x00", std::fs::read(&link.0).unwrap().as_slice());
+}
+
+fn test_strip_file_progress(name: &str, options: StripFileOptions) {
+    let content: Vec<u8> = b"\xEF\xBB\xBF".iter().copied().chain((0..150_000).map(|i| (i % 251) as u8)).collect();
+    let file = TempFile::new(name, &content);
+    let mut calls = Vec::new();
+    let report = strip_bom_file_with_progress(&file.0, BomType::all(), &options, None, |done, total| calls.push((done, total))).unwrap();
+    assert!(calls.len() > 1, "{:?}", calls);
+    assert!(calls.windows(2).all(|pair| pair[0].0 < pair[1].0), "{:?}", calls);
+    assert!(calls.iter().all(|(_, total)| *total == report.new_len()));
+    assert_eq!(Some(&(150_000, 150_000)), calls.last());
+    assert_eq!(&content[3..], std::fs::read(&file.0).unwrap().as_slice());
+}
+
+#[test]
+fn test_strip_file_progress_replace() {
+    test_strip_file_progress("test_strip_file_progress_replace", StripFileOptions::default());
+}
+
+#[test]
+fn test_strip_file_progress_preserve_metadata() {
+    test_strip_file_progress("test_strip_file_progress_preserve_metadata", StripFileOptions { preserve_metadata: true, ..Default::default() });
+}
+
+#[test]
+fn test_strip_file_progress_no_bom() {
+    let file = TempFile::new("test_strip_file_progress_no_bom", b"text");
+    let mut called = false;
+    let _ = strip_bom_file_with_progress(&file.0, BomType::all(), &StripFileOptions::default(), None, |_, _| called = true).unwrap();
+    assert!(!called);
+}
+
+#[test]
+fn test_strip_file_cancelled() {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    let content: Vec<u8> = b"\xEF\xBB\xBF".iter().copied().chain(std::iter::repeat_n(b'a', 300_000)).collect();
+    let file = TempFile::new("test_strip_file_cancelled", &content);
+    let cancel = AtomicBool::new(false);
+    let error = strip_bom_file_with_progress(&file.0, BomType::all(), &StripFileOptions::default(), Some(&cancel), |_, _| cancel.store(true, Ordering::Relaxed)).unwrap_err();
+    assert_eq!(Some(BomErrorCode::Cancelled), BomErrorCode::of(&error));
+    // the original file and its directory are left unchanged
+    assert_eq!(content, std::fs::read(&file.0).unwrap());
+    let mut temp_path = file.0.clone().into_os_string();
+    temp_path.push(".skip_bom.tmp");
+    assert!(!PathBuf::from(temp_path).exists());
+    // cancelled before the rewrite
+    let options = StripFileOptions { preserve_metadata: true, ..Default::default() };
+    let error = strip_bom_file_with_progress(&file.0, BomType::all(), &options, Some(&cancel), |_, _| {}).unwrap_err();
+    assert_eq!(Some(BomErrorCode::Cancelled), BomErrorCode::of(&error));
+    assert_eq!(content, std::fs::read(&file.0).unwrap());
+}
+
+#[test]
+fn test_strip_file_max_bytes_per_second() {
+    for preserve_metadata in [false, true].iter().copied() {
+        let content: Vec<u8> = b"\xEF\xBB\xBF".iter().copied().chain(std::iter::repeat_n(b'a', 30_000)).collect();
+        let file = TempFile::new("test_strip_file_max_bytes_per_second", &content);
+        let options = StripFileOptions { preserve_metadata, max_bytes_per_second: std::num::NonZeroU64::new(100_000), ..Default::default() };
+        let start = std::time::Instant::now();
+        let _ = strip_bom_file(&file.0, BomType::all(), &options).unwrap();
+        assert!(start.elapsed() >= std::time::Duration::from_millis(200), "{:?}", start.elapsed());
+        assert_eq!(&content[3..], std::fs::read(&file.0).unwrap().as_slice());
+    }
+}
@@ -0,0 +1,40 @@
+use skip_bom::*;
+use std::io::{Cursor, Read};
+
+fn test_tee(bytes: &[u8], expected_content: &[u8], expected_bom: Option<BomType>) {
+    let mut reader = SkipEncodingBom::new_tee(BomType::all(), bytes, Vec::new());
+    let mut buf = Vec::new();
+    let _ = reader.read_to_end(&mut buf).unwrap();
+    assert_eq!(expected_content, buf.as_slice());
+    assert_eq!(Some(expected_bom), reader.bom_found());
+    assert_eq!(bytes, reader.get_ref().writer().as_slice());
+}
+
+#[test]
+fn test_tee_bom() {
+    test_tee(b"\xEF\xBB\xBFThis stream has a BOM.", b"This stream has a BOM.", Some(BomType::UTF8));
+}
+
+#[test]
+fn test_tee_no_bom() {
+    test_tee(b"This stream has no BOM.", b"This stream has no BOM.", None);
+}
+
+#[test]
+fn test_tee_only_bom() {
+    test_tee(b"\xFE\xFF", b"", Some(BomType::UTF16BE));
+}
+
+#[test]
+fn test_tee_small_reads() {
+    let bytes = b"\xEF\xBB\xBFThis stream has a BOM.";
+    let mut reader = SkipEncodingBom::new_tee(BomType::all(), Cursor::new(&bytes[..]), Vec::new());
+    let mut small_buf = [0u8; 1];
+    assert_eq!(1, reader.read(&mut small_buf).unwrap());
+    assert_eq!(b"T", &small_buf);
+    // the tee receives the bytes consumed from the underlying reader, including those buffered by the BOM detection
+    assert_eq!(b"\xEF\xBB\xBFT", reader.get_ref().writer().as_slice());
+    let mut buf = Vec::new();
+    let _ = reader.read_to_end(&mut buf).unwrap();
+    assert_eq!(&bytes[..], reader.get_ref().writer().as_slice());
+}
@@ -0,0 +1,104 @@
+use skip_bom::*;
+use std::io::Read;
+
+mod reader_test_utils;
+use reader_test_utils::*;
+
+fn sanitize<R: Read>(reader: R, options: SanitizeOptions, chunk_len: usize) -> Vec<u8> {
+    let mut sanitizer = TextSanitizer::new(BomType::all(), reader).with_options(options);
+    let mut output = Vec::new();
+    let mut chunk = vec![0u8; chunk_len];
+    loop {
+        match sanitizer.read(&mut chunk).unwrap() {
+            0 => break output,
+            bytes_read => output.extend(&chunk[..bytes_read]),
+        }
+    }
+}
+
+fn test_sanitize(bytes: &[u8], options: SanitizeOptions, expected: &[u8]) {
+    for chunk_len in 1..=bytes.len().max(1) {
+        assert_eq!(expected, sanitize(bytes, options, chunk_len).as_slice(), "chunks of {} bytes", chunk_len);
+        assert_eq!(expected, sanitize(ByteByByteReader(bytes), options, chunk_len).as_slice(), "byte by byte, chunks of {} bytes", chunk_len);
+    }
+}
+
+const ALL: SanitizeOptions = SanitizeOptions { normalize_newlines: true, strip_eof_marker: true, control_chars: ControlCharPolicy::Allow, control_chars_window: 4096 };
+const NEWLINES: SanitizeOptions = SanitizeOptions { normalize_newlines: true, strip_eof_marker: false, control_chars: ControlCharPolicy::Allow, control_chars_window: 4096 };
+const EOF_MARKER: SanitizeOptions = SanitizeOptions { normalize_newlines: false, strip_eof_marker: true, control_chars: ControlCharPolicy::Allow, control_chars_window: 4096 };
+const NONE: SanitizeOptions = SanitizeOptions { normalize_newlines: false, strip_eof_marker: false, control_chars: ControlCharPolicy::Allow, control_chars_window: 4096 };
+
+macro_rules! test_sanitize {
+    ($test_fn_name:ident, $bytes:expr, $options:expr, $expected:expr) => {
+        #[test]
+        fn $test_fn_name() {
+            test_sanitize($bytes, $options, $expected);
+        }
+    };
+}
+
+test_sanitize!(test_sanitize_all, b"\xEF\xBB\xBFa\r\nb\r\n\x1A", ALL, b"a\nb\n");
+test_sanitize!(test_sanitize_no_bom, b"a\r\nb", ALL, b"a\nb");
+test_sanitize!(test_sanitize_lone_cr, b"a\rb\r", ALL, b"a\rb\r");
+test_sanitize!(test_sanitize_cr_cr_lf, b"a\r\r\nb", ALL, b"a\r\nb");
+test_sanitize!(test_sanitize_inner_eof_marker, b"a\x1Ab\x1A\x1A", ALL, b"a\x1Ab\x1A");
+test_sanitize!(test_sanitize_cr_eof_marker, b"a\r\x1A", ALL, b"a\r");
+test_sanitize!(test_sanitize_only_eof_marker, b"\x1A", ALL, b"");
+test_sanitize!(test_sanitize_empty, b"", ALL, b"");
+test_sanitize!(test_sanitize_newlines_only, b"a\r\nb\x1A", NEWLINES, b"a\nb\x1A");
+test_sanitize!(test_sanitize_eof_marker_only, b"a\r\nb\x1A", EOF_MARKER, b"a\r\nb");
+test_sanitize!(test_sanitize_none, b"\xEF\xBB\xBFa\r\nb\x1A", NONE, b"a\r\nb\x1A");
+
+#[test]
+fn test_sanitize_default_options() {
+    assert_eq!(ALL, SanitizeOptions::default());
+}
+
+#[test]
+fn test_sanitize_bom_found() {
+    let mut sanitizer = TextSanitizer::new(BomType::all(), &b"\xFF\xFEa\x00"[..]);
+    let mut buf = Vec::new();
+    let _ = sanitizer.read_to_end(&mut buf).unwrap();
+    assert_eq!(BomStatus::Bom(BomType::UTF16LE), sanitizer.status());
+    assert_eq!(Some(Some(BomType::UTF16LE)), sanitizer.bom_found());
+}
+
+fn read_control_chars(bytes: &[u8], control_chars: ControlCharPolicy, control_chars_window: usize) -> (std::io::Result<usize>, usize) {
+    let options = SanitizeOptions { control_chars, control_chars_window, ..Default::default() };
+    let mut sanitizer = TextSanitizer::new(BomType::all(), ByteByByteReader(bytes)).with_options(options);
+    let result = sanitizer.read_to_end(&mut Vec::new());
+    (result, sanitizer.control_char_count())
+}
+
+#[test]
+fn test_sanitize_control_chars_allow() {
+    let (result, count) = read_control_chars(b"a\x00b\x07", ControlCharPolicy::Allow, 4096);
+    assert_eq!(4, result.unwrap());
+    assert_eq!(0, count);
+}
+
+#[test]
+fn test_sanitize_control_chars_count() {
+    let (result, count) = read_control_chars(b"\xEF\xBB\xBFa\x00b\tc\r\nd\x1Be\x7F\x1A", ControlCharPolicy::Count, 4096);
+    assert_eq!(10, result.unwrap());
+    // the trailing ^Z is stripped and not counted
+    assert_eq!(2, count);
+}
+
+#[test]
+fn test_sanitize_control_chars_window() {
+    let (_, count) = read_control_chars(b"\x01\x02ab\x03\x04", ControlCharPolicy::Count, 4);
+    assert_eq!(2, count);
+    let (_, count) = read_control_chars(b"ab\x01", ControlCharPolicy::Count, 0);
+    assert_eq!(0, count);
+}
+
+#[test]
+fn test_sanitize_control_chars_reject() {
+    let (result, _) = read_control_chars(b"text\x00", ControlCharPolicy::Reject, 4096);
+    assert_eq!(std::io::ErrorKind::InvalidData, result.unwrap_err().kind());
+    let (result, _) = read_control_chars(b"text\r\n\ttext\x1A", ControlCharPolicy::Reject, 4096);
+    assert_eq!(10, result.unwrap());
+    let (result, _) = read_control_chars(b"text\x00", ControlCharPolicy::Reject, 4);
+    assert_eq!(5, result.unwrap());
+}
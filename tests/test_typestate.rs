@@ -0,0 +1,69 @@
+use skip_bom::typestate::{Detected, SkipEncodingBom};
+use skip_bom::BomType;
+use std::io::{Cursor, Error, ErrorKind, Read};
+
+fn test_typestate(bytes: &[u8], expected_bom: Option<BomType>, expected_content: &[u8]) {
+    let mut reader = SkipEncodingBom::new(BomType::all(), Cursor::new(bytes)).detect().unwrap();
+    assert_eq!(expected_bom, reader.bom_found());
+    let mut buf = Vec::new();
+    let _ = reader.read_to_end(&mut buf).unwrap();
+    assert_eq!(expected_content, buf.as_slice());
+    assert_eq!(expected_bom, reader.into_untyped().bom_found().flatten());
+}
+
+macro_rules! test_typestate {
+    ($test_fn_name:ident, $bytes:expr, $expected_bom:expr, $expected_content:expr) => {
+        #[test]
+        fn $test_fn_name() {
+            test_typestate($bytes, $expected_bom, $expected_content);
+        }
+    };
+}
+
+test_typestate!(test_typestate_utf8, b"\xEF\xBB\xBFThis stream has a BOM.", Some(BomType::UTF8), b"This stream has a BOM.");
+test_typestate!(test_typestate_utf16be, b"\xFE\xFF\x00a", Some(BomType::UTF16BE), b"\x00a");
+test_typestate!(test_typestate_no_bom, b"This stream has no BOM.", None, b"This stream has no BOM.");
+test_typestate!(test_typestate_empty, b"", None, b"");
+test_typestate!(test_typestate_truncated_bom, b"\xEF\xBB", None, b"\xEF\xBB");
+
+#[test]
+fn test_typestate_bom_not_enabled() {
+    let mut reader = SkipEncodingBom::new(&[BomType::UTF32LE], Cursor::new(b"\xFF\xFE\x00")).detect().unwrap();
+    assert_eq!(None, reader.bom_found());
+    let mut buf = Vec::new();
+    let _ = reader.read_to_end(&mut buf).unwrap();
+    assert_eq!(b"\xFF\xFE\x00", buf.as_slice());
+    assert_eq!(Some(None), reader.into_untyped().bom_found());
+}
+
+/// Reader interrupted on its first read.
+struct InterruptedOnceReader<'a> {
+    bytes: &'a [u8],
+    interrupted: bool,
+}
+
+impl Read for InterruptedOnceReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if !self.interrupted {
+            self.interrupted = true;
+            return Err(Error::new(ErrorKind::Interrupted, "interrupted"));
+        }
+        self.bytes.read(buf)
+    }
+}
+
+#[test]
+fn test_typestate_detect_interrupted() {
+    let reader = SkipEncodingBom::new(BomType::all(), InterruptedOnceReader { bytes: b"\xEF\xBB\xBFabc", interrupted: false });
+    let reader: SkipEncodingBom<_, Detected> = reader.detect().unwrap();
+    assert_eq!(Some(BomType::UTF8), reader.bom_found());
+}
+
+#[test]
+fn test_typestate_accessors() {
+    let mut reader = SkipEncodingBom::new(BomType::all(), Cursor::new(b"\xEF\xBB\xBFabc".to_vec()));
+    assert_eq!(0, reader.get_ref().position());
+    reader.get_mut().get_mut().extend_from_slice(b"def");
+    let reader = reader.detect().unwrap();
+    assert_eq!(b"\xEF\xBB\xBFabcdef", reader.into_inner().get_ref().as_slice());
+}
@@ -0,0 +1,50 @@
+use skip_bom::*;
+use std::io::{Cursor, Read};
+
+const BYTES: &[u8] = b"\xEF\xBB\xBFThis stream has a BOM.";
+
+fn read_bom_dyn(reader: &mut SkipEncodingBom<dyn Read>) -> Option<BomType> {
+    reader.read_bom().unwrap()
+}
+
+fn read_to_string<R: Read + ?Sized>(reader: &mut R) -> String {
+    let mut string = String::new();
+    let _ = reader.read_to_string(&mut string).unwrap();
+    string
+}
+
+#[test]
+fn test_unsized_boxed() {
+    let mut reader: Box<SkipEncodingBom<dyn Read>> = Box::new(SkipEncodingBom::new(BomType::all(), Cursor::new(BYTES)));
+    assert_eq!(Some(BomType::UTF8), read_bom_dyn(&mut reader));
+    assert_eq!("This stream has a BOM.", read_to_string(&mut reader));
+    assert_eq!(BomStatus::Bom(BomType::UTF8), reader.status());
+}
+
+#[test]
+fn test_unsized_boxed_byte_by_byte() {
+    let mut reader: Box<SkipEncodingBom<dyn Read>> = Box::new(SkipEncodingBom::new(BomType::all(), Cursor::new(BYTES)));
+    let mut bytes = Vec::new();
+    let mut byte = [0u8; 1];
+    while reader.read(&mut byte).unwrap() == 1 {
+        bytes.push(byte[0]);
+    }
+    assert_eq!(b"This stream has a BOM.", bytes.as_slice());
+}
+
+#[test]
+fn test_unsized_resolve() {
+    let mut reader: Box<SkipEncodingBom<dyn Read>> = Box::new(SkipEncodingBom::new(BomType::all(), Cursor::new(BYTES)));
+    let mut resolved = reader.resolve().unwrap().unwrap();
+    assert_eq!(Some(BomType::UTF8), resolved.bom());
+    assert_eq!("This stream has a BOM.", read_to_string(&mut resolved));
+}
+
+#[test]
+fn test_dyn_read_reference() {
+    let mut inner = Cursor::new(BYTES);
+    let dyn_reader: &mut dyn Read = &mut inner;
+    let mut reader = SkipEncodingBom::new(BomType::all(), dyn_reader);
+    assert_eq!("This stream has a BOM.", read_to_string(&mut reader));
+    assert_eq!(Some(Some(BomType::UTF8)), reader.bom_found());
+}
@@ -0,0 +1,77 @@
+use skip_bom::*;
+use std::io::{ErrorKind, Read};
+
+mod reader_test_utils;
+use reader_test_utils::*;
+
+/// Check that the chunks rebuild the content like `String::from_utf8_lossy` whatever the size of the reads.
+fn test_utf8_chunks(bytes: &[u8], content: &[u8], expected_bom: Option<BomType>) {
+    for chunk_len in 1..=bytes.len().max(1) {
+        let mut chunks = Utf8Chunks::new(BomType::all(), ChunkedReader { bytes, chunk_len });
+        let mut lossy = String::new();
+        let mut rebuilt = Vec::new();
+        for chunk in &mut chunks {
+            let chunk = chunk.unwrap();
+            assert!(!chunk.valid().is_empty() || !chunk.invalid().is_empty());
+            assert!(chunk.invalid().len() <= 3);
+            lossy.push_str(chunk.valid());
+            rebuilt.extend_from_slice(chunk.valid().as_bytes());
+            if !chunk.invalid().is_empty() {
+                lossy.push('\u{FFFD}');
+                rebuilt.extend_from_slice(chunk.invalid());
+            }
+        }
+        assert_eq!(String::from_utf8_lossy(content), lossy, "chunk length {}", chunk_len);
+        assert_eq!(content, rebuilt.as_slice());
+        assert_eq!(Some(expected_bom), chunks.bom_found());
+    }
+}
+
+macro_rules! test_utf8_chunks {
+    ($name:ident, $bytes:expr, $content:expr, $bom:expr) => {
+        #[test]
+        fn $name() {
+            test_utf8_chunks($bytes, $content, $bom);
+        }
+    };
+}
+
+test_utf8_chunks!(test_utf8_chunks_valid, "\u{FEFF}caf\u{E9} \u{1F600}".as_bytes(), "caf\u{E9} \u{1F600}".as_bytes(), Some(BomType::UTF8));
+test_utf8_chunks!(test_utf8_chunks_no_bom, b"plain text", b"plain text", None);
+test_utf8_chunks!(test_utf8_chunks_invalid_bytes, b"\xEF\xBB\xBFa\xFFb\xC3c\xE2\x82d", b"a\xFFb\xC3c\xE2\x82d", Some(BomType::UTF8));
+test_utf8_chunks!(test_utf8_chunks_consecutive_invalid, b"\xFF\xFE\xFD", b"\xFD", Some(BomType::UTF16LE));
+test_utf8_chunks!(test_utf8_chunks_incomplete_at_end, b"text\xF0\x9F\x98", b"text\xF0\x9F\x98", None);
+test_utf8_chunks!(test_utf8_chunks_surrogate, b"a\xED\xA0\x80b", b"a\xED\xA0\x80b", None);
+
+#[test]
+fn test_utf8_chunks_empty() {
+    assert_eq!(0, Utf8Chunks::new(BomType::all(), &b""[..]).count());
+    assert_eq!(0, Utf8Chunks::new(BomType::all(), &b"\xEF\xBB\xBF"[..]).count());
+}
+
+#[test]
+fn test_utf8_chunks_parts() {
+    let chunks: Vec<Utf8Chunk> = Utf8Chunks::new(BomType::all(), &b"ab\xFFcd"[..]).collect::<Result<_>>().unwrap();
+    assert_eq!(vec![("ab".to_string(), vec![0xFF]), ("cd".to_string(), vec![])], chunks.into_iter().map(Utf8Chunk::into_parts).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_utf8_chunks_large() {
+    // the text is yielded in several chunks, none larger than a read
+    let text = "\u{E9}t\u{E9} ".repeat(10_000);
+    let chunks: Vec<Utf8Chunk> = Utf8Chunks::new(BomType::all(), text.as_bytes()).collect::<Result<_>>().unwrap();
+    assert!(chunks.len() > 1);
+    assert_eq!(text, chunks.iter().map(Utf8Chunk::valid).collect::<String>());
+}
+
+#[test]
+fn test_utf8_chunks_error() {
+    struct FailingReader;
+    impl Read for FailingReader {
+        fn read(&mut self, _: &mut [u8]) -> std::io::Result<usize> {
+            Err(std::io::Error::new(ErrorKind::BrokenPipe, "broken"))
+        }
+    }
+    let mut chunks = Utf8Chunks::new(BomType::all(), FailingReader);
+    assert_eq!(ErrorKind::BrokenPipe, chunks.next().unwrap().unwrap_err().kind());
+}
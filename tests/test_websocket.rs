@@ -0,0 +1,42 @@
+#![cfg(feature = "websocket")]
+
+use skip_bom::websocket::*;
+use skip_bom::{BomErrorCode, BomType};
+use std::io::ErrorKind;
+
+macro_rules! test_sanitize {
+    ($test_fn_name:ident, $reject_utf16:expr, $payload:expr, $expected:expr) => {
+        #[test]
+        fn $test_fn_name() {
+            let sanitizer = TextFrameSanitizer { reject_utf16: $reject_utf16 };
+            assert_eq!($expected.map(|bytes: &[u8]| bytes), sanitizer.sanitize($payload).ok());
+        }
+    };
+}
+
+test_sanitize!(test_sanitize_utf8_bom, false, b"\xEF\xBB\xBFhello", Some(&b"hello"[..]));
+test_sanitize!(test_sanitize_bom_only, false, b"\xEF\xBB\xBF", Some(&b""[..]));
+test_sanitize!(test_sanitize_no_bom, true, b"hello", Some(&b"hello"[..]));
+test_sanitize!(test_sanitize_empty, true, b"", Some(&b""[..]));
+test_sanitize!(test_sanitize_bom_in_middle, true, b"a\xEF\xBB\xBF", Some(&b"a\xEF\xBB\xBF"[..]));
+test_sanitize!(test_sanitize_utf16le_allowed, false, b"\xFF\xFEh\x00", Some(&b"\xFF\xFEh\x00"[..]));
+test_sanitize!(test_sanitize_utf16le_rejected, true, b"\xFF\xFEh\x00", None);
+test_sanitize!(test_sanitize_utf16be_rejected, true, b"\xFE\xFF\x00h", None);
+test_sanitize!(test_sanitize_utf32le_rejected, true, b"\xFF\xFE\x00\x00h\x00\x00\x00", None);
+test_sanitize!(test_sanitize_utf16le_bom_only_rejected, true, b"\xFF\xFE", None);
+
+#[test]
+fn test_sanitize_error() {
+    let error = TextFrameSanitizer { reject_utf16: true }.sanitize(b"\xFE\xFF\x00h").unwrap_err();
+    assert_eq!(ErrorKind::InvalidData, error.kind());
+    assert_eq!(Some(BomErrorCode::BomForbidden), BomErrorCode::of(&error));
+    assert_eq!(Some(BomType::UTF16BE), skip_bom::BomError::of(&error).unwrap().bom_type());
+}
+
+#[test]
+fn test_sanitize_str() {
+    let sanitizer = TextFrameSanitizer::default();
+    assert_eq!("hello", sanitizer.sanitize_str("\u{FEFF}hello"));
+    assert_eq!("\u{FEFF}", sanitizer.sanitize_str("\u{FEFF}\u{FEFF}"));
+    assert_eq!("hello\u{FEFF}", sanitizer.sanitize_str("hello\u{FEFF}"));
+}
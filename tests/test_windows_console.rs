@@ -0,0 +1,34 @@
+#![cfg(windows)]
+
+use skip_bom::*;
+use std::io::Read;
+
+fn read_pipe(bytes: &[u8]) -> String {
+    let mut text = String::new();
+    let _ = console_pipe_text_no_bom(bytes).read_to_string(&mut text).unwrap();
+    text
+}
+
+#[test]
+fn test_console_pipe_utf16le() {
+    assert_eq!("dir\r\n", read_pipe(b"\xFF\xFEd\x00i\x00r\x00\r\x00\n\x00"));
+}
+
+#[test]
+fn test_console_pipe_utf8_bom() {
+    assert_eq!("dir\r\n", read_pipe(b"\xEF\xBB\xBFdir\r\n"));
+}
+
+#[test]
+fn test_console_pipe_no_bom() {
+    assert_eq!("dir\r\n", read_pipe(b"dir\r\n"));
+}
+
+#[test]
+fn test_console_pipe_other_bom() {
+    // only the BOMs written by Windows tools are skipped
+    assert_eq!("\u{FEFF}dir", read_pipe(b"\xEF\xBB\xBF\xEF\xBB\xBFdir"));
+    let mut bytes = Vec::new();
+    let _ = console_pipe_text_no_bom(&b"\xFE\xFF\x00d"[..]).read_to_end(&mut bytes).unwrap();
+    assert_eq!(b"\xFE\xFF\x00d", bytes.as_slice());
+}
@@ -0,0 +1,52 @@
+#![cfg(feature = "quick-xml")]
+
+use quick_xml::events::Event;
+use skip_bom::*;
+
+const DOCUMENT: &str = "<?xml version=\"1.0\" encoding=\"UTF-16\"?><root><child>text</child></root>";
+
+fn utf16(string: &str, big_endian: bool) -> Vec<u8> {
+    string.encode_utf16().flat_map(|unit| if big_endian { unit.to_be_bytes() } else { unit.to_le_bytes() }).collect()
+}
+
+fn utf32le(string: &str) -> Vec<u8> {
+    string.chars().flat_map(|c| (c as u32).to_le_bytes()).collect()
+}
+
+/// Read the names of the elements and the text of the document.
+fn read_events(bytes: &[u8]) -> Vec<String> {
+    let mut reader = xml_reader_no_bom(bytes).unwrap();
+    let mut buf = Vec::new();
+    let mut events = Vec::new();
+    loop {
+        match reader.read_event_into(&mut buf).unwrap() {
+            Event::Start(element) => events.push(String::from_utf8(element.name().as_ref().to_vec()).unwrap()),
+            Event::Text(text) => events.push(text.unescape().unwrap().into_owned()),
+            Event::Eof => break events,
+            _ => (),
+        }
+        buf.clear();
+    }
+}
+
+macro_rules! test_xml_reader {
+    ($test_fn_name:ident, $bytes:expr) => {
+        #[test]
+        fn $test_fn_name() {
+            assert_eq!(vec!["root", "child", "text"], read_events(&$bytes));
+        }
+    };
+}
+
+test_xml_reader!(test_xml_reader_utf8, DOCUMENT.as_bytes());
+test_xml_reader!(test_xml_reader_utf8_bom, [&b"\xEF\xBB\xBF"[..], DOCUMENT.as_bytes()].concat());
+test_xml_reader!(test_xml_reader_utf16le_bom, [&b"\xFF\xFE"[..], &utf16(DOCUMENT, false)].concat());
+test_xml_reader!(test_xml_reader_utf16be_bom, [&b"\xFE\xFF"[..], &utf16(DOCUMENT, true)].concat());
+test_xml_reader!(test_xml_reader_utf16le_no_bom, utf16(DOCUMENT, false));
+test_xml_reader!(test_xml_reader_utf16be_no_bom, utf16(DOCUMENT, true));
+test_xml_reader!(test_xml_reader_utf32le_no_bom, utf32le(DOCUMENT));
+
+#[test]
+fn test_xml_reader_empty() {
+    assert!(read_events(b"").is_empty());
+}
@@ -0,0 +1,158 @@
+use skip_bom::*;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::io::{Read, Write};
+
+mod reader_test_utils;
+use reader_test_utils::*;
+
+/// Allocator counting the allocations of the current thread, as tests run in parallel.
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|allocations| allocations.set(allocations.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|allocations| allocations.set(allocations.get() + 1));
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn count_allocations<T, F: FnOnce() -> T>(f: F) -> (T, usize) {
+    let before = ALLOCATIONS.with(Cell::get);
+    let result = f();
+    (result, ALLOCATIONS.with(Cell::get) - before)
+}
+
+/// Read `reader` to the end in chunks of `chunk_len` bytes into a fixed buffer, returning the number of bytes read.
+fn read_fixed<R: Read>(mut reader: R, chunk_len: usize) -> usize {
+    let mut output = [0u8; 256];
+    let mut total = 0;
+    loop {
+        let end = (total + chunk_len).min(output.len());
+        match reader.read(&mut output[total..end]).unwrap() {
+            0 => break total,
+            bytes_read => total += bytes_read,
+        }
+    }
+}
+
+fn stream(bom_type: BomType) -> ([u8; 64], usize) {
+    let mut bytes = [0u8; 64];
+    let bom = bom_type.bom_bytes();
+    bytes[..bom.len()].copy_from_slice(bom);
+    let content = b"the content after the BOM";
+    bytes[bom.len()..bom.len() + content.len()].copy_from_slice(content);
+    (bytes, bom.len() + content.len())
+}
+
+#[test]
+fn test_counting_allocator() {
+    let (_, allocations) = count_allocations(|| vec![0u8; 16]);
+    assert_eq!(1, allocations);
+}
+
+macro_rules! test_zero_alloc {
+    ($test_fn_name:ident, $bom_type:expr) => {
+        #[test]
+        fn $test_fn_name() {
+            let (bytes, len) = stream($bom_type);
+            let bytes = &bytes[..len];
+            for chunk_len in [1, 2, 3, 5, 64] {
+                let (bytes_read, allocations) = count_allocations(|| read_fixed(SkipEncodingBom::new(BomType::all(), bytes), chunk_len));
+                assert_eq!(len - $bom_type.bom_length(), bytes_read);
+                assert_eq!(0, allocations);
+                let (bytes_read, allocations) = count_allocations(|| read_fixed(SkipEncodingBom::new(BomType::all(), ByteByByteReader(bytes)), chunk_len));
+                assert_eq!(len - $bom_type.bom_length(), bytes_read);
+                assert_eq!(0, allocations);
+            }
+        }
+    };
+}
+
+test_zero_alloc!(test_zero_alloc_utf8, BomType::UTF8);
+test_zero_alloc!(test_zero_alloc_utf16le, BomType::UTF16LE);
+test_zero_alloc!(test_zero_alloc_utf16be, BomType::UTF16BE);
+test_zero_alloc!(test_zero_alloc_utf32le, BomType::UTF32LE);
+test_zero_alloc!(test_zero_alloc_utf32be, BomType::UTF32BE);
+test_zero_alloc!(test_zero_alloc_utf7, BomType::UTF7);
+test_zero_alloc!(test_zero_alloc_utf1, BomType::UTF1);
+test_zero_alloc!(test_zero_alloc_utfebdic, BomType::UTFEBDIC);
+test_zero_alloc!(test_zero_alloc_scsu, BomType::SCSU);
+test_zero_alloc!(test_zero_alloc_bocu1, BomType::BOCU1);
+test_zero_alloc!(test_zero_alloc_gb1803, BomType::GB1803);
+
+#[test]
+fn test_zero_alloc_no_bom() {
+    for bytes in [&b""[..], b"\xEF", b"\xFF\xFE\x00", b"no BOM"] {
+        let (bytes_read, allocations) = count_allocations(|| read_fixed(SkipEncodingBom::new(BomType::all(), ByteByByteReader(bytes)), 1));
+        assert!(bytes_read <= bytes.len());
+        assert_eq!(0, allocations);
+    }
+}
+
+#[test]
+fn test_zero_alloc_read_bom() {
+    let ((bom_type, bom_found, status), allocations) = count_allocations(|| {
+        let mut reader = SkipEncodingBom::new(BomType::all(), ByteByByteReader(b"\xFF\xFE\x00\x00text"));
+        (reader.read_bom().unwrap(), reader.bom_found(), reader.status())
+    });
+    assert_eq!(Some(BomType::UTF32LE), bom_type);
+    assert_eq!(Some(Some(BomType::UTF32LE)), bom_found);
+    assert_eq!(BomStatus::Bom(BomType::UTF32LE), status);
+    assert_eq!(0, allocations);
+}
+
+#[test]
+fn test_zero_alloc_read_bom_from() {
+    let (result, allocations) = count_allocations(|| read_bom_from(&mut ByteByByteReader(b"\xEF\xBB\xBFtext"), BomType::all()).unwrap().0);
+    assert_eq!(Some(BomType::UTF8), result);
+    assert_eq!(0, allocations);
+}
+
+#[test]
+fn test_zero_alloc_resolve() {
+    let (bytes_read, allocations) = count_allocations(|| {
+        let mut reader = SkipEncodingBom::new(BomType::all(), ByteByByteReader(b"\xFE\xFF\x00t\x00e"));
+        let resolved = reader.resolve().unwrap().unwrap();
+        read_fixed(resolved, 3)
+    });
+    assert_eq!(4, bytes_read);
+    assert_eq!(0, allocations);
+}
+
+#[test]
+fn test_zero_alloc_strip_bom_writer() {
+    let mut output = [0u8; 64];
+    let (written, allocations) = count_allocations(|| {
+        let mut writer = StripBomWriter::new(BomType::all(), &mut output[..]);
+        for byte in b"\xEF\xBB\xBFtext" {
+            writer.write_all(&[*byte]).unwrap();
+        }
+        64 - writer.finish().unwrap().len()
+    });
+    assert_eq!(4, written);
+    assert_eq!(b"text", &output[..4]);
+    assert_eq!(0, allocations);
+}
+
+#[test]
+fn test_zero_alloc_text_sanitizer() {
+    let (bytes_read, allocations) = count_allocations(|| read_fixed(TextSanitizer::new(BomType::all(), ByteByByteReader(b"\xEF\xBB\xBFa\r\nb\x1A")), 2));
+    assert_eq!(3, bytes_read);
+    assert_eq!(0, allocations);
+}
@@ -0,0 +1,75 @@
+#![cfg(feature = "zeroize")]
+
+use skip_bom::*;
+use std::io::{BufReader, Read};
+use zeroize::Zeroize;
+
+#[test]
+fn test_zeroize_push_buffer() {
+    let mut buffer = BomBytesPushBuffer::from_slice(b"\xEF\xBB\xBF").unwrap();
+    assert_ne!(format!("{:?}", BomBytesPushBuffer::default()), format!("{:?}", buffer));
+    buffer.zeroize();
+    // the bytes held are zeroed, not only forgotten
+    assert_eq!(format!("{:?}", BomBytesPushBuffer::default()), format!("{:?}", buffer));
+    assert!(buffer.is_empty());
+    assert_eq!(BomBytesPushBuffer::CAPACITY, buffer.available_bytes());
+    buffer.push(b"ab").unwrap();
+    assert_eq!(b"ab", buffer.bytes());
+}
+
+macro_rules! test_zeroize_read {
+    ($name:ident, $bytes:expr, $content:expr) => {
+        #[test]
+        fn $name() {
+            // zeroing the buffers replaced by each state transition does not alter the content read
+            for buf_len in 1..=8 {
+                let mut reader = SkipEncodingBom::new(BomType::all(), &$bytes[..]);
+                let mut content = Vec::new();
+                let mut buf = vec![0; buf_len];
+                loop {
+                    match reader.read(&mut buf).unwrap() {
+                        0 => break,
+                        bytes_read => content.extend_from_slice(&buf[..bytes_read]),
+                    }
+                }
+                assert_eq!(&$content[..], content.as_slice());
+                let mut reader = SkipEncodingBomBuf::new(BomType::all(), BufReader::with_capacity(buf_len, &$bytes[..]));
+                content.clear();
+                let _ = reader.read_to_end(&mut content).unwrap();
+                assert_eq!(&$content[..], content.as_slice());
+            }
+        }
+    };
+}
+
+test_zeroize_read!(test_zeroize_read_utf8, b"\xEF\xBB\xBFsecret", b"secret");
+test_zeroize_read!(test_zeroize_read_utf16, b"\xFF\xFEs\x00", b"s\x00");
+test_zeroize_read!(test_zeroize_read_no_bom, b"secret", b"secret");
+
+#[test]
+fn test_zeroize_resolved_bom() {
+    // the guard zeroes its copy of the buffered bytes when dropped, not the bytes returned to the reader
+    let mut reader = SkipEncodingBom::new(BomType::all(), &b"\xEF\xBB\xBFsecret"[..]);
+    {
+        let mut resolved = reader.resolve().unwrap().unwrap();
+        assert_eq!(b"s", resolved.buffered());
+        let mut buf = [0u8; 1];
+        assert_eq!(1, resolved.read(&mut buf).unwrap());
+    }
+    let mut content = Vec::new();
+    let _ = reader.read_to_end(&mut content).unwrap();
+    assert_eq!(b"ecret", content.as_slice());
+    let mut reader = SkipEncodingBom::new(BomType::all(), &b"\xEF\xBB\xBFsecret"[..]);
+    drop(reader.resolve().unwrap().unwrap());
+    content.clear();
+    let _ = reader.read_to_end(&mut content).unwrap();
+    assert_eq!(b"secret", content.as_slice());
+}
+
+#[test]
+fn test_zeroize_kept_bom() {
+    let mut reader = SkipEncodingBom::new(BomType::all(), &b"\xFF\xFEs\x00"[..]).with_bom_kept();
+    let mut content = Vec::new();
+    let _ = reader.read_to_end(&mut content).unwrap();
+    assert_eq!(b"\xFF\xFEs\x00", content.as_slice());
+}
@@ -0,0 +1,53 @@
+#![cfg(feature = "zip")]
+
+use skip_bom::*;
+use std::io::{Cursor, Read, Write};
+
+fn zip_archive(entries: &[(&str, &[u8])]) -> zip::ZipArchive<Cursor<Vec<u8>>> {
+    let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    writer.add_directory("directory/", zip::write::SimpleFileOptions::default()).unwrap();
+    for (name, bytes) in entries {
+        writer.start_file(*name, zip::write::SimpleFileOptions::default()).unwrap();
+        writer.write_all(bytes).unwrap();
+    }
+    zip::ZipArchive::new(writer.finish().unwrap()).unwrap()
+}
+
+#[test]
+fn test_zip_entries_boms() {
+    let mut archive = zip_archive(&[
+        ("utf8.csv", b"\xEF\xBB\xBFa,b"),
+        ("utf16.csv", b"\xFF\xFEa\x00"),
+        ("no_bom.csv", b"a,b"),
+        ("directory/short.csv", b"\xEF"),
+    ]);
+    let mut contents = Vec::new();
+    let boms = for_each_zip_entry(&mut archive, BomType::all(), |name, reader| {
+        let mut buf = Vec::new();
+        let _ = reader.read_to_end(&mut buf)?;
+        contents.push((name.to_owned(), buf));
+        Ok(())
+    }).unwrap();
+    assert_eq!(vec![
+        ("utf8.csv".to_owned(), Some(BomType::UTF8)),
+        ("utf16.csv".to_owned(), Some(BomType::UTF16LE)),
+        ("no_bom.csv".to_owned(), None),
+        ("directory/short.csv".to_owned(), None),
+    ], boms);
+    assert_eq!(("utf8.csv".to_owned(), b"a,b".to_vec()), contents[0]);
+    assert_eq!(("utf16.csv".to_owned(), b"a\x00".to_vec()), contents[1]);
+}
+
+#[test]
+fn test_zip_entries_boms_without_reading() {
+    let mut archive = zip_archive(&[("utf8.csv", b"\xEF\xBB\xBFa,b")]);
+    let boms = for_each_zip_entry(&mut archive, &[BomType::UTF8], |_, _| Ok(())).unwrap();
+    assert_eq!(vec![("utf8.csv".to_owned(), Some(BomType::UTF8))], boms);
+}
+
+#[test]
+fn test_zip_entries_error() {
+    let mut archive = zip_archive(&[("a.csv", b"a"), ("b.csv", b"b")]);
+    let result = for_each_zip_entry(&mut archive, BomType::all(), |_, _| Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "stop")));
+    assert_eq!("stop", result.unwrap_err().to_string());
+}
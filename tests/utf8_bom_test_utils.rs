@@ -1,3 +1,5 @@
+#![allow(clippy::needless_lifetimes, clippy::redundant_static_lifetimes)]
+
 use std::io::{Cursor, Read};
 
 use skip_bom::{BomType, SkipEncodingBom};